@@ -0,0 +1,144 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+// WADs and PK3s behind one interface, for code that just needs to find and update lumps
+// lumps are addressed by index, WADs can have several lumps with the same name
+
+use std::borrow::Cow;
+use std::path::Path;
+use super::pk3::{self, Namespace, Pk3};
+use super::wad::{Wad, WadType};
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum ArchiveType {
+    Wad,
+    Pk3
+}
+
+impl ArchiveType {
+    // by extension, anything else isn't an archive
+    pub fn from_path(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "wad" => Some(ArchiveType::Wad),
+            "pk3" | "pk7" | "pke" | "ipk3" | "zip" => Some(ArchiveType::Pk3),
+            _ => None
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub enum Archive {
+    Wad(Wad),
+    Pk3(Pk3)
+}
+
+impl Archive {
+    // a new, empty archive of the type the path is for
+    pub fn new(path: &Path) -> Result<Self, String> {
+        match ArchiveType::from_path(path) {
+            Some(ArchiveType::Wad) => Ok(Archive::Wad(Wad::new(WadType::PWAD))),
+            Some(ArchiveType::Pk3) => Ok(Archive::Pk3(Pk3::new())),
+            None => Err(format!("{} isn't a WAD or PK3", path.display()))
+        }
+    }
+
+    pub fn open(path: &Path) -> Result<Self, String> {
+        match ArchiveType::from_path(path) {
+            Some(ArchiveType::Wad) => Wad::open(path).map(Archive::Wad),
+            Some(ArchiveType::Pk3) => Pk3::open(path).map(Archive::Pk3),
+            None => Err(format!("{} isn't a WAD or PK3", path.display()))
+        }
+    }
+
+    pub fn archive_type(&self) -> ArchiveType {
+        match self {
+            Archive::Wad(_) => ArchiveType::Wad,
+            Archive::Pk3(_) => ArchiveType::Pk3,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        match self {
+            Archive::Wad(wad) => wad.lumps().len(),
+            Archive::Pk3(pk3) => pk3.len(),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    // the full path for PK3 entries
+    pub fn name(&self, idx: usize) -> Option<&str> {
+        match self {
+            Archive::Wad(wad) => wad.lumps().get(idx).map(|lump| lump.name.as_str()),
+            Archive::Pk3(pk3) => pk3.name(idx),
+        }
+    }
+
+    // the name other lumps refer to this one by
+    pub fn lump_name(&self, idx: usize) -> Option<&str> {
+        match self {
+            Archive::Wad(_) => self.name(idx),
+            Archive::Pk3(pk3) => pk3.name(idx).map(pk3::short_name),
+        }
+    }
+
+    pub fn lump_data(&self, idx: usize) -> Option<Cow<'_, [u8]>> {
+        match self {
+            Archive::Wad(wad) => wad.lump_data(idx).map(Cow::Borrowed),
+            Archive::Pk3(pk3) if pk3.is_directory(idx) => None,
+            Archive::Pk3(pk3) => pk3.read_index(idx).ok().map(Cow::Owned),
+        }
+    }
+
+    pub fn find(&self, name: &str) -> Option<usize> {
+        match self {
+            Archive::Wad(wad) => wad.find(name),
+            Archive::Pk3(pk3) => pk3.find(name),
+        }
+    }
+
+    pub fn read(&self, name: &str) -> Option<Cow<'_, [u8]>> {
+        self.lump_data(self.find(name)?)
+    }
+
+    // WADs have no folders, so the namespace only matters for PK3s
+    pub fn find_in(&self, name: &str, namespace: Namespace) -> Option<usize> {
+        match self {
+            Archive::Wad(wad) => wad.find(name),
+            Archive::Pk3(pk3) => pk3.find_in(name, namespace),
+        }
+    }
+
+    pub fn read_in(&self, name: &str, namespace: Namespace) -> Option<Cow<'_, [u8]>> {
+        self.lump_data(self.find_in(name, namespace)?)
+    }
+
+    // replaces the lump the name finds, or adds a new one
+    pub fn set(&mut self, name: &str, data: Vec<u8>) -> Result<usize, String> {
+        match self {
+            Archive::Wad(wad) => wad.set(name, data),
+            Archive::Pk3(pk3) => pk3.set(name, data),
+        }
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        match self {
+            Archive::Wad(wad) => wad.save(path),
+            Archive::Pk3(pk3) => pk3.save(path),
+        }
+    }
+}
@@ -0,0 +1,452 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+// doom's graphics formats, decoded into RGBA for previews
+// PLAYPAL: 14 palettes of 256 RGB colours, COLORMAP: 34 maps of 256 palette indexes for light levels
+// flats: raw palette indexes, 64x64 in vanilla
+// patches: u16 width, u16 height, i16 left offset, i16 top offset, a u32 offset for each column,
+// then each column is a list of posts: u8 top delta, u8 length, a padding byte, the pixels and another padding byte,
+// ended by a top delta of 255
+// PK3s can use PNGs anywhere a patch or flat goes, with offsets in a grAb chunk
+
+use std::io::Cursor;
+use super::pk3::Namespace;
+use super::resources::ResourceStack;
+
+pub const PALETTE_COLORS: usize = 256;
+const PALETTE_SIZE: usize = PALETTE_COLORS * 3;
+const PATCH_HEADER_SIZE: usize = 8;
+const POST_END: u8 = 0xFF;
+const PNG_SIGNATURE: &[u8] = b"\x89PNG\r\n\x1a\n";
+
+pub type Palette = [[u8; 3]; PALETTE_COLORS];
+pub type Colormap = [u8; PALETTE_COLORS];
+
+// each palette in PLAYPAL, the first one is the normal one
+pub fn read_playpal(data: &[u8]) -> Result<Vec<Palette>, String> {
+    if data.is_empty() || !data.len().is_multiple_of(PALETTE_SIZE) {
+        return Err(format!("PLAYPAL should be a multiple of {PALETTE_SIZE} bytes long, not {}", data.len()));
+    }
+    Ok(data.chunks_exact(PALETTE_SIZE)
+        .map(|palette| std::array::from_fn(|idx| [palette[idx * 3], palette[idx * 3 + 1], palette[idx * 3 + 2]]))
+        .collect())
+}
+
+// each light level in COLORMAP, from brightest to darkest, followed by the invulnerability map
+pub fn read_colormap(data: &[u8]) -> Result<Vec<Colormap>, String> {
+    if data.is_empty() || !data.len().is_multiple_of(PALETTE_COLORS) {
+        return Err(format!("COLORMAP should be a multiple of {PALETTE_COLORS} bytes long, not {}", data.len()));
+    }
+    Ok(data.chunks_exact(PALETTE_COLORS)
+        .map(|map| map.try_into().expect("chunks are PALETTE_COLORS bytes long"))
+        .collect())
+}
+
+// palette indexes before a palette is applied, None is transparent
+#[derive(Clone, PartialEq, Debug)]
+pub struct IndexedImage {
+    pub width: usize,
+    pub height: usize,
+    pub left_offset: i32,
+    pub top_offset: i32,
+    pub pixels: Vec<Option<u8>>
+}
+
+impl IndexedImage {
+    pub fn get(&self, x: usize, y: usize) -> Option<u8> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        self.pixels[y * self.width + x]
+    }
+
+    // for translations and colormaps, which are both tables of 256 palette indexes
+    pub fn remap(&mut self, table: &[u8]) {
+        for index in self.pixels.iter_mut().flatten() {
+            if let Some(new) = table.get(usize::from(*index)) {
+                *index = *new;
+            }
+        }
+    }
+
+    pub fn to_rgba(&self, palette: &Palette) -> Image {
+        Image {
+            width: self.width,
+            height: self.height,
+            left_offset: self.left_offset,
+            top_offset: self.top_offset,
+            pixels: self.pixels
+                .iter()
+                .flat_map(|pixel| match pixel {
+                    Some(index) => {
+                        let [r, g, b] = palette[usize::from(*index)];
+                        [r, g, b, 0xFF]
+                    },
+                    None => [0; 4]
+                })
+                .collect()
+        }
+    }
+}
+
+// 4 bytes per pixel, rows top to bottom
+#[derive(Clone, PartialEq, Debug)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    pub left_offset: i32,
+    pub top_offset: i32,
+    pub pixels: Vec<u8>
+}
+
+impl Image {
+    // fully transparent
+    pub fn new(width: usize, height: usize) -> Self {
+        Self {
+            width,
+            height,
+            left_offset: 0,
+            top_offset: 0,
+            pixels: vec![0; width * height * 4]
+        }
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<[u8; 4]> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let offset = (y * self.width + x) * 4;
+        self.pixels[offset..offset + 4].try_into().ok()
+    }
+
+    pub fn set(&mut self, x: usize, y: usize, pixel: [u8; 4]) {
+        if x < self.width && y < self.height {
+            let offset = (y * self.width + x) * 4;
+            self.pixels[offset..offset + 4].copy_from_slice(&pixel);
+        }
+    }
+
+    // draws other with its top left corner at x, y, ignoring its offsets
+    // doom graphics are either transparent or not, so anything that isn't fully transparent is drawn as is
+    pub fn draw(&mut self, other: &Image, x: i32, y: i32) {
+        for other_y in 0..other.height {
+            let Some(dest_y) = offset_coordinate(y, other_y) else {
+                continue;
+            };
+            for other_x in 0..other.width {
+                if let Some(dest_x) = offset_coordinate(x, other_x)
+                    && let Some(pixel) = other.get(other_x, other_y)
+                    && pixel[3] != 0 {
+                    self.set(dest_x, dest_y, pixel);
+                }
+            }
+        }
+    }
+}
+
+fn offset_coordinate(origin: i32, offset: usize) -> Option<usize> {
+    usize::try_from(i64::from(origin) + i64::try_from(offset).ok()?).ok()
+}
+
+fn read_u16(data: &[u8], offset: usize) -> Option<u16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(u16::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+pub fn read_patch(data: &[u8]) -> Result<IndexedImage, String> {
+    let (Some(width), Some(height), Some(left_offset), Some(top_offset)) =
+        (read_u16(data, 0), read_u16(data, 2), read_u16(data, 4), read_u16(data, 6)) else {
+        return Err("patch is too short to have a header".to_owned());
+    };
+    let (width, height) = (usize::from(width), usize::from(height));
+    if width == 0 || height == 0 || data.len() < PATCH_HEADER_SIZE + width * 4 {
+        return Err(format!("patch is {width}x{height}, which doesn't fit in {} bytes", data.len()));
+    }
+    let mut pixels = vec![None; width * height];
+    for x in 0..width {
+        let mut offset = read_u32(data, PATCH_HEADER_SIZE + x * 4)
+            .and_then(|offset| usize::try_from(offset).ok())
+            .expect("the column offsets were checked to fit");
+        // tall patches go past 254 by making each top delta relative to the last one when it doesn't go down
+        let mut last_top: Option<usize> = None;
+        loop {
+            let Some(&delta) = data.get(offset) else {
+                return Err(format!("column {x} runs past the end of the patch"));
+            };
+            if delta == POST_END {
+                break;
+            }
+            let delta = usize::from(delta);
+            let top = match last_top {
+                Some(last) if delta <= last => last + delta,
+                _ => delta
+            };
+            last_top = Some(top);
+            let Some(&length) = data.get(offset + 1) else {
+                return Err(format!("column {x} runs past the end of the patch"));
+            };
+            let length = usize::from(length);
+            let Some(post) = data.get(offset + 3..offset + 3 + length) else {
+                return Err(format!("column {x} runs past the end of the patch"));
+            };
+            // posts that hang off the bottom are cut off like in vanilla
+            for (y, index) in (top..height).zip(post) {
+                pixels[y * width + x] = Some(*index);
+            }
+            offset += length + 4;
+        }
+    }
+    Ok(IndexedImage {
+        width,
+        height,
+        left_offset: i32::from(left_offset.cast_signed()),
+        top_offset: i32::from(top_offset.cast_signed()),
+        pixels
+    })
+}
+
+// flats don't have a header, so only square ones can be read, 64x64 in vanilla and larger ones in some ports
+pub fn read_flat(data: &[u8]) -> Result<IndexedImage, String> {
+    let size = data.len().isqrt();
+    if size == 0 || size * size != data.len() {
+        return Err(format!("flat is {} bytes, which isn't a square", data.len()));
+    }
+    Ok(IndexedImage {
+        width: size,
+        height: size,
+        left_offset: 0,
+        top_offset: 0,
+        pixels: data.iter().copied().map(Some).collect()
+    })
+}
+
+pub fn is_png(data: &[u8]) -> bool {
+    data.starts_with(PNG_SIGNATURE)
+}
+
+// the offsets ZDoom stores in a grAb chunk, big endian like the rest of the PNG
+fn png_offsets(data: &[u8]) -> Option<(i32, i32)> {
+    let mut offset = PNG_SIGNATURE.len();
+    while let Some(header) = data.get(offset..offset + 8) {
+        let length = usize::try_from(u32::from_be_bytes(header[..4].try_into().ok()?)).ok()?;
+        let chunk = data.get(offset + 8..offset + 8 + length)?;
+        match &header[4..] {
+            b"grAb" if length == 8 => return Some((
+                i32::from_be_bytes(chunk[..4].try_into().ok()?),
+                i32::from_be_bytes(chunk[4..].try_into().ok()?)
+            )),
+            b"IDAT" | b"IEND" => return None,
+            _ => offset += length + 12
+        }
+    }
+    None
+}
+
+pub fn read_png(data: &[u8]) -> Result<Image, String> {
+    let mut decoder = png::Decoder::new(Cursor::new(data));
+    decoder.set_transformations(png::Transformations::normalize_to_color8());
+    let mut reader = decoder.read_info().map_err(|why| format!("failed to read PNG: {why}"))?;
+    let size = reader.output_buffer_size().ok_or("PNG is too big")?;
+    let mut buffer = vec![0; size];
+    let info = reader.next_frame(&mut buffer).map_err(|why| format!("failed to read PNG: {why}"))?;
+    let buffer = &buffer[..info.buffer_size()];
+    let pixels = match info.color_type {
+        png::ColorType::Rgba => buffer.to_vec(),
+        png::ColorType::Rgb => buffer.chunks_exact(3).flat_map(|rgb| [rgb[0], rgb[1], rgb[2], 0xFF]).collect(),
+        png::ColorType::GrayscaleAlpha => buffer.chunks_exact(2).flat_map(|ga| [ga[0], ga[0], ga[0], ga[1]]).collect(),
+        png::ColorType::Grayscale => buffer.iter().flat_map(|g| [*g, *g, *g, 0xFF]).collect(),
+        png::ColorType::Indexed => return Err("PNG palette wasn't expanded".to_owned()),
+    };
+    let (left_offset, top_offset) = png_offsets(data).unwrap_or_default();
+    Ok(Image {
+        width: usize::try_from(info.width).map_err(|_| "PNG is too wide")?,
+        height: usize::try_from(info.height).map_err(|_| "PNG is too tall")?,
+        left_offset,
+        top_offset,
+        pixels
+    })
+}
+
+// anything that can be drawn where a patch goes
+pub fn read_graphic(data: &[u8], palette: &Palette) -> Result<Image, String> {
+    if is_png(data) {
+        read_png(data)
+    } else {
+        read_patch(data).map(|patch| patch.to_rgba(palette))
+    }
+}
+
+// the normal palette from whichever file loads PLAYPAL last
+pub fn load_palette(resources: &ResourceStack) -> Result<Palette, String> {
+    let data = resources.read("PLAYPAL").ok_or("there's no PLAYPAL")?;
+    read_playpal(&data).map(|palettes| palettes[0])
+}
+
+pub fn load_graphic(resources: &ResourceStack, name: &str, palette: &Palette) -> Result<Image, String> {
+    let data = resources.read_in(name, Namespace::Graphics).ok_or_else(|| format!("there's no graphic named {name}"))?;
+    read_graphic(&data, palette).map_err(|why| format!("{name}: {why}"))
+}
+
+pub fn load_flat(resources: &ResourceStack, name: &str, palette: &Palette) -> Result<Image, String> {
+    let data = resources.read_in(name, Namespace::Flats).ok_or_else(|| format!("there's no flat named {name}"))?;
+    let image = if is_png(&data) { read_png(&data) } else { read_flat(&data).map(|flat| flat.to_rgba(palette)) };
+    image.map_err(|why| format!("{name}: {why}"))
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    // a gradient, so every index has a different colour
+    pub(crate) fn test_palette() -> Palette {
+        std::array::from_fn(|idx| {
+            let idx = u8::try_from(idx).unwrap();
+            [idx, 255 - idx, idx / 2]
+        })
+    }
+    // each column is a list of (top delta, pixels)
+    pub(crate) fn build_patch(height: u16, offsets: (i16, i16), columns: &[&[(u8, &[u8])]]) -> Vec<u8> {
+        let width = u16::try_from(columns.len()).unwrap();
+        let mut data = Vec::new();
+        for value in [width, height, offsets.0.cast_unsigned(), offsets.1.cast_unsigned()] {
+            data.extend(value.to_le_bytes());
+        }
+        let mut column_data = Vec::new();
+        for column in columns {
+            let offset = PATCH_HEADER_SIZE + columns.len() * 4 + column_data.len();
+            data.extend(u32::try_from(offset).unwrap().to_le_bytes());
+            for (delta, pixels) in *column {
+                column_data.extend([*delta, u8::try_from(pixels.len()).unwrap(), 0]);
+                column_data.extend(*pixels);
+                column_data.push(0);
+            }
+            column_data.push(POST_END);
+        }
+        data.extend(column_data);
+        data
+    }
+    #[test]
+    fn read_palettes() {
+        let palette = test_palette();
+        let data: Vec<u8> = palette.iter().flatten().copied().cycle().take(PALETTE_SIZE * 14).collect();
+        let palettes = read_playpal(&data).unwrap();
+        assert_eq!(palettes.len(), 14);
+        assert_eq!(palettes[13][200], [200, 55, 100]);
+        assert!(read_playpal(&data[1..]).is_err());
+
+        // each light level is one index darker than the last
+        let data: Vec<u8> = (0..34u8)
+            .flat_map(|level| (0..=255u8).map(move |index| index.saturating_sub(level)))
+            .collect();
+        let colormaps = read_colormap(&data).unwrap();
+        assert_eq!(colormaps.len(), 34);
+        assert_eq!(colormaps[0][100], 100);
+        assert_eq!(colormaps[10][100], 90);
+        assert!(read_colormap(&[]).is_err());
+    }
+    #[test]
+    fn read_patches() {
+        let data = build_patch(4, (1, -2), &[
+            &[(0, &[1, 2])],
+            &[],
+            &[(1, &[3]), (3, &[4, 5])],
+        ]);
+        let patch = read_patch(&data).unwrap();
+        assert_eq!((patch.width, patch.height, patch.left_offset, patch.top_offset), (3, 4, 1, -2));
+        assert_eq!(patch.pixels, vec![
+            Some(1), None, None,
+            Some(2), None, Some(3),
+            None, None, None,
+            None, None, Some(4),
+        ]);
+        let image = patch.to_rgba(&test_palette());
+        assert_eq!(image.get(2, 1), Some([3, 252, 1, 0xFF]));
+        assert_eq!(image.get(1, 1), Some([0; 4]));
+
+        let mut translated = patch.clone();
+        translated.remap(&std::array::from_fn::<u8, 256, _>(|idx| u8::try_from(idx).unwrap().wrapping_add(10)));
+        assert_eq!(translated.get(0, 0), Some(11));
+
+        assert!(read_patch(&data[..data.len() - 3]).is_err());
+        assert!(read_patch(&data[..10]).is_err());
+    }
+    #[test]
+    fn draw_images() {
+        let patch = read_patch(&build_patch(2, (0, 0), &[&[(0, &[1, 2])], &[(1, &[3])]])).unwrap();
+        let patch = patch.to_rgba(&test_palette());
+        let mut image = Image::new(3, 3);
+        image.draw(&patch, 2, -1);
+        image.draw(&patch, -1, 1);
+        assert_eq!(image.get(2, 0), Some([2, 253, 1, 0xFF]));
+        assert_eq!(image.get(0, 2), Some([3, 252, 1, 0xFF]));
+        // the transparent pixel at the top of the second column doesn't cover anything
+        assert_eq!(image.get(0, 1), Some([0; 4]));
+        assert_eq!(image.pixels.iter().filter(|value| **value == 0xFF).count(), 2);
+    }
+    #[test]
+    fn read_tall_patch() {
+        // the second post starts at 254 + 10, past what a byte can hold
+        let pixels = [7; 254];
+        let data = build_patch(300, (0, 0), &[&[(0, &pixels), (254, &[1]), (10, &[2])]]);
+        let patch = read_patch(&data).unwrap();
+        assert_eq!(patch.get(0, 253), Some(7));
+        assert_eq!(patch.get(0, 254), Some(1));
+        assert_eq!(patch.get(0, 264), Some(2));
+        assert_eq!(patch.get(0, 265), None);
+    }
+    #[test]
+    fn load_from_resources() {
+        use super::super::archive::Archive;
+        use super::super::wad::{test::build_wad, Wad};
+        let playpal: Vec<u8> = test_palette().iter().flatten().copied().collect();
+        let patch = build_patch(1, (0, 0), &[&[(0, &[40])]]);
+        let wad = build_wad(b"IWAD", &[("PLAYPAL", &playpal), ("STBAR", &patch), ("FLOOR0_1", &[9; 4096])]);
+        let mut resources = ResourceStack::new();
+        resources.push("doom2.wad".into(), Archive::Wad(Wad::from_bytes(wad).unwrap()));
+
+        let palette = load_palette(&resources).unwrap();
+        assert_eq!(load_graphic(&resources, "STBAR", &palette).unwrap().get(0, 0), Some([40, 215, 20, 0xFF]));
+        assert_eq!(load_flat(&resources, "FLOOR0_1", &palette).unwrap().get(63, 63), Some([9, 246, 4, 0xFF]));
+        assert!(load_graphic(&resources, "STTNUM0", &palette).is_err());
+    }
+    #[test]
+    fn read_flats() {
+        let data: Vec<u8> = (0..4096).map(|idx| u8::try_from(idx % 64).unwrap()).collect();
+        let flat = read_flat(&data).unwrap();
+        assert_eq!((flat.width, flat.height), (64, 64));
+        assert_eq!(flat.get(63, 10), Some(63));
+        assert!(read_flat(&data[..4000]).is_err());
+    }
+    #[test]
+    fn read_pngs() {
+        let mut data = Vec::new();
+        let mut encoder = png::Encoder::new(&mut data, 2, 1);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_chunk(png::chunk::ChunkType(*b"grAb"), &[0, 0, 0, 5, 0xFF, 0xFF, 0xFF, 0xFE]).unwrap();
+        writer.write_image_data(&[255, 0, 0, 0, 0, 255]).unwrap();
+        writer.finish().unwrap();
+
+        assert!(is_png(&data));
+        let image = read_graphic(&data, &test_palette()).unwrap();
+        assert_eq!((image.width, image.height, image.left_offset, image.top_offset), (2, 1, 5, -2));
+        assert_eq!(image.pixels, vec![255, 0, 0, 255, 0, 0, 255, 255]);
+        assert!(read_png(&data[..20]).is_err());
+    }
+}
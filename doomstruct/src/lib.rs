@@ -0,0 +1,43 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+// reading (and eventually writing) the files doom data lives in, loosely following MTrop's DoomStruct
+// only covers what the editor needs: finding lumps referenced by json and the assets for graphical editors
+
+pub mod wad;
+pub mod pk3;
+pub mod archive;
+pub mod resources;
+pub mod textures;
+pub mod graphics;
+pub mod sky;
+
+use std::path::Path;
+
+// written to a temporary file next to path first, so a failed save can't leave a broken file behind
+pub(crate) fn write_atomically(path: &Path, data: &[u8]) -> Result<(), String> {
+    let mut temp_name = path.file_name().unwrap_or_default().to_owned();
+    temp_name.push(".tmp");
+    let temp_path = path.with_file_name(temp_name);
+    let write = || -> std::io::Result<()> {
+        let mut file = std::fs::File::create(&temp_path)?;
+        std::io::Write::write_all(&mut file, data)?;
+        file.sync_all()?;
+        std::fs::rename(&temp_path, path)
+    };
+    write().map_err(|why| {
+        let _ = std::fs::remove_file(&temp_path);
+        format!("failed to write {}: {why}", path.display())
+    })
+}
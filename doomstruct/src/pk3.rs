@@ -0,0 +1,314 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+// PK3s are zip files, every file in them is a lump named by its path, e.g. filter/doom.id/skydefs.json
+// files in the root and in the namespace folders are also found by their lump name, the file name up to the first dot
+
+use std::io::{Cursor, Read, Write};
+use std::path::Path;
+use std::sync::Arc;
+use zip::{CompressionMethod, ZipArchive, ZipWriter};
+use zip::write::SimpleFileOptions;
+
+#[derive(Clone, PartialEq, Debug)]
+struct Entry {
+    name: String,
+    // None until the entry is replaced, then it's read from data
+    data: Option<Vec<u8>>
+}
+
+// the whole file is kept in memory, entries are decompressed when read
+#[derive(Clone, Debug)]
+pub struct Pk3 {
+    entries: Vec<Entry>,
+    // the zip as read, entries before this are at the same index in it
+    original_count: usize,
+    // the central directory is only parsed once, clones share it and the file's bytes
+    archive: Option<ZipArchive<Cursor<Arc<[u8]>>>>
+}
+
+// the lump name of a path, e.g. SKYDEFS for skydefs.json
+pub fn short_name(path: &str) -> &str {
+    let file_name = path.rsplit('/').next().unwrap_or(path);
+    file_name.split('.').next().unwrap_or(file_name)
+}
+
+// the folders ZDoom style PK3s sort lumps into, which one is searched depends on what the lump is used as
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum Namespace {
+    // only the root
+    Global,
+    Graphics,
+    Flats,
+    Patches,
+    Sprites,
+    Music,
+    Textures
+}
+
+impl Namespace {
+    // searched in order, subfolders included
+    pub fn folders(self) -> &'static [&'static str] {
+        match self {
+            Namespace::Global   => &[],
+            // patches can be used anywhere a graphic can
+            Namespace::Graphics => &["graphics", "patches"],
+            Namespace::Flats    => &["flats"],
+            Namespace::Patches  => &["patches"],
+            Namespace::Sprites  => &["sprites"],
+            Namespace::Music    => &["music"],
+            Namespace::Textures => &["textures"]
+        }
+    }
+}
+
+fn in_folder(path: &str, folder: &str) -> bool {
+    path.split_once('/').is_some_and(|(top, _)| top.eq_ignore_ascii_case(folder))
+}
+
+fn zip_error(why: zip::result::ZipError) -> String {
+    format!("invalid PK3: {why}")
+}
+
+impl Pk3 {
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+            original_count: 0,
+            archive: None
+        }
+    }
+
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let data = std::fs::read(path).map_err(|why| format!("failed to open {}: {why}", path.display()))?;
+        Self::from_bytes(data).map_err(|why| format!("{}: {why}", path.display()))
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, String> {
+        let archive = ZipArchive::new(Cursor::new(Arc::from(data))).map_err(zip_error)?;
+        let entries: Vec<_> = (0..archive.len())
+            .filter_map(|idx| archive.name_for_index(idx))
+            .map(|name| Entry { name: name.to_owned(), data: None })
+            .collect();
+        Ok(Self { original_count: entries.len(), entries, archive: Some(archive) })
+    }
+
+    // reading needs its own handle to seek with, cloning one doesn't parse the zip again
+    fn archive(&self) -> Result<ZipArchive<Cursor<Arc<[u8]>>>, String> {
+        self.archive.clone().ok_or_else(|| "the PK3 wasn't read from a file".to_owned())
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn name(&self, idx: usize) -> Option<&str> {
+        self.entries.get(idx).map(|entry| entry.name.as_str())
+    }
+
+    pub fn is_directory(&self, idx: usize) -> bool {
+        self.name(idx).is_some_and(|name| name.ends_with('/'))
+    }
+
+    pub fn read_index(&self, idx: usize) -> Result<Vec<u8>, String> {
+        let entry = self.entries.get(idx).ok_or_else(|| format!("there is no entry {idx}"))?;
+        if let Some(data) = &entry.data {
+            return Ok(data.clone());
+        }
+        let mut archive = self.archive()?;
+        let mut file = archive.by_index(idx).map_err(zip_error)?;
+        let mut data = Vec::new();
+        file.read_to_end(&mut data).map_err(|why| format!("failed to read {}: {why}", entry.name))?;
+        Ok(data)
+    }
+
+    // a path matches exactly, ignoring case, and a bare lump name matches any file in the root with that name
+    // like WADs the last match wins
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.find_in(name, Namespace::Global)
+    }
+
+    // like find, but a bare lump name is looked for in the namespace's folders before the root
+    pub fn find_in(&self, name: &str, namespace: Namespace) -> Option<usize> {
+        if let Some(idx) = self.entries.iter().rposition(|entry| entry.name.eq_ignore_ascii_case(name)) {
+            return Some(idx);
+        }
+        if name.contains('/') {
+            return None;
+        }
+        let named = |entry: &Entry| !entry.name.ends_with('/') && short_name(&entry.name).eq_ignore_ascii_case(short_name(name));
+        namespace.folders()
+            .iter()
+            .find_map(|folder| self.entries.iter().rposition(|entry| in_folder(&entry.name, folder) && named(entry)))
+            .or_else(|| self.entries.iter().rposition(|entry| !entry.name.contains('/') && named(entry)))
+    }
+
+    pub fn read(&self, name: &str) -> Option<Vec<u8>> {
+        self.read_index(self.find(name)?).ok()
+    }
+
+    // replaces the entry the name finds if there is one, otherwise adds an entry with exactly that path
+    pub fn set(&mut self, name: &str, data: Vec<u8>) -> Result<usize, String> {
+        if name.is_empty() || name.ends_with('/') {
+            return Err(format!("'{name}' isn't a file name"));
+        }
+        match self.find(name) {
+            Some(idx) => {
+                self.entries[idx].data = Some(data);
+                Ok(idx)
+            },
+            None => {
+                self.entries.push(Entry { name: name.to_owned(), data: Some(data) });
+                Ok(self.entries.len() - 1)
+            }
+        }
+    }
+
+    // untouched entries are copied without being decompressed, so they come out byte for byte the same
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut archive = if self.original_count > 0 { Some(self.archive()?) } else { None };
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (idx, entry) in self.entries.iter().enumerate() {
+            let original = match archive.as_mut() {
+                Some(archive) if idx < self.original_count => Some(archive.by_index_raw(idx).map_err(zip_error)?),
+                _ => None
+            };
+            match (&entry.data, original) {
+                (None, Some(file)) => writer.raw_copy_file(file).map_err(zip_error)?,
+                (Some(data), original) => {
+                    // keep the compression the entry already had, stored entries are sometimes required
+                    let compression = match original.map(|file| file.compression()) {
+                        Some(CompressionMethod::Stored) => CompressionMethod::Stored,
+                        _ => CompressionMethod::Deflated
+                    };
+                    writer.start_file(entry.name.as_str(), SimpleFileOptions::default().compression_method(compression))
+                        .map_err(zip_error)?;
+                    writer.write_all(data).map_err(|why| format!("failed to write {}: {why}", entry.name))?;
+                },
+                (None, None) => return Err(format!("entry {} has no data", entry.name))
+            }
+        }
+        Ok(writer.finish().map_err(zip_error)?.into_inner())
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        super::write_atomically(path, &self.to_bytes()?)
+    }
+}
+
+impl Default for Pk3 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    pub(crate) fn build_pk3(files: &[(&str, &[u8], CompressionMethod)]) -> Vec<u8> {
+        let mut writer = ZipWriter::new(Cursor::new(Vec::new()));
+        for (name, data, compression) in files {
+            if name.ends_with('/') {
+                writer.add_directory(*name, SimpleFileOptions::default()).unwrap();
+                continue;
+            }
+            writer.start_file(*name, SimpleFileOptions::default().compression_method(*compression)).unwrap();
+            writer.write_all(data).unwrap();
+        }
+        writer.finish().unwrap().into_inner()
+    }
+    #[test]
+    fn short_names() {
+        assert_eq!(short_name("skydefs.json"), "skydefs");
+        assert_eq!(short_name("filter/doom.id/SBARDEF.json"), "SBARDEF");
+        assert_eq!(short_name("TRAKINFO"), "TRAKINFO");
+    }
+    #[test]
+    fn read_pk3() {
+        let pk3 = Pk3::from_bytes(build_pk3(&[
+            ("skydefs.json", b"{ \"type\": \"skydefs\" }", CompressionMethod::Deflated),
+            ("filter/", b"", CompressionMethod::Stored),
+            ("filter/gameconf.json", b"gameconf", CompressionMethod::Stored),
+            ("interlevel/intermission_e1.json", b"interlevel", CompressionMethod::Deflated),
+        ])).unwrap();
+        assert_eq!(pk3.len(), 4);
+        assert!(pk3.is_directory(1));
+        assert_eq!(pk3.read("SKYDEFS"), Some(b"{ \"type\": \"skydefs\" }".to_vec()));
+        assert_eq!(pk3.read("Filter/GameConf.json"), Some(b"gameconf".to_vec()));
+        // without a namespace only root files are found by their lump name
+        assert_eq!(pk3.find("GAMECONF"), None);
+        assert_eq!(pk3.find("intermission_e1"), None);
+        assert_eq!(pk3.find("interlevel/intermission_e1.json"), Some(3));
+    }
+    #[test]
+    fn find_in_namespaces() {
+        let pk3 = Pk3::from_bytes(build_pk3(&[
+            ("stbar.lmp", b"root", CompressionMethod::Stored),
+            ("graphics/", b"", CompressionMethod::Stored),
+            ("graphics/stbar.png", b"graphic", CompressionMethod::Stored),
+            ("graphics/hud/stfst01.png", b"face", CompressionMethod::Stored),
+            ("flats/grnrock.png", b"flat", CompressionMethod::Stored),
+            ("music/d_runnin.ogg", b"music", CompressionMethod::Stored),
+            ("patches/wall00_1.png", b"patch", CompressionMethod::Stored),
+        ])).unwrap();
+        assert_eq!(pk3.find_in("STBAR", Namespace::Graphics), Some(2));
+        assert_eq!(pk3.find_in("STFST01", Namespace::Graphics), Some(3));
+        // patches are graphics too
+        assert_eq!(pk3.find_in("WALL00_1", Namespace::Graphics), Some(6));
+        assert_eq!(pk3.find_in("GRNROCK", Namespace::Flats), Some(4));
+        assert_eq!(pk3.find_in("D_RUNNIN", Namespace::Music), Some(5));
+        // the folder is picked by what the lump is used as, with the root as the last resort
+        assert_eq!(pk3.find_in("GRNROCK", Namespace::Graphics), None);
+        assert_eq!(pk3.find_in("STBAR", Namespace::Flats), Some(0));
+        assert_eq!(pk3.find("STBAR"), Some(0));
+        // a directory isn't a lump
+        assert_eq!(pk3.find_in("graphics", Namespace::Global), None);
+    }
+    #[test]
+    fn write_pk3() {
+        let original = build_pk3(&[
+            ("skydefs.json", b"old skies", CompressionMethod::Deflated),
+            ("sprites/", b"", CompressionMethod::Stored),
+            ("sprites/trooa1.png", &[0x89, b'P', b'N', b'G', 0, 1, 2, 3], CompressionMethod::Stored),
+            ("textures.txt", b"texture textures texture", CompressionMethod::Deflated),
+        ]);
+        let mut pk3 = Pk3::from_bytes(original.clone()).unwrap();
+        assert_eq!(pk3.set("SKYDEFS", b"new skies".to_vec()), Ok(0));
+        assert_eq!(pk3.set("demoloop.json", b"demos".to_vec()), Ok(4));
+        assert!(pk3.set("sprites/", Vec::new()).is_err());
+        let written = pk3.to_bytes().unwrap();
+        let pk3 = Pk3::from_bytes(written.clone()).unwrap();
+        let names: Vec<_> = (0..pk3.len()).filter_map(|idx| pk3.name(idx)).collect();
+        assert_eq!(names, ["skydefs.json", "sprites/", "sprites/trooa1.png", "textures.txt", "demoloop.json"]);
+        assert_eq!(pk3.read("skydefs"), Some(b"new skies".to_vec()));
+        assert_eq!(pk3.read("DEMOLOOP"), Some(b"demos".to_vec()));
+        assert_eq!(pk3.read("textures.txt"), Some(b"texture textures texture".to_vec()));
+
+        // the compressed bytes of the untouched entries are copied across exactly
+        let mut before = ZipArchive::new(Cursor::new(original.as_slice())).unwrap();
+        let mut after = ZipArchive::new(Cursor::new(written.as_slice())).unwrap();
+        for idx in 1..4 {
+            let mut raw_before = Vec::new();
+            before.by_index_raw(idx).unwrap().read_to_end(&mut raw_before).unwrap();
+            let mut raw_after = Vec::new();
+            after.by_index_raw(idx).unwrap().read_to_end(&mut raw_after).unwrap();
+            assert_eq!(raw_before, raw_after);
+        }
+        assert_eq!(after.by_index(0).unwrap().compression(), CompressionMethod::Deflated);
+    }
+}
@@ -0,0 +1,260 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+// the files a game is started with, in load order: the IWAD, then PWADs, then the file being edited
+// a lump in a later file replaces any lump of the same name before it, the same as in the engine
+
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use super::archive::{Archive, ArchiveType};
+use super::pk3::Namespace;
+use super::wad::WadType;
+
+#[derive(Clone, Debug)]
+pub struct Resource {
+    pub path: PathBuf,
+    pub archive: Archive
+}
+
+// where a lump name resolved to, resource is the position in load order
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub struct LumpLocation {
+    pub resource: usize,
+    pub index: usize
+}
+
+// how a name used by a lump resolves
+#[derive(Clone, Eq, PartialEq, Debug)]
+pub enum Resolution {
+    Found(LumpLocation),
+    // only found because PK3 lookups ignore case, along with the name that would always work
+    CaseMismatch(LumpLocation, String),
+    Missing
+}
+
+#[derive(Clone, Default, Debug)]
+pub struct ResourceStack {
+    resources: Vec<Resource>
+}
+
+impl ResourceStack {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    // files named by a GAMECONF are looked for in dirs, in order
+    // files that can't be found or opened are left out, with the reason returned alongside the stack
+    pub fn load(iwad: Option<&str>, pwads: &[String], dirs: &[PathBuf]) -> (Self, Vec<String>) {
+        let mut stack = Self::new();
+        let mut errors = Vec::new();
+        for name in iwad.into_iter().chain(pwads.iter().map(String::as_str)) {
+            let result = find_file(name, dirs)
+                .ok_or_else(|| format!("couldn't find {name}"))
+                .and_then(|path| stack.open(&path));
+            if let Err(why) = result {
+                errors.push(why);
+            }
+        }
+        (stack, errors)
+    }
+
+    // goes on top of everything loaded so far
+    pub fn push(&mut self, path: PathBuf, archive: Archive) {
+        self.resources.push(Resource { path, archive });
+    }
+
+    pub fn open(&mut self, path: &Path) -> Result<(), String> {
+        let archive = Archive::open(path)?;
+        self.push(path.to_owned(), archive);
+        Ok(())
+    }
+
+    pub fn resources(&self) -> &[Resource] {
+        &self.resources
+    }
+
+    // without the IWAD most references won't resolve, so there isn't much point checking them
+    pub fn has_iwad(&self) -> bool {
+        self.resources.first().is_some_and(|resource| match &resource.archive {
+            Archive::Wad(wad) => wad.wad_type == WadType::IWAD,
+            Archive::Pk3(_) => resource.path.extension().is_some_and(|extension| extension.eq_ignore_ascii_case("ipk3")),
+        })
+    }
+
+    pub fn len(&self) -> usize {
+        self.resources.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.resources.is_empty()
+    }
+
+    // the lump that wins, the last loaded file that has one
+    pub fn find(&self, name: &str) -> Option<LumpLocation> {
+        self.find_in(name, Namespace::Global)
+    }
+
+    // PK3s are searched in the namespace's folders as well as their root
+    pub fn find_in(&self, name: &str, namespace: Namespace) -> Option<LumpLocation> {
+        self.resources
+            .iter()
+            .enumerate()
+            .rev()
+            .find_map(|(resource, Resource { archive, .. })| {
+                archive.find_in(name, namespace).map(|index| LumpLocation { resource, index })
+            })
+    }
+
+    // PK3 files are usually named in lower case, which upper case lump names find just as they would in a WAD
+    // lower or mixed case names only work in PK3s, and full paths have to match the file's path exactly
+    pub fn resolve(&self, name: &str, namespace: Namespace) -> Resolution {
+        let Some(location) = self.find_in(name, namespace) else {
+            return Resolution::Missing;
+        };
+        let archive = &self.resources[location.resource].archive;
+        if archive.archive_type() == ArchiveType::Pk3 {
+            if name.contains('/') {
+                if let Some(path) = archive.name(location.index)
+                    && path != name {
+                    return Resolution::CaseMismatch(location, path.to_owned());
+                }
+            } else if name.chars().any(|c| c.is_ascii_lowercase()) {
+                return Resolution::CaseMismatch(location, name.to_ascii_uppercase());
+            }
+        }
+        Resolution::Found(location)
+    }
+
+    // the file the winning lump comes from
+    pub fn source(&self, name: &str) -> Option<&Path> {
+        self.find(name).map(|location| self.resources[location.resource].path.as_path())
+    }
+
+    pub fn read(&self, name: &str) -> Option<Cow<'_, [u8]>> {
+        self.read_in(name, Namespace::Global)
+    }
+
+    pub fn read_in(&self, name: &str, namespace: Namespace) -> Option<Cow<'_, [u8]>> {
+        let location = self.find_in(name, namespace)?;
+        self.resources[location.resource].archive.lump_data(location.index)
+    }
+
+    // every lump name that resolves to something, once each and upper case, in load order
+    pub fn lump_names(&self) -> Vec<String> {
+        let mut seen = std::collections::HashSet::new();
+        let mut names = Vec::new();
+        for Resource { archive, .. } in &self.resources {
+            for idx in 0..archive.len() {
+                if let Some(name) = archive.lump_name(idx)
+                    && archive.lump_data(idx).is_some()
+                    && seen.insert(name.to_uppercase()) {
+                    names.push(name.to_uppercase());
+                }
+            }
+        }
+        names
+    }
+}
+
+// file names from a GAMECONF are matched case insensitively, most file systems doom runs on don't care
+pub fn find_file(name: &str, dirs: &[PathBuf]) -> Option<PathBuf> {
+    let path = Path::new(name);
+    if path.is_absolute() {
+        return path.exists().then(|| path.to_owned());
+    }
+    dirs.iter().find_map(|dir| {
+        let exact = dir.join(path);
+        if exact.exists() {
+            return Some(exact);
+        }
+        std::fs::read_dir(dir).ok()?
+            .flatten()
+            .map(|entry| entry.path())
+            .find(|candidate| candidate.file_name().is_some_and(|file| file.eq_ignore_ascii_case(name)))
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::wad::Wad;
+    use super::super::wad::test::build_wad;
+    use super::super::pk3::Pk3;
+    use super::super::pk3::test::build_pk3;
+    use zip::CompressionMethod;
+    fn wad(lumps: &[(&str, &[u8])]) -> Archive {
+        Archive::Wad(Wad::from_bytes(build_wad(b"PWAD", lumps)).unwrap())
+    }
+    #[test]
+    fn resolve_lumps() {
+        let mut stack = ResourceStack::new();
+        stack.push(PathBuf::from("doom2.wad"), wad(&[("PLAYPAL", b"iwad"), ("SKY1", b"sky"), ("TITLEPIC", b"title")]));
+        stack.push(PathBuf::from("mod.wad"), wad(&[("SKY1", b"first"), ("SKY1", b"second"), ("SKYDEFS", b"{ }")]));
+        stack.push(PathBuf::from("edited.wad"), wad(&[("titlepic", b"edited")]));
+
+        assert_eq!(stack.find("PLAYPAL"), Some(LumpLocation { resource: 0, index: 0 }));
+        // the last lump in the last file wins
+        assert_eq!(stack.find("sky1"), Some(LumpLocation { resource: 1, index: 1 }));
+        assert_eq!(stack.read("SKY1").as_deref(), Some(&b"second"[..]));
+        assert_eq!(stack.source("TITLEPIC"), Some(Path::new("edited.wad")));
+        assert_eq!(stack.find("COLORMAP"), None);
+        assert!(!stack.has_iwad());
+        assert_eq!(stack.lump_names(), vec!["PLAYPAL", "SKY1", "TITLEPIC", "SKYDEFS"]);
+    }
+    #[test]
+    fn resolve_pk3_lumps() {
+        let pk3 = build_pk3(&[
+            ("graphics/STBAR.png", b"bar", CompressionMethod::Stored),
+            ("graphics/stfst01.png", b"face", CompressionMethod::Stored),
+            ("music/d_runnin.ogg", b"music", CompressionMethod::Stored),
+        ]);
+        let mut stack = ResourceStack::new();
+        stack.push(PathBuf::from("doom2.wad"), wad(&[("STBAR", b"iwad"), ("D_RUNNIN", b"iwad")]));
+        stack.push(PathBuf::from("mod.pk3"), Archive::Pk3(Pk3::from_bytes(pk3).unwrap()));
+        let in_pk3 = |index| LumpLocation { resource: 1, index };
+
+        assert_eq!(stack.resolve("STBAR", Namespace::Graphics), Resolution::Found(in_pk3(0)));
+        assert_eq!(stack.read_in("STBAR", Namespace::Graphics).as_deref(), Some(&b"bar"[..]));
+        // without the namespace only the IWAD's lump is found
+        assert_eq!(stack.resolve("STBAR", Namespace::Global), Resolution::Found(LumpLocation { resource: 0, index: 0 }));
+        // lower case files are how PK3s are usually laid out, upper case names still find them
+        assert_eq!(stack.resolve("STFST01", Namespace::Graphics), Resolution::Found(in_pk3(1)));
+        assert_eq!(stack.resolve("D_RUNNIN", Namespace::Music), Resolution::Found(in_pk3(2)));
+        // but a lower case name wouldn't work in a WAD
+        assert_eq!(stack.resolve("stbar", Namespace::Graphics), Resolution::CaseMismatch(in_pk3(0), "STBAR".to_owned()));
+        assert_eq!(
+            stack.resolve("graphics/stbar.png", Namespace::Graphics),
+            Resolution::CaseMismatch(in_pk3(0), "graphics/STBAR.png".to_owned())
+        );
+        assert_eq!(stack.resolve("graphics/STBAR.png", Namespace::Graphics), Resolution::Found(in_pk3(0)));
+        assert_eq!(stack.resolve("STFB0", Namespace::Graphics), Resolution::Missing);
+    }
+    #[test]
+    fn load_gameconf_files() {
+        let dir = std::env::temp_dir().join(format!("doomstruct-resources-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        std::fs::write(dir.join("DOOM2.WAD"), build_wad(b"IWAD", &[("PLAYPAL", b"iwad")])).unwrap();
+        std::fs::write(dir.join("mod.wad"), build_wad(b"PWAD", &[("PLAYPAL", b"pwad")])).unwrap();
+
+        let pwads = vec!["mod.wad".to_owned(), "missing.wad".to_owned()];
+        let (stack, errors) = ResourceStack::load(Some("doom2.wad"), &pwads, std::slice::from_ref(&dir));
+        assert_eq!(stack.len(), 2);
+        assert!(stack.has_iwad());
+        assert_eq!(errors, vec!["couldn't find missing.wad".to_owned()]);
+        assert_eq!(stack.read("PLAYPAL").as_deref(), Some(&b"pwad"[..]));
+        assert_eq!(stack.source("PLAYPAL"), Some(dir.join("mod.wad").as_path()));
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
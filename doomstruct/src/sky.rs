@@ -0,0 +1,199 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+// a software sky, drawn the way vanilla draws one when looking straight at the horizon
+// 256 texels of sky span the 320 pixel wide view, and the texture's mid row sits on the horizon halfway down
+
+use super::graphics::{Image, Palette};
+
+pub const VIEW_WIDTH: usize = 320;
+pub const VIEW_HEIGHT: usize = 200;
+// a 256 wide sky goes around 4 times in 360 degrees, so a 90 degree view sees 256 texels
+const TEXELS_PER_VIEW: f32 = 256.0;
+
+#[derive(Clone, Copy, Debug)]
+pub struct SkyLayer<'a> {
+    pub image: &'a Image,
+    // the texture row drawn at the horizon
+    pub mid: f32,
+    // in texels per second
+    pub scroll: (f32, f32),
+    // how much bigger each texel is drawn, 2 shows half as much of the texture
+    pub scale: (f32, f32)
+}
+
+impl SkyLayer<'_> {
+    // the texture wraps in both directions
+    fn sample(&self, x: usize, y: usize, time: f32) -> Option<[u8; 4]> {
+        if self.image.width == 0 || self.image.height == 0 || self.scale.0 <= 0.0 || self.scale.1 <= 0.0 {
+            return None;
+        }
+        #[allow(clippy::cast_precision_loss)]
+        let (x, y, horizon) = (x as f32, y as f32, (VIEW_HEIGHT / 2) as f32);
+        #[allow(clippy::cast_precision_loss)]
+        let u = x * TEXELS_PER_VIEW / VIEW_WIDTH as f32 / self.scale.0 + self.scroll.0 * time;
+        let v = (y - horizon) / self.scale.1 + self.mid + self.scroll.1 * time;
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss, clippy::cast_precision_loss)]
+        let (u, v) = (
+            u.floor().rem_euclid(self.image.width as f32) as usize,
+            v.floor().rem_euclid(self.image.height as f32) as usize
+        );
+        self.image.get(u, v)
+    }
+}
+
+// layers are drawn back to front, transparent parts of a layer show the ones behind it
+// time is in seconds since scrolling started
+pub fn render_sky(layers: &[SkyLayer], time: f32) -> Image {
+    let mut image = Image::new(VIEW_WIDTH, VIEW_HEIGHT);
+    for y in 0..VIEW_HEIGHT {
+        for x in 0..VIEW_WIDTH {
+            let pixel = layers.iter()
+                .rev()
+                .find_map(|layer| layer.sample(x, y, time).filter(|pixel| pixel[3] != 0));
+            if let Some(pixel) = pixel {
+                image.set(x, y, pixel);
+            }
+        }
+    }
+    image
+}
+
+// PSX Doom's fire, the hottest colour is kept burning along the bottom row
+// every update each heat value rises a row, drifting sideways and cooling at random
+#[derive(Clone, Debug)]
+pub struct Fire {
+    pub width: usize,
+    pub height: usize,
+    // indexes into the fire's colours, 0 is the coldest
+    heat: Vec<u8>,
+    seed: u32
+}
+
+impl Fire {
+    // colours is how many the fire's palette has, the bottom row burns with the last one
+    pub fn new(width: usize, height: usize, colours: usize) -> Self {
+        let hottest = u8::try_from(colours.saturating_sub(1)).unwrap_or(u8::MAX);
+        let mut heat = vec![0; width * height];
+        if let Some(bottom) = height.checked_sub(1) {
+            heat[bottom * width..].fill(hottest);
+        }
+        Self { width, height, heat, seed: 0x1D24_F13E }
+    }
+
+    // xorshift, the fire only needs to look random
+    fn random(&mut self) -> u32 {
+        self.seed ^= self.seed << 13;
+        self.seed ^= self.seed >> 17;
+        self.seed ^= self.seed << 5;
+        self.seed
+    }
+
+    pub fn update(&mut self) {
+        for x in 0..self.width {
+            for y in 1..self.height {
+                let heat = self.heat[y * self.width + x];
+                let random = self.random() & 3;
+                // up to one column either way, wrapping around since skies do
+                let spread = (x + self.width + 1 - random as usize) % self.width;
+                let above = if heat == 0 { x } else { spread };
+                self.heat[(y - 1) * self.width + above] = heat.saturating_sub(u8::from(random & 1 == 1));
+            }
+        }
+    }
+
+    pub fn heat(&self, x: usize, y: usize) -> Option<u8> {
+        (x < self.width && y < self.height).then(|| self.heat[y * self.width + x])
+    }
+
+    // colours maps heat to palette indices, heat without a colour is left transparent
+    pub fn to_image(&self, colours: &[u8], palette: &Palette) -> Image {
+        let mut image = Image::new(self.width, self.height);
+        for y in 0..self.height {
+            for x in 0..self.width {
+                if let Some(&index) = colours.get(usize::from(self.heat[y * self.width + x])) {
+                    let [r, g, b] = palette[usize::from(index)];
+                    image.set(x, y, [r, g, b, 0xFF]);
+                }
+            }
+        }
+        image
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    // each texel's red is its column and green its row
+    fn gradient(width: usize, height: usize) -> Image {
+        let mut image = Image::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                image.set(x, y, [u8::try_from(x).unwrap(), u8::try_from(y).unwrap(), 0, 0xFF]);
+            }
+        }
+        image
+    }
+    #[test]
+    fn render_background() {
+        let image = gradient(256, 128);
+        let layer = SkyLayer { image: &image, mid: 100.0, scroll: (0.0, 0.0), scale: (1.0, 1.0) };
+        let sky = render_sky(&[layer], 0.0);
+        assert_eq!((sky.width, sky.height), (VIEW_WIDTH, VIEW_HEIGHT));
+        // the mid row is on the horizon and the rows wrap
+        assert_eq!(sky.get(0, 100), Some([0, 100, 0, 0xFF]));
+        assert_eq!(sky.get(0, 130), Some([0, 2, 0, 0xFF]));
+        assert_eq!(sky.get(319, 0), Some([255, 0, 0, 0xFF]));
+
+        let scaled = SkyLayer { scale: (2.0, 2.0), ..layer };
+        assert_eq!(render_sky(&[scaled], 0.0).get(319, 0), Some([127, 50, 0, 0xFF]));
+        // scrolling 10 texels per second, for 1.5 seconds
+        let scrolling = SkyLayer { scroll: (10.0, -10.0), ..layer };
+        assert_eq!(render_sky(&[scrolling], 1.5).get(0, 100), Some([15, 85, 0, 0xFF]));
+    }
+    #[test]
+    fn burn_fire() {
+        let mut fire = Fire::new(16, 8, 3);
+        assert_eq!(fire.heat(5, 7), Some(2));
+        assert_eq!(fire.heat(5, 6), Some(0));
+        assert_eq!(fire.heat(16, 0), None);
+        for _ in 0..32 {
+            fire.update();
+        }
+        // the bottom keeps burning and the heat above it never gets hotter
+        assert!((0..16).all(|x| fire.heat(x, 7) == Some(2)));
+        assert!((0..16).any(|x| fire.heat(x, 6) != Some(0)));
+        assert!((0..8).all(|y| (0..16).all(|x| fire.heat(x, y) <= Some(2))));
+
+        let palette = crate::graphics::test::test_palette();
+        let image = fire.to_image(&[7, 9, 11], &palette);
+        assert_eq!(image.get(0, 7), Some([11, 244, 5, 0xFF]));
+        // the palette got shorter than the fire is hot
+        assert_eq!(fire.to_image(&[7], &palette).get(0, 7), Some([0, 0, 0, 0]));
+    }
+    #[test]
+    fn render_foreground() {
+        let background = gradient(256, 128);
+        let mut foreground = Image::new(256, 128);
+        foreground.set(0, 100, [1, 2, 3, 0xFF]);
+        let sky = render_sky(&[
+            SkyLayer { image: &background, mid: 100.0, scroll: (0.0, 0.0), scale: (1.0, 1.0) },
+            SkyLayer { image: &foreground, mid: 100.0, scroll: (0.0, 0.0), scale: (1.0, 1.0) },
+        ], 0.0);
+        assert_eq!(sky.get(0, 100), Some([1, 2, 3, 0xFF]));
+        // screen pixels are narrower than texels
+        assert_eq!(sky.get(1, 100), Some([1, 2, 3, 0xFF]));
+        assert_eq!(sky.get(2, 100), Some([1, 100, 0, 0xFF]));
+    }
+}
@@ -0,0 +1,449 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+// wall textures are built from patches, so a texture name isn't a lump name
+// PNAMES: i32 count, then an 8 byte name for each patch
+// TEXTURE1/TEXTURE2: i32 count, an i32 offset for each texture, then each texture is
+// 8 byte name, i32 flags, i16 width, i16 height, i32 unused, i16 patch count,
+// and for each patch i16 x, i16 y, i16 PNAMES index, i16 unused, i16 unused
+// the ZDoom TEXTURES lump defines them in text instead, only the outline of each definition is read from it
+
+use super::archive::{Archive, ArchiveType};
+use super::graphics::{self, Image, Palette};
+use super::pk3::Namespace;
+use super::resources::ResourceStack;
+use super::wad::{lump_name, read_i32};
+
+const NAME_SIZE: usize = 8;
+const TEXTURE_HEADER_SIZE: usize = 22;
+const PATCH_SIZE: usize = 10;
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct TexturePatch {
+    pub x: i16,
+    pub y: i16,
+    pub patch: String
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Texture {
+    pub name: String,
+    pub width: u16,
+    pub height: u16,
+    pub patches: Vec<TexturePatch>
+}
+
+fn read_i16(data: &[u8], offset: usize) -> Option<i16> {
+    let bytes = data.get(offset..offset + 2)?;
+    Some(i16::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_count(data: &[u8], offset: usize, what: &str) -> Result<usize, String> {
+    let value = read_i32(data, offset).ok_or_else(|| format!("{what} is truncated"))?;
+    usize::try_from(value).map_err(|_| format!("{what} has a negative count: {value}"))
+}
+
+pub fn read_pnames(data: &[u8]) -> Result<Vec<String>, String> {
+    let count = read_count(data, 0, "PNAMES")?;
+    (0..count)
+        .map(|idx| {
+            let offset = 4 + idx * NAME_SIZE;
+            data.get(offset..offset + NAME_SIZE)
+                .map(lump_name)
+                .ok_or_else(|| format!("PNAMES is truncated, it should have {count} names"))
+        })
+        .collect()
+}
+
+// patches are stored as indexes into pnames, ones that are out of range are an error like in vanilla
+pub fn read_texture_lump(data: &[u8], pnames: &[String]) -> Result<Vec<Texture>, String> {
+    let count = read_count(data, 0, "texture lump")?;
+    let mut textures = Vec::with_capacity(count.min(data.len() / 4));
+    for idx in 0..count {
+        let offset = read_count(data, 4 + idx * 4, "texture lump")?;
+        let header = data.get(offset..offset + TEXTURE_HEADER_SIZE)
+            .ok_or_else(|| format!("texture {idx} is past the end of the lump"))?;
+        let name = lump_name(&header[..NAME_SIZE]);
+        let (Some(width), Some(height), Some(patch_count)) = (read_i16(header, 12), read_i16(header, 14), read_i16(header, 20)) else {
+            unreachable!("the header is TEXTURE_HEADER_SIZE bytes long");
+        };
+        let mut patches = Vec::new();
+        for patch_idx in 0..usize::try_from(patch_count).unwrap_or(0) {
+            let offset = offset + TEXTURE_HEADER_SIZE + patch_idx * PATCH_SIZE;
+            let Some(entry) = data.get(offset..offset + PATCH_SIZE) else {
+                return Err(format!("texture {name} is truncated"));
+            };
+            let (Some(x), Some(y), Some(patch)) = (read_i16(entry, 0), read_i16(entry, 2), read_i16(entry, 4)) else {
+                unreachable!("the entry is PATCH_SIZE bytes long");
+            };
+            let patch = usize::try_from(patch).ok()
+                .and_then(|patch| pnames.get(patch))
+                .ok_or_else(|| format!("texture {name} uses patch {patch}, but PNAMES only has {}", pnames.len()))?;
+            patches.push(TexturePatch { x, y, patch: patch.clone() });
+        }
+        textures.push(Texture {
+            name,
+            width: u16::try_from(width).unwrap_or(0),
+            height: u16::try_from(height).unwrap_or(0),
+            patches
+        });
+    }
+    Ok(textures)
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Word(String),
+    String(String),
+    Symbol(char)
+}
+
+// comments are skipped, numbers are left as words
+fn tokenize(text: &str) -> Result<Vec<Token>, String> {
+    let mut tokens = Vec::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => (),
+            '/' if chars.peek() == Some(&'/') => {
+                chars.by_ref().find(|c| *c == '\n');
+            },
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut last = ' ';
+                loop {
+                    match chars.next() {
+                        Some('/') if last == '*' => break,
+                        Some(c) => last = c,
+                        None => return Err("unterminated comment".to_owned())
+                    }
+                }
+            },
+            '"' => {
+                let mut string = String::new();
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => string.extend(chars.next()),
+                        Some(c) => string.push(c),
+                        None => return Err("unterminated string".to_owned())
+                    }
+                }
+                tokens.push(Token::String(string));
+            },
+            '{' | '}' | ',' => tokens.push(Token::Symbol(c)),
+            c => {
+                let mut word = c.to_string();
+                while let Some(c) = chars.next_if(|c| !c.is_whitespace() && !matches!(c, '{' | '}' | ',' | '"')) {
+                    word.push(c);
+                }
+                tokens.push(Token::Word(word));
+            }
+        }
+    }
+    Ok(tokens)
+}
+
+fn expect_number(tokens: &mut impl Iterator<Item = Token>, what: &str) -> Result<i32, String> {
+    match tokens.next() {
+        Some(Token::Word(word)) => word.parse().map_err(|_| format!("{what} should be a number, not '{word}'")),
+        _ => Err(format!("missing {what}"))
+    }
+}
+
+// every kind of definition is read, but only Texture and WallTexture definitions are kept
+// the properties inside each definition are skipped apart from patches
+pub fn read_textures_lump(text: &str) -> Result<Vec<Texture>, String> {
+    let mut tokens = tokenize(text)?.into_iter().peekable();
+    let mut textures = Vec::new();
+    while let Some(token) = tokens.next() {
+        let Token::Word(kind) = token else {
+            return Err(format!("expected a texture type, found {token:?}"));
+        };
+        let kind = kind.to_ascii_lowercase();
+        // optional only means the texture is skipped if its patches don't exist
+        tokens.next_if(|token| matches!(token, Token::Word(word) if word.eq_ignore_ascii_case("optional")));
+        let name = match tokens.next() {
+            Some(Token::Word(name) | Token::String(name)) => name,
+            _ => return Err(format!("{kind} is missing its name"))
+        };
+        if tokens.next() != Some(Token::Symbol(',')) {
+            return Err(format!("expected ',' after the name of {name}"));
+        }
+        let width = expect_number(&mut tokens, &format!("width of {name}"))?;
+        if tokens.next() != Some(Token::Symbol(',')) {
+            return Err(format!("expected ',' after the width of {name}"));
+        }
+        let height = expect_number(&mut tokens, &format!("height of {name}"))?;
+        if tokens.next() != Some(Token::Symbol('{')) {
+            return Err(format!("expected '{{' to start {name}"));
+        }
+        let mut patches = Vec::new();
+        let mut depth = 1;
+        while depth > 0 {
+            match tokens.next() {
+                Some(Token::Symbol('{')) => depth += 1,
+                Some(Token::Symbol('}')) => depth -= 1,
+                Some(Token::Word(word)) if depth == 1 && word.eq_ignore_ascii_case("patch") => {
+                    let patch = match tokens.next() {
+                        Some(Token::Word(patch) | Token::String(patch)) => patch,
+                        _ => return Err(format!("patch in {name} is missing its name"))
+                    };
+                    tokens.next_if_eq(&Token::Symbol(','));
+                    let x = expect_number(&mut tokens, &format!("x of {patch} in {name}"))?;
+                    tokens.next_if_eq(&Token::Symbol(','));
+                    let y = expect_number(&mut tokens, &format!("y of {patch} in {name}"))?;
+                    patches.push(TexturePatch {
+                        x: i16::try_from(x).map_err(|_| format!("x of {patch} in {name} is out of range"))?,
+                        y: i16::try_from(y).map_err(|_| format!("y of {patch} in {name} is out of range"))?,
+                        patch
+                    });
+                },
+                Some(_) => (),
+                None => return Err(format!("{name} is missing its closing '}}'"))
+            }
+        }
+        if kind == "texture" || kind == "walltexture" {
+            let width = u16::try_from(width).map_err(|_| format!("width of {name} is out of range"))?;
+            let height = u16::try_from(height).map_err(|_| format!("height of {name} is out of range"))?;
+            textures.push(Texture { name, width, height, patches });
+        }
+    }
+    Ok(textures)
+}
+
+impl Texture {
+    // patches are drawn in order onto a transparent image the size of the texture
+    pub fn render(&self, resources: &ResourceStack, palette: &Palette) -> Result<Image, String> {
+        let mut image = Image::new(usize::from(self.width), usize::from(self.height));
+        for patch in &self.patches {
+            let data = resources.read_in(&patch.patch, Namespace::Patches)
+                .ok_or_else(|| format!("texture {}: there's no patch named {}", self.name, patch.patch))?;
+            let patch_image = graphics::read_graphic(&data, palette)
+                .map_err(|why| format!("texture {}: {}: {why}", self.name, patch.patch))?;
+            image.draw(&patch_image, i32::from(patch.x), i32::from(patch.y));
+        }
+        Ok(image)
+    }
+}
+
+// every texture the loaded files define, later definitions replace earlier ones with the same name
+#[derive(Clone, Default, Debug)]
+pub struct TextureSet {
+    textures: Vec<Texture>
+}
+
+impl TextureSet {
+    // TEXTURE1 and TEXTURE2 come from whichever file loads them last, like vanilla
+    // TEXTURES lumps add to them from every file that has one, like ZDoom
+    // problems with individual lumps are returned rather than stopping the rest from loading
+    pub fn load(resources: &ResourceStack) -> (Self, Vec<String>) {
+        let mut set = Self::default();
+        let mut errors = Vec::new();
+        let pnames = resources.read("PNAMES").map(|data| read_pnames(&data));
+        for name in ["TEXTURE1", "TEXTURE2"] {
+            let Some(data) = resources.read(name) else {
+                continue;
+            };
+            let result = match &pnames {
+                Some(Ok(pnames)) => read_texture_lump(&data, pnames),
+                Some(Err(why)) => Err(why.clone()),
+                None => Err("there's no PNAMES".to_owned())
+            };
+            match result {
+                Ok(textures) => set.extend(textures),
+                Err(why) => errors.push(format!("{name}: {why}"))
+            }
+        }
+        for resource in resources.resources() {
+            for data in textures_lumps(&resource.archive) {
+                let result = std::str::from_utf8(&data)
+                    .map_err(|why| why.to_string())
+                    .and_then(read_textures_lump);
+                match result {
+                    Ok(textures) => set.extend(textures),
+                    Err(why) => errors.push(format!("TEXTURES in {}: {why}", resource.path.display()))
+                }
+            }
+        }
+        (set, errors)
+    }
+
+    pub fn extend(&mut self, textures: impl IntoIterator<Item = Texture>) {
+        for texture in textures {
+            self.textures.retain(|existing| !existing.name.eq_ignore_ascii_case(&texture.name));
+            self.textures.push(texture);
+        }
+    }
+
+    pub fn find(&self, name: &str) -> Option<&Texture> {
+        self.textures.iter().find(|texture| texture.name.eq_ignore_ascii_case(name))
+    }
+
+    // anything in a PK3 can be used as a texture by its name, not just what the texture lumps define
+    pub fn render(&self, name: &str, resources: &ResourceStack, palette: &Palette) -> Result<Image, String> {
+        if let Some(texture) = self.find(name) {
+            return texture.render(resources, palette);
+        }
+        let location = resources.find_in(name, Namespace::Textures).ok_or_else(|| format!("there's no texture named {name}"))?;
+        if resources.resources()[location.resource].archive.archive_type() != ArchiveType::Pk3 {
+            return Err(format!("there's no texture named {name}"));
+        }
+        let data = resources.read_in(name, Namespace::Textures).ok_or_else(|| format!("there's no texture named {name}"))?;
+        graphics::read_graphic(&data, palette).map_err(|why| format!("{name}: {why}"))
+    }
+
+    pub fn textures(&self) -> &[Texture] {
+        &self.textures
+    }
+
+    pub fn len(&self) -> usize {
+        self.textures.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.textures.is_empty()
+    }
+}
+
+fn textures_lumps(archive: &Archive) -> Vec<Vec<u8>> {
+    (0..archive.len())
+        .filter(|idx| archive.lump_name(*idx).is_some_and(|name| name.eq_ignore_ascii_case("TEXTURES")))
+        .filter_map(|idx| archive.lump_data(idx).map(|data| data.into_owned()))
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::wad::Wad;
+    use super::super::wad::test::build_wad;
+    fn name(name: &str) -> [u8; NAME_SIZE] {
+        let mut bytes = [0; NAME_SIZE];
+        bytes[..name.len()].copy_from_slice(name.as_bytes());
+        bytes
+    }
+    fn build_pnames(names: &[&str]) -> Vec<u8> {
+        let mut data = i32::try_from(names.len()).unwrap().to_le_bytes().to_vec();
+        for patch in names {
+            data.extend(name(patch));
+        }
+        data
+    }
+    // a name, size and list of (x, y, patch index)
+    type TestTexture<'a> = (&'a str, i16, i16, &'a [(i16, i16, i16)]);
+    fn build_texture_lump(textures: &[TestTexture]) -> Vec<u8> {
+        let mut data = i32::try_from(textures.len()).unwrap().to_le_bytes().to_vec();
+        let mut offset = 4 + textures.len() * 4;
+        for (_, _, _, patches) in textures {
+            data.extend(i32::try_from(offset).unwrap().to_le_bytes());
+            offset += TEXTURE_HEADER_SIZE + patches.len() * PATCH_SIZE;
+        }
+        for (texture, width, height, patches) in textures {
+            data.extend(name(texture));
+            data.extend(0i32.to_le_bytes());
+            data.extend(width.to_le_bytes());
+            data.extend(height.to_le_bytes());
+            data.extend(0i32.to_le_bytes());
+            data.extend(i16::try_from(patches.len()).unwrap().to_le_bytes());
+            for (x, y, patch) in *patches {
+                for value in [*x, *y, *patch, 1, 0] {
+                    data.extend(value.to_le_bytes());
+                }
+            }
+        }
+        data
+    }
+    #[test]
+    fn read_binary_textures() {
+        let pnames = read_pnames(&build_pnames(&["RSKY1", "WALL00_1"])).unwrap();
+        assert_eq!(pnames, vec!["RSKY1", "WALL00_1"]);
+        let data = build_texture_lump(&[
+            ("SKY1", 256, 128, &[(0, 0, 0)]),
+            ("BIGDOOR1", 128, 96, &[(0, 0, 1), (64, -8, 1)]),
+        ]);
+        let textures = read_texture_lump(&data, &pnames).unwrap();
+        assert_eq!(textures.len(), 2);
+        assert_eq!(textures[0].name, "SKY1");
+        assert_eq!((textures[0].width, textures[0].height), (256, 128));
+        assert_eq!(textures[1].patches[1], TexturePatch { x: 64, y: -8, patch: "WALL00_1".to_owned() });
+
+        assert!(read_texture_lump(&data, &pnames[..1]).is_err());
+        assert!(read_texture_lump(&data[..data.len() - 1], &pnames).is_err());
+        assert!(read_pnames(&build_pnames(&["RSKY1"])[..8]).is_err());
+    }
+    #[test]
+    fn read_zdoom_textures() {
+        let text = r#"
+            // the sky from the PSX port
+            WallTexture "SKY1", 256, 128
+            {
+                Patch "RSKY1", 0, 0 { FlipX }
+                Patch RSKY1, 128, 0
+            }
+            /* sprites aren't textures */
+            Sprite "TROOA1", 41, 57 { Offset 19, 52 Patch TROOA1, 0, 0 }
+            Texture Optional "textures/longname", 64, 64 { XScale 2.0 }
+        "#;
+        let textures = read_textures_lump(text).unwrap();
+        assert_eq!(textures.len(), 2);
+        assert_eq!(textures[0].patches.len(), 2);
+        assert_eq!(textures[0].patches[1], TexturePatch { x: 128, y: 0, patch: "RSKY1".to_owned() });
+        assert_eq!(textures[1].name, "textures/longname");
+        assert_eq!((textures[1].width, textures[1].height), (64, 64));
+        assert!(read_textures_lump("Texture SKY1, 256 { }").is_err());
+        assert!(read_textures_lump("Texture SKY1, 256, 128 {").is_err());
+    }
+    #[test]
+    fn load_texture_set() {
+        let pnames = build_pnames(&["RSKY1"]);
+        let iwad = build_wad(b"IWAD", &[
+            ("PNAMES", &pnames),
+            ("TEXTURE1", &build_texture_lump(&[("SKY1", 256, 128, &[(0, 0, 0)]), ("SKY2", 256, 128, &[(0, 0, 0)])])),
+        ]);
+        let pwad = build_wad(b"PWAD", &[("TEXTURES", b"WallTexture SKY2, 1024, 200 { }")]);
+        let mut resources = ResourceStack::new();
+        resources.push("doom2.wad".into(), Archive::Wad(Wad::from_bytes(iwad).unwrap()));
+        resources.push("mod.wad".into(), Archive::Wad(Wad::from_bytes(pwad).unwrap()));
+        let (textures, errors) = TextureSet::load(&resources);
+        assert!(errors.is_empty());
+        assert_eq!(textures.len(), 2);
+        assert_eq!(textures.find("sky1").map(|texture| texture.width), Some(256));
+        assert_eq!(textures.find("SKY2").map(|texture| texture.width), Some(1024));
+        assert!(textures.find("SKY3").is_none());
+    }
+    #[test]
+    fn render_texture() {
+        use super::super::graphics::test::{build_patch, test_palette};
+        let patch = build_patch(2, (0, 0), &[&[(0, &[1, 2])], &[(0, &[3, 4])]]);
+        let wad = build_wad(b"IWAD", &[
+            ("PNAMES", &build_pnames(&["WALL", "MISSING"])),
+            ("TEXTURE1", &build_texture_lump(&[("TWICE", 3, 2, &[(0, 0, 0), (2, 0, 0)]), ("BROKEN", 2, 2, &[(0, 0, 1)])])),
+            ("WALL", &patch),
+        ]);
+        let mut resources = ResourceStack::new();
+        resources.push("doom2.wad".into(), Archive::Wad(Wad::from_bytes(wad).unwrap()));
+        let (textures, _) = TextureSet::load(&resources);
+        let palette = test_palette();
+
+        let image = textures.render("TWICE", &resources, &palette).unwrap();
+        assert_eq!((image.width, image.height), (3, 2));
+        assert_eq!(image.get(1, 1), Some([4, 251, 2, 0xFF]));
+        // the second copy is cut off at the edge
+        assert_eq!(image.get(2, 0), Some([1, 254, 0, 0xFF]));
+        assert!(textures.render("BROKEN", &resources, &palette).is_err());
+        // lumps in a WAD aren't textures
+        assert!(textures.render("WALL", &resources, &palette).is_err());
+    }
+}
@@ -0,0 +1,337 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+// all numbers in a WAD are little endian i32s
+// header: "IWAD" or "PWAD", lump count, directory offset
+// directory: an entry of offset, size and 8 byte name for each lump
+
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+
+const HEADER_SIZE: usize = 12;
+const DIRECTORY_ENTRY_SIZE: usize = 16;
+pub const MAX_LUMP_NAME_LENGTH: usize = 8;
+
+#[derive(Clone, Copy, Eq, PartialEq, Debug)]
+pub enum WadType {
+    IWAD,
+    PWAD
+}
+
+impl Display for WadType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            WadType::IWAD => "IWAD",
+            WadType::PWAD => "PWAD",
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum LumpData {
+    // still in the file that was read
+    Original { offset: usize, size: usize },
+    New(Vec<u8>)
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Lump {
+    // as stored, without the NUL padding
+    pub name: String,
+    data: LumpData
+}
+
+impl Lump {
+    pub fn size(&self) -> usize {
+        match &self.data {
+            LumpData::Original { size, .. } => *size,
+            LumpData::New(data) => data.len()
+        }
+    }
+}
+
+// the whole file is kept in memory, lump data is borrowed from it until a lump is replaced
+#[derive(Clone, Debug)]
+pub struct Wad {
+    pub wad_type: WadType,
+    lumps: Vec<Lump>,
+    data: Vec<u8>
+}
+
+pub(crate) fn read_i32(data: &[u8], offset: usize) -> Option<i32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(i32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+fn read_usize(data: &[u8], offset: usize, what: &str) -> Result<usize, String> {
+    let value = read_i32(data, offset).ok_or_else(|| format!("WAD is truncated, {what} is missing"))?;
+    usize::try_from(value).map_err(|_| format!("WAD has a negative {what}: {value}"))
+}
+
+fn write_i32(out: &mut Vec<u8>, value: usize) -> Result<(), String> {
+    let value = i32::try_from(value).map_err(|_| "WAD is too big, it must be smaller than 2GB".to_owned())?;
+    out.extend(value.to_le_bytes());
+    Ok(())
+}
+
+// only the header of a file already on disk is checked, so nothing gets written over an IWAD by accident
+pub fn is_iwad(path: &Path) -> bool {
+    let mut magic = [0; 4];
+    std::fs::File::open(path)
+        .and_then(|mut file| std::io::Read::read_exact(&mut file, &mut magic))
+        .is_ok_and(|()| &magic == b"IWAD")
+}
+
+// lump names are at most 8 characters of printable ASCII
+pub fn check_lump_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > MAX_LUMP_NAME_LENGTH {
+        return Err(format!("lump name '{name}' must be between 1 and {MAX_LUMP_NAME_LENGTH} characters long"));
+    }
+    if !name.bytes().all(|b| b.is_ascii_graphic()) {
+        return Err(format!("lump name '{name}' can only use printable ASCII characters"));
+    }
+    Ok(())
+}
+
+impl Wad {
+    pub fn new(wad_type: WadType) -> Self {
+        Self {
+            wad_type,
+            lumps: Vec::new(),
+            data: Vec::new()
+        }
+    }
+
+    pub fn open(path: &Path) -> Result<Self, String> {
+        let data = std::fs::read(path).map_err(|why| format!("failed to open {}: {why}", path.display()))?;
+        Self::from_bytes(data).map_err(|why| format!("{}: {why}", path.display()))
+    }
+
+    pub fn from_bytes(data: Vec<u8>) -> Result<Self, String> {
+        if data.len() < HEADER_SIZE {
+            return Err("WAD is truncated, the header is missing".to_owned());
+        }
+        let wad_type = match &data[0..4] {
+            b"IWAD" => WadType::IWAD,
+            b"PWAD" => WadType::PWAD,
+            _ => return Err("not a WAD file, the header must start with IWAD or PWAD".to_owned())
+        };
+        let count = read_usize(&data, 4, "lump count")?;
+        let directory = read_usize(&data, 8, "directory offset")?;
+        let directory_end = count.checked_mul(DIRECTORY_ENTRY_SIZE)
+            .and_then(|size| size.checked_add(directory))
+            .filter(|end| *end <= data.len())
+            .ok_or_else(|| format!("WAD directory of {count} lumps at {directory} is past the end of the file"))?;
+        let lumps = data[directory..directory_end]
+            .chunks_exact(DIRECTORY_ENTRY_SIZE)
+            .enumerate()
+            .map(|(idx, entry)| {
+                let offset = read_usize(entry, 0, "lump offset")?;
+                let size = read_usize(entry, 4, "lump size")?;
+                let name = lump_name(&entry[8..16]);
+                if offset.checked_add(size).is_none_or(|end| end > data.len()) {
+                    return Err(format!("lump {idx} ({name}) is past the end of the file"));
+                }
+                Ok(Lump { name, data: LumpData::Original { offset, size } })
+            })
+            .collect::<Result<_, String>>()?;
+        Ok(Self { wad_type, lumps, data })
+    }
+
+    pub fn lumps(&self) -> &[Lump] {
+        &self.lumps
+    }
+
+    pub fn lump_data(&self, idx: usize) -> Option<&[u8]> {
+        match &self.lumps.get(idx)?.data {
+            LumpData::Original { offset, size } => Some(&self.data[*offset..*offset + *size]),
+            LumpData::New(data) => Some(data)
+        }
+    }
+
+    // like the game, the last lump with the name wins
+    pub fn find(&self, name: &str) -> Option<usize> {
+        self.lumps.iter().rposition(|lump| lump.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn read(&self, name: &str) -> Option<&[u8]> {
+        self.lump_data(self.find(name)?)
+    }
+
+    // the lump keeps its name and place in the directory
+    pub fn replace(&mut self, idx: usize, data: Vec<u8>) -> Result<(), String> {
+        let lump = self.lumps.get_mut(idx).ok_or_else(|| format!("there is no lump {idx}"))?;
+        lump.data = LumpData::New(data);
+        Ok(())
+    }
+
+    // adds a lump to the end of the directory, returns its index
+    pub fn push(&mut self, name: &str, data: Vec<u8>) -> Result<usize, String> {
+        check_lump_name(name)?;
+        self.lumps.push(Lump { name: name.to_owned(), data: LumpData::New(data) });
+        Ok(self.lumps.len() - 1)
+    }
+
+    // replaces the lump the game would use if there is one, otherwise adds it
+    pub fn set(&mut self, name: &str, data: Vec<u8>) -> Result<usize, String> {
+        match self.find(name) {
+            Some(idx) => self.replace(idx, data).map(|()| idx),
+            None => self.push(name, data)
+        }
+    }
+
+    // lump data in directory order, then the directory
+    pub fn to_bytes(&self) -> Result<Vec<u8>, String> {
+        let mut out = Vec::with_capacity(HEADER_SIZE + self.lumps.iter().map(Lump::size).sum::<usize>());
+        out.extend(self.wad_type.to_string().as_bytes());
+        write_i32(&mut out, self.lumps.len())?;
+        // directory offset, filled in at the end
+        write_i32(&mut out, 0)?;
+        let mut offsets = Vec::with_capacity(self.lumps.len());
+        for idx in 0..self.lumps.len() {
+            offsets.push(out.len());
+            out.extend(self.lump_data(idx).unwrap_or_default());
+        }
+        let directory = out.len();
+        for (lump, offset) in self.lumps.iter().zip(offsets) {
+            write_i32(&mut out, offset)?;
+            write_i32(&mut out, lump.size())?;
+            let mut name = [0; MAX_LUMP_NAME_LENGTH];
+            let length = lump.name.len().min(MAX_LUMP_NAME_LENGTH);
+            name[..length].copy_from_slice(&lump.name.as_bytes()[..length]);
+            out.extend(name);
+        }
+        let mut directory_offset = Vec::new();
+        write_i32(&mut directory_offset, directory)?;
+        out[8..12].copy_from_slice(&directory_offset);
+        Ok(out)
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if self.wad_type == WadType::IWAD || is_iwad(path) {
+            return Err(format!("refusing to write to {}, it's an IWAD", path.display()));
+        }
+        super::write_atomically(path, &self.to_bytes()?)
+    }
+}
+
+// names are padded with NULs, anything after the first one is ignored like the game does
+pub(crate) fn lump_name(bytes: &[u8]) -> String {
+    let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+    String::from_utf8_lossy(&bytes[..end]).into_owned()
+}
+
+#[cfg(test)]
+pub(crate) mod test {
+    use super::*;
+    // builds a WAD the simple way, with the directory at the end
+    pub(crate) fn build_wad(wad_type: &[u8; 4], lumps: &[(&str, &[u8])]) -> Vec<u8> {
+        let mut data = wad_type.to_vec();
+        let directory = HEADER_SIZE + lumps.iter().map(|(_, bytes)| bytes.len()).sum::<usize>();
+        data.extend(i32::try_from(lumps.len()).unwrap().to_le_bytes());
+        data.extend(i32::try_from(directory).unwrap().to_le_bytes());
+        for (_, bytes) in lumps {
+            data.extend(*bytes);
+        }
+        let mut offset = HEADER_SIZE;
+        for (name, bytes) in lumps {
+            data.extend(i32::try_from(offset).unwrap().to_le_bytes());
+            data.extend(i32::try_from(bytes.len()).unwrap().to_le_bytes());
+            let mut padded = [0; MAX_LUMP_NAME_LENGTH];
+            padded[..name.len()].copy_from_slice(name.as_bytes());
+            data.extend(padded);
+            offset += bytes.len();
+        }
+        data
+    }
+    #[test]
+    fn read_wad() {
+        let data = build_wad(b"PWAD", &[
+            ("MAP01", b""),
+            ("SKYDEFS", b"{ }"),
+            ("GAMECONF", b"gameconf"),
+            ("skydefs", b"second"),
+        ]);
+        let wad = Wad::from_bytes(data).unwrap();
+        assert_eq!(wad.wad_type, WadType::PWAD);
+        assert_eq!(wad.lumps().len(), 4);
+        assert_eq!(wad.lumps()[0].name, "MAP01");
+        assert_eq!(wad.lumps()[0].size(), 0);
+        assert_eq!(wad.lump_data(1), Some(&b"{ }"[..]));
+        assert_eq!(wad.read("gameconf"), Some(&b"gameconf"[..]));
+        assert_eq!(wad.find("SKYDEFS"), Some(3));
+        assert_eq!(wad.read("TEXTURE1"), None);
+        assert_eq!(wad.lump_data(4), None);
+    }
+    #[test]
+    fn reject_bad_wads() {
+        assert!(Wad::from_bytes(b"PWA".to_vec()).is_err());
+        assert!(Wad::from_bytes(b"ZIP!\0\0\0\0\0\0\0\0".to_vec()).is_err());
+        let mut data = build_wad(b"IWAD", &[("PLAYPAL", b"palette")]);
+        assert_eq!(Wad::from_bytes(data.clone()).unwrap().wad_type, WadType::IWAD);
+        // lump size past the end of the file
+        let size = data.len() - DIRECTORY_ENTRY_SIZE + 4;
+        data[size..size + 4].copy_from_slice(&100i32.to_le_bytes());
+        assert_eq!(Wad::from_bytes(data).unwrap_err(), "lump 0 (PLAYPAL) is past the end of the file");
+        // directory past the end of the file
+        let mut data = build_wad(b"PWAD", &[]);
+        data[4..8].copy_from_slice(&1i32.to_le_bytes());
+        assert!(Wad::from_bytes(data).is_err());
+    }
+    #[test]
+    fn write_wad() {
+        let data = build_wad(b"PWAD", &[
+            ("MAP01", b""),
+            ("THINGS", b"things"),
+            ("SKYDEFS", b"old skies"),
+            ("GAMECONF", b"gameconf"),
+        ]);
+        // unchanged WADs written the same way come out identical
+        assert_eq!(Wad::from_bytes(data.clone()).unwrap().to_bytes().unwrap(), data);
+
+        let mut wad = Wad::from_bytes(data).unwrap();
+        assert_eq!(wad.set("skydefs", b"new skies".to_vec()), Ok(2));
+        assert_eq!(wad.set("DEMOLOOP", b"demos".to_vec()), Ok(4));
+        assert!(wad.push("TOOLONGNAME", Vec::new()).is_err());
+        let wad = Wad::from_bytes(wad.to_bytes().unwrap()).unwrap();
+        let names: Vec<_> = wad.lumps().iter().map(|lump| lump.name.as_str()).collect();
+        assert_eq!(names, ["MAP01", "THINGS", "SKYDEFS", "GAMECONF", "DEMOLOOP"]);
+        assert_eq!(wad.read("THINGS"), Some(&b"things"[..]));
+        assert_eq!(wad.read("SKYDEFS"), Some(&b"new skies"[..]));
+        assert_eq!(wad.read("GAMECONF"), Some(&b"gameconf"[..]));
+        assert_eq!(wad.read("DEMOLOOP"), Some(&b"demos"[..]));
+    }
+    #[test]
+    fn save_wad() {
+        let dir = std::env::temp_dir().join(format!("doomstruct-save-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let path = dir.join("test.wad");
+
+        let mut wad = Wad::new(WadType::PWAD);
+        wad.push("SKYDEFS", b"skies".to_vec()).unwrap();
+        wad.save(&path).unwrap();
+        assert_eq!(Wad::open(&path).unwrap().read("SKYDEFS"), Some(&b"skies"[..]));
+        assert!(!dir.join("test.wad.tmp").exists());
+
+        // an IWAD in memory or on disk is never written
+        let iwad = Wad::from_bytes(build_wad(b"IWAD", &[])).unwrap();
+        assert!(iwad.save(&path).is_err());
+        std::fs::write(&path, build_wad(b"IWAD", &[])).unwrap();
+        assert!(is_iwad(&path));
+        assert!(wad.save(&path).is_err());
+        assert_eq!(Wad::open(&path).unwrap().wad_type, WadType::IWAD);
+
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
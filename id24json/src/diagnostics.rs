@@ -0,0 +1,220 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use std::fmt::{Display, Formatter};
+
+// ordered so that the worst severity in a list can be found with max()
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error
+}
+
+impl Display for Severity {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Severity::Info    => "info",
+            Severity::Warning => "warning",
+            Severity::Error   => "error",
+        })
+    }
+}
+
+// the codes returned by Rule::code are part of the output format used by CI, don't rename them
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Debug)]
+pub enum Rule {
+    InvalidJson,
+    InvalidUMapInfo,
+    UnsupportedVersion,
+    NewerVersion,
+    NullMetadata,
+    NullFillFlat,
+    NullStatusBarChildren,
+    LegacyPwadFiles,
+    DuplicateSkyTexture,
+    DuplicateSkyFlat,
+    SkyFireMismatch,
+    SkyForegroundMismatch,
+    EmptyDemoLoop,
+    EmptyNumberFonts,
+    EmptyStatusBars,
+    NumberFontStemLength,
+    NumberFontStemNoSigns,
+    NumberFontMissingGlyph,
+    EmptyInterlevelLayers,
+    FinaleBunnyMismatch,
+    FinaleCastMismatch,
+    EmptyCastMembers,
+    CastThingType,
+    TranslationTableSize,
+    UnknownTranslation,
+    MissingLump,
+    InvalidLump,
+    WrongLumpType,
+    LumpCaseMismatch,
+    WadLumpNameLength,
+    WadLumpNameCase,
+    EmptyConditions,
+    EmptyFrames,
+}
+
+impl Rule {
+    pub fn code(self) -> &'static str {
+        match self {
+            Rule::InvalidJson           => "invalid-json",
+            Rule::InvalidUMapInfo       => "invalid-umapinfo",
+            Rule::UnsupportedVersion    => "version-unsupported",
+            Rule::NewerVersion          => "version-newer",
+            Rule::NullMetadata          => "quirk-null-metadata",
+            Rule::NullFillFlat          => "quirk-null-fillflat",
+            Rule::NullStatusBarChildren => "quirk-null-children",
+            Rule::LegacyPwadFiles       => "quirk-pwadfiles",
+            Rule::DuplicateSkyTexture   => "skydefs-duplicate-texture",
+            Rule::DuplicateSkyFlat      => "skydefs-duplicate-flat",
+            Rule::SkyFireMismatch       => "skydefs-fire-mismatch",
+            Rule::SkyForegroundMismatch => "skydefs-foreground-mismatch",
+            Rule::EmptyDemoLoop         => "demoloop-empty",
+            Rule::EmptyNumberFonts      => "sbardef-empty-numberfonts",
+            Rule::EmptyStatusBars       => "sbardef-empty-statusbars",
+            Rule::NumberFontStemLength  => "sbardef-stem-length",
+            Rule::NumberFontStemNoSigns => "sbardef-stem-no-signs",
+            Rule::NumberFontMissingGlyph => "sbardef-missing-glyph",
+            Rule::EmptyInterlevelLayers => "interlevel-empty-layers",
+            Rule::FinaleBunnyMismatch   => "finale-bunny-mismatch",
+            Rule::FinaleCastMismatch    => "finale-cast-mismatch",
+            Rule::EmptyCastMembers      => "finale-empty-cast",
+            Rule::CastThingType         => "finale-cast-thing-type",
+            Rule::TranslationTableSize  => "translation-table-size",
+            Rule::UnknownTranslation    => "unknown-translation",
+            Rule::MissingLump           => "missing-lump",
+            Rule::InvalidLump           => "invalid-lump",
+            Rule::WrongLumpType         => "wrong-lump-type",
+            Rule::LumpCaseMismatch      => "lump-case-mismatch",
+            Rule::WadLumpNameLength     => "wad-lump-name-length",
+            Rule::WadLumpNameCase       => "wad-lump-name-case",
+            Rule::EmptyConditions       => "empty-conditions",
+            Rule::EmptyFrames           => "empty-frames",
+        }
+    }
+
+    // quirks are deviations from the spec that are known to exist in real world lumps (mostly Legacy of Rust)
+    // they are accepted in lenient mode and rejected in strict mode
+    pub fn is_quirk(self) -> bool {
+        matches!(self, Rule::NullMetadata | Rule::NullFillFlat | Rule::NullStatusBarChildren | Rule::LegacyPwadFiles)
+    }
+
+    pub fn severity(self) -> Severity {
+        match self {
+            Rule::NullMetadata |
+            Rule::NullFillFlat |
+            Rule::NullStatusBarChildren |
+            Rule::LegacyPwadFiles |
+            Rule::NewerVersion |
+            Rule::UnknownTranslation |
+            Rule::WadLumpNameCase |
+            Rule::LumpCaseMismatch |
+            Rule::NumberFontStemNoSigns |
+            Rule::NumberFontMissingGlyph => Severity::Warning,
+            Rule::InvalidJson |
+            Rule::InvalidUMapInfo |
+            Rule::UnsupportedVersion |
+            Rule::DuplicateSkyTexture |
+            Rule::DuplicateSkyFlat |
+            Rule::SkyFireMismatch |
+            Rule::SkyForegroundMismatch |
+            Rule::EmptyDemoLoop |
+            Rule::EmptyNumberFonts |
+            Rule::EmptyStatusBars |
+            Rule::NumberFontStemLength |
+            Rule::EmptyInterlevelLayers |
+            Rule::FinaleBunnyMismatch |
+            Rule::FinaleCastMismatch |
+            Rule::EmptyCastMembers |
+            Rule::CastThingType |
+            Rule::TranslationTableSize |
+            Rule::MissingLump |
+            Rule::InvalidLump |
+            Rule::WrongLumpType |
+            Rule::WadLumpNameLength |
+            Rule::EmptyConditions |
+            Rule::EmptyFrames => Severity::Error,
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub rule: Rule,
+    // JSON pointer (RFC 6901) to the offending value, e.g. /data/skies/3/name
+    pub path: String,
+    pub message: String,
+}
+
+impl Diagnostic {
+    pub fn new(rule: Rule, path: impl Into<String>, message: impl Into<String>) -> Self {
+        Self {
+            severity: rule.severity(),
+            rule,
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}[{}] {}: {}", self.severity, self.rule.code(), self.path, self.message)
+    }
+}
+
+pub fn has_errors(diagnostics: &[Diagnostic]) -> bool {
+    diagnostics.iter().any(|d| d.severity == Severity::Error)
+}
+
+// splits a JSON pointer into its reference tokens, undoing the ~0 and ~1 escapes
+pub fn pointer_tokens(path: &str) -> impl Iterator<Item = String> + '_ {
+    path.split('/')
+        .skip(1)
+        .map(|token| token.replace("~1", "/").replace("~0", "~"))
+}
+
+// the other way around, for building a pointer out of an object key
+pub fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+// conditions arrays must be null or non-empty in every lump that has them
+#[allow(clippy::ref_option)]
+pub(super) fn check_conditions<T>(conditions: &Option<Vec<T>>, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if conditions.as_ref().is_some_and(Vec::is_empty) {
+        diagnostics.push(Diagnostic::new(
+            Rule::EmptyConditions,
+            format!("{path}/conditions"),
+            "Conditions must be null or a non-empty array"
+        ));
+    }
+}
+
+// path is the frames array itself, since cast members have two of them
+pub(super) fn check_frames<T>(frames: &[T], path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if frames.is_empty() {
+        diagnostics.push(Diagnostic::new(
+            Rule::EmptyFrames,
+            path,
+            "Animations must have at least one frame"
+        ));
+    }
+}
@@ -0,0 +1,276 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use std::fmt::{Display, Formatter};
+use super::Extra;
+use super::diagnostics::{check_frames, Diagnostic, Rule};
+use super::references::{push_reference, Reference, ReferenceKind};
+
+#[derive(serde_repr::Serialize_repr, serde_repr::Deserialize_repr, Clone, Copy, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema_repr))]
+#[repr(u8)]
+pub enum Type {
+    ArtScreen = 0,
+    BunnyScroller = 1,
+    CastRollCall = 2
+}
+
+impl Display for Type {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Type::ArtScreen     => "Art Screen",
+            Type::BunnyScroller => "Bunny Scroller",
+            Type::CastRollCall  => "Cast Roll Call",
+        })
+    }
+}
+
+// the end of episode 3 in vanilla
+// stitchimage scrolls in from the right of the background, then overlaycount patches named
+// <overlay>0, <overlay>1, ... are drawn one after another at overlayx, overlayy
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Bunny {
+    pub stitchimage: String,
+    pub overlay: String,
+    pub overlaycount: i32,
+    pub overlaysound: i32,
+    pub overlayx: i32,
+    pub overlayy: i32,
+    #[serde(flatten)]
+    pub extra: Extra
+}
+
+impl Default for Bunny {
+    fn default() -> Self {
+        Self {
+            stitchimage: "PFUB1".to_owned(),
+            overlay: "END".to_owned(),
+            overlaycount: 7,
+            overlaysound: 0,
+            overlayx: 108,
+            overlayy: 68,
+            extra: Extra::new()
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CastRollCall {
+    #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+    pub castmembers: Vec<CastMember>,
+    #[serde(flatten)]
+    pub extra: Extra
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CastMember {
+    // shown under the sprite, can be a string mnemonic like $CC_ZOMBIE
+    pub name: String,
+    // played when the member first appears, empty for none
+    pub alertsound: String,
+    // the thing type the member is shown as, an index into the game's thing definitions
+    #[cfg_attr(feature = "schema", schemars(range(min = 0)))]
+    pub thingtype: i32,
+    // looped until the member is shot
+    #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+    pub aliveframes: Vec<CastFrame>,
+    // played once after being shot, the last frame stays up until the next member
+    #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+    pub deathframes: Vec<CastFrame>,
+    #[serde(flatten)]
+    pub extra: Extra
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct CastFrame {
+    pub lump: String,
+    pub flipped: bool,
+    // in seconds
+    pub duration: f32,
+    // played when the frame starts, empty for none
+    pub sound: String,
+    #[serde(flatten)]
+    pub extra: Extra
+}
+
+impl Default for CastFrame {
+    fn default() -> Self {
+        Self {
+            lump: "POSSA1".to_owned(),
+            flipped: false,
+            duration: 0.25,
+            sound: String::new(),
+            extra: Extra::new()
+        }
+    }
+}
+
+impl Default for CastMember {
+    fn default() -> Self {
+        Self {
+            name: "$CC_ZOMBIE".to_owned(),
+            alertsound: "sfx_posit1".to_owned(),
+            thingtype: 1,
+            aliveframes: vec![CastFrame::default()],
+            deathframes: vec![CastFrame { lump: "POSSH0".to_owned(), ..CastFrame::default() }],
+            extra: Extra::new()
+        }
+    }
+}
+
+// sounds aren't included, they're sound names rather than lumps
+pub(super) fn references(
+    bunny: Option<&Bunny>, castrollcall: Option<&CastRollCall>,
+    path: &str, references: &mut Vec<Reference>
+) {
+    if let Some(bunny) = bunny {
+        let path = format!("{path}/bunny");
+        push_reference(references, ReferenceKind::Graphic, &bunny.stitchimage, &path, "stitchimage");
+        push_reference(references, ReferenceKind::Prefix, &bunny.overlay, &path, "overlay");
+    }
+    for (idx, member) in castrollcall.into_iter().flat_map(|castrollcall| &castrollcall.castmembers).enumerate() {
+        for (key, frames) in [("aliveframes", &member.aliveframes), ("deathframes", &member.deathframes)] {
+            for (frame_idx, frame) in frames.iter().enumerate() {
+                push_reference(
+                    references, ReferenceKind::Graphic, &frame.lump,
+                    &format!("{path}/castrollcall/castmembers/{idx}/{key}/{frame_idx}"), "lump"
+                );
+            }
+        }
+    }
+}
+
+// bunny and castrollcall have to be present exactly when the finale type uses them
+pub(super) fn validate(
+    finale_type: Type, bunny: Option<&Bunny>, castrollcall: Option<&CastRollCall>,
+    path: &str, diagnostics: &mut Vec<Diagnostic>
+) {
+    let wants_bunny = finale_type == Type::BunnyScroller;
+    if wants_bunny != bunny.is_some() {
+        diagnostics.push(Diagnostic::new(
+            Rule::FinaleBunnyMismatch,
+            format!("{path}/bunny"),
+            if wants_bunny {
+                "Bunny scroller finales must have bunny properties"
+            } else {
+                "Bunny properties must be null unless the finale type is Bunny Scroller"
+            }
+        ));
+    }
+    let wants_cast = finale_type == Type::CastRollCall;
+    if wants_cast != castrollcall.is_some() {
+        diagnostics.push(Diagnostic::new(
+            Rule::FinaleCastMismatch,
+            format!("{path}/castrollcall"),
+            if wants_cast {
+                "Cast roll call finales must have cast roll call properties"
+            } else {
+                "Cast roll call properties must be null unless the finale type is Cast Roll Call"
+            }
+        ));
+    }
+    if let Some(castrollcall) = castrollcall {
+        if castrollcall.castmembers.is_empty() {
+            diagnostics.push(Diagnostic::new(
+                Rule::EmptyCastMembers,
+                format!("{path}/castrollcall/castmembers"),
+                "Cast roll calls must have at least one cast member"
+            ));
+        }
+        for (idx, member) in castrollcall.castmembers.iter().enumerate() {
+            let path = format!("{path}/castrollcall/castmembers/{idx}");
+            if member.thingtype < 0 {
+                diagnostics.push(Diagnostic::new(
+                    Rule::CastThingType,
+                    format!("{path}/thingtype"),
+                    "Cast member thing types can't be negative"
+                ));
+            }
+            check_frames(&member.aliveframes, &format!("{path}/aliveframes"), diagnostics);
+            check_frames(&member.deathframes, &format!("{path}/deathframes"), diagnostics);
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+    use super::super::parse::ParseMode;
+    fn round_trip(json: &str) -> ID24Json {
+        let data = ID24Json::parse(json, ParseMode::Strict).unwrap();
+        assert!(data.validate().is_empty());
+        let original: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(data.to_value().unwrap(), original);
+        data
+    }
+    #[test]
+    fn art_screen() {
+        let data = round_trip(include_str!("test_files/finale_artscreen.json"));
+        assert_eq!(data.data, ID24JsonData::finale());
+    }
+    #[test]
+    fn bunny_scroller() {
+        let data = round_trip(include_str!("test_files/finale_bunny.json"));
+        let ID24JsonData::Finale { bunny: Some(bunny), .. } = data.data else {
+            panic!("expected a bunny scroller");
+        };
+        assert_eq!(bunny, Bunny::default());
+    }
+    #[test]
+    fn cast_roll_call() {
+        let data = round_trip(include_str!("test_files/finale_cast.json"));
+        let ID24JsonData::Finale { castrollcall: Some(castrollcall), .. } = data.data else {
+            panic!("expected a cast roll call");
+        };
+        assert_eq!(castrollcall.castmembers.len(), 2);
+        assert_eq!(castrollcall.castmembers[0].thingtype, 1);
+        assert_eq!(castrollcall.castmembers[0].aliveframes.len(), 2);
+        assert_eq!(castrollcall.castmembers[0].deathframes.len(), 3);
+        assert!(castrollcall.castmembers[1].aliveframes[1].flipped);
+    }
+    #[test]
+    fn validate_type_mismatch() {
+        let mut diagnostics = Vec::new();
+        validate(Type::BunnyScroller, None, Some(&CastRollCall::default()), "/data", &mut diagnostics);
+        let rules: Vec<Rule> = diagnostics.iter().map(|d| d.rule).collect();
+        assert_eq!(rules, vec![Rule::FinaleBunnyMismatch, Rule::FinaleCastMismatch, Rule::EmptyCastMembers]);
+
+        diagnostics.clear();
+        let mut castrollcall = CastRollCall { castmembers: vec![CastMember::default()], ..CastRollCall::default() };
+        castrollcall.castmembers[0].deathframes.clear();
+        castrollcall.castmembers[0].thingtype = -1;
+        validate(Type::CastRollCall, None, Some(&castrollcall), "/data", &mut diagnostics);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, Rule::CastThingType);
+        assert_eq!(diagnostics[0].path, "/data/castrollcall/castmembers/0/thingtype");
+        assert_eq!(diagnostics[1].rule, Rule::EmptyFrames);
+        assert_eq!(diagnostics[1].path, "/data/castrollcall/castmembers/0/deathframes");
+    }
+    #[test]
+    fn cast_references() {
+        let castrollcall = CastRollCall { castmembers: vec![CastMember::default()], ..CastRollCall::default() };
+        let mut found = Vec::new();
+        references(None, Some(&castrollcall), "/data", &mut found);
+        let paths: Vec<&str> = found.iter().map(|reference| reference.path.as_str()).collect();
+        assert_eq!(paths, vec![
+            "/data/castrollcall/castmembers/0/aliveframes/0/lump",
+            "/data/castrollcall/castmembers/0/deathframes/0/lump"
+        ]);
+    }
+}
@@ -0,0 +1,304 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use super::{serialize_vec_non_empty, Extra};
+use super::diagnostics::{check_conditions, check_frames, Diagnostic, Rule};
+use super::references::{push_reference, Reference, ReferenceKind};
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Layer {
+    anims: Vec<Anim>,
+    #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+    conditions: Option<Vec<Condition>>,
+    #[serde(flatten)]
+    extra: Extra
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct Anim {
+    x: u16,
+    y: u16,
+    #[serde(serialize_with = "serialize_vec_non_empty")]
+    #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+    frames: Vec<Frame>,
+    #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+    conditions: Option<Vec<Condition>>,
+    #[serde(flatten)]
+    extra: Extra
+}
+
+pub(super) fn validate(layers: Option<&Vec<Layer>>, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    let Some(layers) = layers else {
+        return;
+    };
+    if layers.is_empty() {
+        diagnostics.push(Diagnostic::new(
+            Rule::EmptyInterlevelLayers,
+            format!("{path}/layers"),
+            "Layers must be null or a non-empty array"
+        ));
+    }
+    for (idx, layer) in layers.iter().enumerate() {
+        let path = format!("{path}/layers/{idx}");
+        check_conditions(&layer.conditions, &path, diagnostics);
+        for (anim_idx, anim) in layer.anims.iter().enumerate() {
+            let path = format!("{path}/anims/{anim_idx}");
+            check_frames(&anim.frames, &format!("{path}/frames"), diagnostics);
+            check_conditions(&anim.conditions, &path, diagnostics);
+        }
+    }
+}
+
+// empty conditions arrays mean the same as null, so rewrite them into the spec conformant form
+pub(super) fn normalise(layers: Option<&mut Vec<Layer>>) {
+    for layer in layers.into_iter().flatten() {
+        if layer.conditions.as_ref().is_some_and(Vec::is_empty) {
+            layer.conditions = None;
+        }
+        for anim in &mut layer.anims {
+            if anim.conditions.as_ref().is_some_and(Vec::is_empty) {
+                anim.conditions = None;
+            }
+        }
+    }
+}
+
+pub(super) fn references(layers: Option<&Vec<Layer>>, path: &str, references: &mut Vec<Reference>) {
+    for (idx, layer) in layers.into_iter().flatten().enumerate() {
+        for (anim_idx, anim) in layer.anims.iter().enumerate() {
+            for (frame_idx, frame) in anim.frames.iter().enumerate() {
+                push_reference(
+                    references, ReferenceKind::Graphic, &frame.image,
+                    &format!("{path}/layers/{idx}/anims/{anim_idx}/frames/{frame_idx}"), "image"
+                );
+            }
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct Frame {
+    image: String,
+    #[serde(rename = "type")]
+    frame_type: FrameType,
+    duration: f32,
+    maxduration: f32,
+    #[serde(flatten)]
+    extra: Extra
+}
+
+impl serde::Serialize for FrameType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        serializer.serialize_u16(self.to_u16())
+    }
+}
+
+// only one duration bit may be set, optionally combined with the random offset bit
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for FrameType {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "FrameType".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let values: Vec<u16> = (0..=0b1_0000_0000_0111)
+            .filter(|value| FrameType::from_u16(*value).is_ok_and(|frame_type| frame_type.to_u16() == *value))
+            .collect();
+        schemars::json_schema!({
+            "description": "Bitfield: 1 infinite, 2 fixed, 4 random duration, plus 4096 for a random start offset",
+            "type": "integer",
+            "enum": values
+        })
+    }
+}
+
+impl<'a> serde::Deserialize<'a> for FrameType {
+    fn deserialize<D>(deserializer: D) -> Result<FrameType, D::Error> where D: serde::Deserializer<'a> {
+        let value = u16::deserialize(deserializer)?;
+        FrameType::from_u16(value).map_err(serde::de::Error::custom)
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+struct FrameType {
+    random_offset: bool,
+    duration: Duration
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Duration {
+    None,
+    Infinite,
+    Fixed,
+    Random
+}
+
+impl FrameType {
+    fn from_u16(value: u16) -> Result<Self, &'static str> {
+        let duration = match value & 0b111 {
+            0b000 => Duration::None,
+            0b001 => Duration::Infinite,
+            0b010 => Duration::Fixed,
+            0b100 => Duration::Random,
+            _ => return Err("Multiple durations specified."),
+        };
+        let random_offset = (value & 0b1_0000_0000_0000) != 0;
+
+        Ok(Self {
+            random_offset,
+            duration,
+        })
+    }
+
+    fn to_u16(&self) -> u16 {
+        let duration_bits = match self.duration {
+            Duration::None     => 0b000,
+            Duration::Infinite => 0b001,
+            Duration::Fixed    => 0b010,
+            Duration::Random   => 0b100,
+        };
+        let offset_bit = if self.random_offset { 0b1_0000_0000_0000 } else { 0 };
+
+        duration_bits | offset_bit
+    }
+}
+
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct Condition {
+    condition: ConditionType,
+    param: u8,
+    #[serde(flatten)]
+    extra: Extra
+}
+
+#[derive(serde_repr::Serialize_repr, serde_repr::Deserialize_repr, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema_repr))]
+#[repr(u8)]
+enum ConditionType {
+    None             = 0,
+    CurrMapGreater   = 1, // Current map number is greater than the param value
+    CurrMapEqual     = 2, // Current map number is equal to the param value
+    MapVisited       = 3, // The map number corresponding to the param value has been visited
+    CurrMapNotSecret = 4, // The current map is not a secret map
+    AnySecretVisited = 5, // Any secret map has been visited
+    OnFinishedScreen = 6, // The current screen is the "finished" screen
+    OnEnteringScreen = 7, // The current screen is the “entering” screen
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+    #[test]
+    fn validate_empty_layers() {
+        let mut diagnostics = Vec::new();
+        validate(None, "/data", &mut diagnostics);
+        assert!(diagnostics.is_empty());
+        validate(Some(&Vec::new()), "/data", &mut diagnostics);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::EmptyInterlevelLayers);
+        assert_eq!(diagnostics[0].path, "/data/layers");
+    }
+    #[test]
+    fn validate_layer_contents() {
+        let json = r#"{
+            "type": "interlevel",
+            "version": "1.0.0",
+            "metadata": { },
+            "data":
+            {
+                "backgroundimage": "INTERPIC",
+                "music": "D_DM2INT",
+                "layers":
+                [
+                    {
+                        "anims":
+                        [
+                            {
+                                "x": 10,
+                                "y": 20,
+                                "frames": [],
+                                "conditions": null
+                            },
+                            {
+                                "x": 10,
+                                "y": 20,
+                                "frames":
+                                [
+                                    { "image": "WIA00000", "type": 2, "duration": 0.5, "maxduration": 0 }
+                                ],
+                                "conditions": []
+                            }
+                        ],
+                        "conditions": []
+                    }
+                ]
+            }
+        }"#;
+        let data: ID24Json = serde_json::from_str(json).unwrap();
+        let diagnostics = data.validate();
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0].rule, Rule::EmptyConditions);
+        assert_eq!(diagnostics[0].path, "/data/layers/0/conditions");
+        assert_eq!(diagnostics[1].rule, Rule::EmptyFrames);
+        assert_eq!(diagnostics[1].path, "/data/layers/0/anims/0/frames");
+        assert_eq!(diagnostics[2].rule, Rule::EmptyConditions);
+        assert_eq!(diagnostics[2].path, "/data/layers/0/anims/1/conditions");
+    }
+    #[test]
+    fn normalise_empty_conditions() {
+        let mut data: ID24Json = serde_json::from_str(r#"{
+            "type": "interlevel",
+            "version": "1.0.0",
+            "metadata": { },
+            "data":
+            {
+                "backgroundimage": "INTERPIC",
+                "music": "D_DM2INT",
+                "layers":
+                [
+                    {
+                        "anims":
+                        [
+                            {
+                                "x": 10,
+                                "y": 20,
+                                "frames":
+                                [
+                                    { "image": "WIA00000", "type": 2, "duration": 0.5, "maxduration": 0 }
+                                ],
+                                "conditions": []
+                            }
+                        ],
+                        "conditions": []
+                    }
+                ]
+            }
+        }"#).unwrap();
+        // written as is until normalised
+        let value = data.to_value().unwrap();
+        assert_eq!(value["data"]["layers"][0]["conditions"], serde_json::json!([]));
+        assert_eq!(value["data"]["layers"][0]["anims"][0]["conditions"], serde_json::json!([]));
+        data.normalise();
+        assert!(data.validate().is_empty());
+        let value = data.to_value().unwrap();
+        assert!(value["data"]["layers"][0]["conditions"].is_null());
+        assert!(value["data"]["layers"][0]["anims"][0]["conditions"].is_null());
+    }
+}
@@ -0,0 +1,597 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+pub mod skydefs;
+pub mod gameconf;
+pub mod demoloop;
+pub mod interlevel;
+pub mod finale;
+pub mod trakinfo;
+pub mod sbardef;
+pub mod translation;
+pub mod references;
+pub mod umapinfo;
+pub mod diagnostics;
+pub mod parse;
+pub mod preserve;
+pub mod version;
+pub mod metadata;
+#[cfg(feature = "schema")]
+pub mod schema;
+
+use serde::{Serialize, Serializer};
+use skydefs::{Sky, FlatMapping};
+use diagnostics::{Diagnostic, Rule};
+use metadata::Metadata;
+
+// TODO: add Display impls for all the enum types that need drop downs in the gui
+
+// any keys that aren't part of the spec, kept in their original order so port specific extensions survive a save
+pub type Extra = serde_json::Map<String, serde_json::Value>;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+#[serde(tag = "type", content = "data", rename_all = "lowercase")]
+pub enum ID24JsonData {
+    GAMECONF {
+        title: Option<String>,
+        author: Option<String>,
+        description: Option<String>,
+        version: Option<String>,
+        iwad: Option<String>,
+        // TODO: ask for clarification on spec, notes below
+        // spec says its called pwads, official GAMECONFS use pwadfiles
+        // ID24Json remembers which one was read so it can be written back the same way
+        #[serde(rename = "pwads", alias = "pwadfiles")]
+        pwadfiles: Option<Vec<String>>,
+        dehfiles: Option<Vec<String>>, // not mentioned at all in spec but present in official GAMECONFS
+        playertranslations: Option<Vec<String>>, // spec says it can be null, does *not* say it can be undefined. it is undefined in official GAMECONFS
+        wadtranslation: Option<String>, // same as above
+        executable: Option<gameconf::Executable>,
+        mode: Option<gameconf::Mode>,
+        options: Option<gameconf::Options>,
+        #[serde(flatten)]
+        extra: Extra
+    },
+    DEMOLOOP {
+        #[serde(serialize_with = "serialize_vec_non_empty")]
+        #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+        entries: Vec<demoloop::Entry>,
+        #[serde(flatten)]
+        extra: Extra
+    },
+    #[serde(rename = "statusbar")]
+    SBARDEF {
+        #[serde(serialize_with = "serialize_vec_non_empty")]
+        #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+        numberfonts: Vec<sbardef::NumberFont>,
+        #[serde(serialize_with = "serialize_vec_non_empty")]
+        #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+        statusbars: Vec<sbardef::StatusBar>,
+        #[serde(flatten)]
+        extra: Extra
+    },
+    SKYDEFS {
+        #[serde(serialize_with = "serialize_vec_as_null")]
+        skies: Option<Vec<Sky>>,
+        #[serde(serialize_with = "serialize_vec_as_null")]
+        flatmapping: Option<Vec<FlatMapping>>,
+        #[serde(flatten)]
+        extra: Extra
+    },
+    TRAKINFO(trakinfo::TrakInfo), // TODO: split this out for now, but i hope that formalized TRAKINFO ends up using the same root
+    Interlevel {
+        backgroundimage: String,
+        music: String,
+        #[serde(serialize_with = "serialize_vec_as_null")]
+        #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+        layers: Option<Vec<interlevel::Layer>>,
+        #[serde(flatten)]
+        extra: Extra
+    },
+    Finale {
+        #[serde(rename = "type")]
+        finale_type: finale::Type,
+        music: String,
+        background: String,
+        donextmap: bool,
+        // only used by the finale type of the same name, null otherwise
+        bunny: Option<finale::Bunny>,
+        castrollcall: Option<finale::CastRollCall>,
+        #[serde(flatten)]
+        extra: Extra
+    },
+    Translation {
+        name: String,
+        sbartranslation: bool,
+        // palette index -> palette index
+        #[cfg_attr(feature = "schema", schemars(length(min = 256, max = 256)))]
+        table: Vec<u8>,
+        #[serde(flatten)]
+        extra: Extra
+    }
+}
+
+#[allow(clippy::ref_option)]
+// TODO: use this for all of the Option<Vec<T>> where it should only be null or non-empty
+// suppress the warning because we do actually need it this way for serde
+fn serialize_vec_as_null<T: Serialize, S>(vec: &Option<Vec<T>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    match vec {
+        Some(v) if !v.is_empty() => vec.serialize(serializer),
+        _ => serializer.serialize_none()
+    }
+}
+
+#[allow(clippy::ref_option)]
+fn serialize_vec_non_empty<T: Serialize, S>(vec: &Vec<T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    if vec.is_empty() {
+        return Err(serde::ser::Error::custom("Array must not be empty"));
+    }
+    vec.serialize(serializer)
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct ID24Json {
+    version: ID24JsonVersion,
+    pub metadata: Option<Metadata>, // ID24 spec says this can't ever be null but LoR has null in its SBARDEF
+    #[serde(flatten)]
+    pub data: ID24JsonData,
+    // has to come after data so that it only picks up the keys data didn't use
+    #[serde(flatten)]
+    pub extra: Extra,
+    #[serde(skip)]
+    legacy_pwadfiles: bool,
+}
+
+// field order matters, the derived ordering compares major first
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct ID24JsonVersion {
+    major: u8,
+    minor: u8,
+    revision: u8,
+}
+
+impl ID24JsonVersion {
+    pub const fn new(major: u8, minor: u8, revision: u8) -> Self {
+        Self { major, minor, revision }
+    }
+}
+
+impl std::fmt::Display for ID24JsonVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.revision)
+    }
+}
+
+impl serde::Serialize for ID24JsonVersion {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        serializer.serialize_str(self.to_string().as_ref())
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for ID24JsonVersion {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "ID24JsonVersion".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        schemars::json_schema!({
+            "type": "string",
+            "pattern": "^[0-9]+\\.[0-9]+\\.[0-9]+$"
+        })
+    }
+}
+
+impl<'a> serde::Deserialize<'a> for ID24JsonVersion {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::Deserializer<'a> {
+        let s = String::deserialize(deserializer)?;
+        let parts: Vec<&str> = s.split('.').collect();
+        if parts.len() != 3 {
+            return Err(serde::de::Error::custom("Expected format 'major.minor.revision'"));
+        }
+
+        let major = parts[0].parse::<u8>()
+            .map_err(|_| serde::de::Error::custom("Invalid major version"))?;
+        let minor = parts[1].parse::<u8>()
+            .map_err(|_| serde::de::Error::custom("Invalid minor version"))?;
+        let revision = parts[2].parse::<u8>()
+            .map_err(|_| serde::de::Error::custom("Invalid revision version"))?;
+
+        Ok(ID24JsonVersion { major, minor, revision })
+    }
+}
+
+impl ID24JsonData {
+    pub fn gameconf() -> Self {
+        Self::GAMECONF {
+            title: None,
+            author: None,
+            description: None,
+            version: None,
+            iwad: None,
+            pwadfiles: None,
+            dehfiles: None,
+            playertranslations: None,
+            wadtranslation: None,
+            executable: None,
+            mode: None,
+            options: None,
+            extra: Extra::new()
+        }
+    }
+
+    pub fn skydefs() -> Self {
+        Self::SKYDEFS {
+            skies: None,
+            flatmapping: None,
+            extra: Extra::new()
+        }
+    }
+
+    pub fn demoloop() -> Self {
+        Self::DEMOLOOP {
+            entries: Vec::new(),
+            extra: Extra::new()
+        }
+    }
+
+    pub fn finale() -> Self {
+        Self::Finale {
+            finale_type: finale::Type::ArtScreen,
+            music: "D_VICTOR".to_owned(),
+            background: "VICTORY2".to_owned(),
+            donextmap: false,
+            bunny: None,
+            castrollcall: None,
+            extra: Extra::new()
+        }
+    }
+
+    pub fn trakinfo() -> Self {
+        Self::TRAKINFO(trakinfo::TrakInfo::default())
+    }
+
+    pub fn translation() -> Self {
+        Self::Translation {
+            name: String::new(),
+            sbartranslation: false,
+            table: translation::identity(),
+            extra: Extra::new()
+        }
+    }
+
+    // the name used in the type field
+    pub fn type_name(&self) -> &'static str {
+        match self {
+            Self::GAMECONF { .. }    => "gameconf",
+            Self::DEMOLOOP { .. }    => "demoloop",
+            Self::SBARDEF { .. }     => "statusbar",
+            Self::SKYDEFS { .. }     => "skydefs",
+            Self::TRAKINFO(_)        => "trakinfo",
+            Self::Interlevel { .. }  => "interlevel",
+            Self::Finale { .. }      => "finale",
+            Self::Translation { .. } => "translation",
+        }
+    }
+
+    // the name the game looks for in a WAD
+    // interlevel, finale and translation lumps are found through whatever refers to them so can be called anything
+    pub fn lump_name(&self) -> Option<&'static str> {
+        match self {
+            Self::GAMECONF { .. } => Some("GAMECONF"),
+            Self::DEMOLOOP { .. } => Some("DEMOLOOP"),
+            Self::SBARDEF { .. }  => Some("SBARDEF"),
+            Self::SKYDEFS { .. }  => Some("SKYDEFS"),
+            Self::TRAKINFO(_)     => Some("TRAKINFO"),
+            Self::Interlevel { .. } |
+            Self::Finale { .. } |
+            Self::Translation { .. } => None
+        }
+    }
+
+    pub fn extra(&self) -> Option<&Extra> {
+        match self {
+            Self::GAMECONF { extra, .. } |
+            Self::DEMOLOOP { extra, .. } |
+            Self::SBARDEF { extra, .. } |
+            Self::SKYDEFS { extra, .. } |
+            Self::Interlevel { extra, .. } |
+            Self::Finale { extra, .. } |
+            Self::Translation { extra, .. } => Some(extra),
+            Self::TRAKINFO(_) => None
+        }
+    }
+
+    // TODO: this seems like maybe not the best way?
+    fn check_duplicates<T, F>(
+        items: Option<&Vec<T>>, get_name: F, path: &str, field: &str, rule: Rule, item_type: &str,
+        diagnostics: &mut Vec<Diagnostic>
+    )
+    where F: Fn(&T) -> &str
+    {
+        if let Some(items) = items {
+            let mut seen = std::collections::HashSet::new();
+            for (idx, name) in items.iter().map(get_name).enumerate() {
+                if !seen.insert(name) {
+                    diagnostics.push(Diagnostic::new(
+                        rule,
+                        format!("{path}/{idx}/{field}"),
+                        format!("Duplicate {item_type} name '{name}'")
+                    ));
+                }
+            }
+        }
+    }
+
+    pub fn validate(&self, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+        match self {
+            Self::SKYDEFS { skies, flatmapping, .. } => {
+                for (idx, sky) in skies.iter().flatten().enumerate() {
+                    sky.validate(&format!("{path}/skies/{idx}"), diagnostics);
+                }
+                Self::check_duplicates(
+                    skies.as_ref(), |sky| &sky.backgroundtex.name,
+                    &format!("{path}/skies"), "name", Rule::DuplicateSkyTexture, "sky texture", diagnostics
+                );
+                Self::check_duplicates(
+                    flatmapping.as_ref(), |mapping| &mapping.flat,
+                    &format!("{path}/flatmapping"), "flat", Rule::DuplicateSkyFlat, "sky flat", diagnostics
+                );
+            },
+            Self::DEMOLOOP { entries, .. } => {
+                if entries.is_empty() {
+                    diagnostics.push(Diagnostic::new(
+                        Rule::EmptyDemoLoop,
+                        format!("{path}/entries"),
+                        "Demo loop must have at least one entry"
+                    ));
+                }
+            },
+            Self::SBARDEF { numberfonts, statusbars, .. } => {
+                sbardef::validate(numberfonts, statusbars, path, diagnostics);
+            },
+            Self::Interlevel { layers, .. } => {
+                interlevel::validate(layers.as_ref(), path, diagnostics);
+            },
+            Self::Finale { finale_type, bunny, castrollcall, .. } => {
+                finale::validate(*finale_type, bunny.as_ref(), castrollcall.as_ref(), path, diagnostics);
+            },
+            Self::Translation { table, .. } => {
+                translation::validate(table, path, diagnostics);
+            },
+            Self::GAMECONF { .. } |
+            Self::TRAKINFO(_) => ()
+        }
+    }
+}
+
+impl ID24Json {
+    // a new lump written with the current version for its type
+    pub fn new(data: ID24JsonData) -> Self {
+        Self {
+            version: version::current(data.type_name()).unwrap_or(ID24JsonVersion::new(1, 0, 0)),
+            metadata: Some(Metadata::default()),
+            data,
+            extra: Extra::new(),
+            legacy_pwadfiles: false
+        }
+    }
+
+    // call right before saving, fills in the metadata even if the lump didn't have any
+    pub fn touch(&mut self, application: &str) {
+        self.metadata
+            .get_or_insert_with(Metadata::default)
+            .touch(application, std::time::SystemTime::now());
+    }
+
+    // collects every problem in the lump instead of stopping at the first one
+    pub fn validate(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        diagnostics.extend(version::check(self.data.type_name(), self.version));
+        self.validate_quirks(&mut diagnostics);
+        self.data.validate("/data", &mut diagnostics);
+        diagnostics
+    }
+}
+
+impl Default for ID24Json {
+    fn default() -> Self {
+        Self::new(ID24JsonData::skydefs())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn fail_on_missing_root_fields() {
+        let json = r#"{
+            "type": "skydefs",
+            "version": "1.0.0",
+            "metadata": { },
+            "data": null
+        }"#;
+        assert!(serde_json::from_str::<ID24Json>(json).is_err());
+        let json = r#"{
+            "type": "skydefs",
+            "version": "1.0.0",
+            "metadata": { },
+        }"#;
+        assert!(serde_json::from_str::<ID24Json>(json).is_err());
+        let json = r#"{
+            "type": "skydefs",
+            "metadata": { },
+            "data":
+            {
+                "skies": null,
+                "flatmapping": null
+            }
+        }"#;
+        assert!(serde_json::from_str::<ID24Json>(json).is_err());
+    }
+    #[test]
+    fn fail_on_invalid_version() {
+        let json = r#"{
+            "type": "skydefs",
+            "version": "1.0",
+            "metadata": { }
+            "data":
+            {
+                "skies": null,
+                "flatmapping": null
+            }
+        }"#;
+        assert!(serde_json::from_str::<ID24Json>(json).is_err());
+        let json = r#"{
+            "type": "skydefs",
+            "version": "1.0.0.0",
+            "metadata": { }
+            "data":
+            {
+                "skies": null,
+                "flatmapping": null
+            }
+        }"#;
+        assert!(serde_json::from_str::<ID24Json>(json).is_err());
+        let json = r#"{
+            "type": "skydefs",
+            "version": "one",
+            "metadata": { }
+            "data":
+            {
+                "skies": null,
+                "flatmapping": null
+            }
+        }"#;
+        assert!(serde_json::from_str::<ID24Json>(json).is_err());
+        let json = r#"{
+            "type": "skydefs",
+            "version": 1.0.0,
+            "metadata": { }
+            "data":
+            {
+                "skies": null,
+                "flatmapping": null
+            }
+        }"#;
+        assert!(serde_json::from_str::<ID24Json>(json).is_err());
+    }
+    #[test]
+    fn validate_reports_every_problem() {
+        let json = r#"{
+            "type": "skydefs",
+            "version": "1.0.0",
+            "metadata": { },
+            "data":
+            {
+                "skies":
+                [
+                    { "type": 0, "name": "SKY1", "mid": 100, "scrollx": 0, "scrolly": 0, "scalex": 1, "scaley": 1, "fire": null, "foregroundtex": null },
+                    { "type": 0, "name": "SKY2", "mid": 100, "scrollx": 0, "scrolly": 0, "scalex": 1, "scaley": 1, "fire": null, "foregroundtex": null },
+                    { "type": 0, "name": "SKY1", "mid": 100, "scrollx": 0, "scrolly": 0, "scalex": 1, "scaley": 1, "fire": null, "foregroundtex": null }
+                ],
+                "flatmapping":
+                [
+                    { "flat": "F_SKY1", "sky": "SKY1" },
+                    { "flat": "F_SKY1", "sky": "SKY2" }
+                ]
+            }
+        }"#;
+        let data: ID24Json = serde_json::from_str(json).unwrap();
+        let diagnostics = data.validate();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, Rule::DuplicateSkyTexture);
+        assert_eq!(diagnostics[0].path, "/data/skies/2/name");
+        assert_eq!(diagnostics[0].severity, diagnostics::Severity::Error);
+        assert_eq!(diagnostics[1].rule, Rule::DuplicateSkyFlat);
+        assert_eq!(diagnostics[1].path, "/data/flatmapping/1/flat");
+        assert!(diagnostics::has_errors(&diagnostics));
+    }
+    #[test]
+    fn validate_empty_demoloop() {
+        let json = ID24Json {
+            data: ID24JsonData::demoloop(),
+            ..ID24Json::default()
+        };
+        let diagnostics = json.validate();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule.code(), "demoloop-empty");
+        assert_eq!(diagnostics[0].path, "/data/entries");
+    }
+    #[test]
+    fn pointer_tokens_unescape() {
+        let tokens: Vec<String> = diagnostics::pointer_tokens("/data/a~1b/c~0d/3").collect();
+        assert_eq!(tokens, vec!["data", "a/b", "c~d", "3"]);
+    }
+    #[test]
+    fn round_trip_unknown_fields() {
+        let json = r#"{
+            "type": "skydefs",
+            "version": "1.0.0",
+            "metadata": { },
+            "data": {
+                "skies": [
+                    {
+                        "type": 2,
+                        "name": "SKY1",
+                        "mid": 100,
+                        "scrollx": 0.5,
+                        "scrolly": 0.0,
+                        "scalex": 1.0,
+                        "scaley": 1.0,
+                        "fire": null,
+                        "foregroundtex": {
+                            "name": "SKY2",
+                            "mid": 100,
+                            "scrollx": 0.0,
+                            "scrolly": 0.0,
+                            "scalex": 1.0,
+                            "scaley": 1.0,
+                            "zport_tint": [ 1, 2, 3 ]
+                        },
+                        "zport_sky_b": true,
+                        "zport_sky_a": "second"
+                    }
+                ],
+                "flatmapping": null,
+                "zport_data": { "nested": 1 }
+            },
+            "zport_root": 5
+        }"#;
+        let data: ID24Json = serde_json::from_str(json).unwrap();
+        assert_eq!(data.extra.keys().collect::<Vec<_>>(), vec!["zport_root"]);
+        assert_eq!(data.data.extra().unwrap().keys().collect::<Vec<_>>(), vec!["zport_data"]);
+        let ID24JsonData::SKYDEFS { skies: Some(skies), .. } = &data.data else {
+            panic!("expected skydefs");
+        };
+        // sky level keys stay with the sky rather than the flattened background texture
+        assert_eq!(skies[0].extra.keys().collect::<Vec<_>>(), vec!["zport_sky_b", "zport_sky_a"]);
+        assert!(skies[0].backgroundtex.extra.is_empty());
+        assert_eq!(skies[0].foregroundtex.as_ref().unwrap().extra.keys().collect::<Vec<_>>(), vec!["zport_tint"]);
+
+        // saving puts everything back where it was, unknown keys included
+        assert_eq!(data.to_string_preserving(Some(json)).unwrap(), json);
+        // and the model writes each struct's unknown keys after its known ones
+        let saved = serde_json::to_string(&data).unwrap();
+        assert!(saved.contains(r#""foregroundtex":{"name":"SKY2","mid":100,"scrollx":0.0,"scrolly":0.0,"scalex":1.0,"scaley":1.0,"zport_tint":[1,2,3]},"zport_sky_b":true,"zport_sky_a":"second"}"#));
+    }
+}
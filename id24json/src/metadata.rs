@@ -0,0 +1,156 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use std::time::{SystemTime, UNIX_EPOCH};
+use super::Extra;
+
+// the spec doesn't define any keys, these are just the ones that show up in practice
+// each is None when the key is missing and Some(None) when it's null, so loading and saving keeps both as they were
+// a known key with something other than a string or null in it is left in extra, since it's not ours to fix
+#[derive(serde::Serialize, Clone, Default, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Metadata {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub application: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub author: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<Option<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub comment: Option<Option<String>>,
+    #[serde(flatten)]
+    pub extra: Extra,
+}
+
+impl<'a> serde::Deserialize<'a> for Metadata {
+    fn deserialize<D>(deserializer: D) -> Result<Metadata, D::Error> where D: serde::Deserializer<'a> {
+        let mut extra = Extra::deserialize(deserializer)?;
+        let mut take = |key: &str| match extra.get(key)? {
+            serde_json::Value::String(_) | serde_json::Value::Null => {
+                extra.shift_remove(key).map(|value| value.as_str().map(str::to_owned))
+            },
+            _ => None
+        };
+        Ok(Metadata {
+            application: take("application"),
+            author: take("author"),
+            timestamp: take("timestamp"),
+            comment: take("comment"),
+            extra
+        })
+    }
+}
+
+impl Metadata {
+    // marks the lump as last written by application at the given time
+    pub fn touch(&mut self, application: &str, now: SystemTime) {
+        set(&mut self.application, &mut self.extra, "application", application);
+        set(&mut self.timestamp, &mut self.extra, "timestamp", &timestamp(now));
+    }
+
+    pub fn set_author(&mut self, author: &str) {
+        set(&mut self.author, &mut self.extra, "author", author);
+    }
+
+    pub fn set_comment(&mut self, comment: &str) {
+        set(&mut self.comment, &mut self.extra, "comment", comment);
+    }
+}
+
+// clearing a field removes the key instead of leaving an empty string behind, unless the lump had it as null
+// whatever non-string value was under the key is replaced
+fn set(field: &mut Option<Option<String>>, extra: &mut Extra, key: &str, value: &str) {
+    extra.shift_remove(key);
+    *field = if value.is_empty() {
+        field.take().filter(Option::is_none)
+    } else {
+        Some(Some(value.to_owned()))
+    };
+}
+
+// ISO 8601 in UTC, e.g. 2025-01-31T12:00:00Z
+// converting days to a date is done by hand to avoid pulling in a date crate for one string
+fn timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map_or(0, |duration| duration.as_secs());
+    let (days, secs) = (secs / 86400, secs % 86400);
+    // https://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    format!(
+        "{year:04}-{month:02}-{day:02}T{:02}:{:02}:{:02}Z",
+        secs / 3600, secs / 60 % 60, secs % 60
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+    #[test]
+    fn timestamps() {
+        assert_eq!(timestamp(UNIX_EPOCH), "1970-01-01T00:00:00Z");
+        assert_eq!(timestamp(UNIX_EPOCH + Duration::from_secs(951_782_400)), "2000-02-29T00:00:00Z");
+        assert_eq!(timestamp(UNIX_EPOCH + Duration::from_secs(1_735_689_599)), "2024-12-31T23:59:59Z");
+    }
+    #[test]
+    fn round_trip() {
+        let json = r#"{ "author": "someone", "tool_version": 3 }"#;
+        let mut metadata: Metadata = serde_json::from_str(json).unwrap();
+        assert_eq!(metadata.author, Some(Some("someone".to_owned())));
+        assert_eq!(serde_json::to_string(&metadata).unwrap(), r#"{"author":"someone","tool_version":3}"#);
+
+        metadata.touch("ID24 JSON Editor 1.0", UNIX_EPOCH);
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::json!({
+                "application": "ID24 JSON Editor 1.0",
+                "author": "someone",
+                "timestamp": "1970-01-01T00:00:00Z",
+                "tool_version": 3
+            })
+        );
+    }
+    #[test]
+    fn nulls_and_other_types() {
+        let json = r#"{"author":null,"timestamp":1700000000,"comment":"hi","application":null}"#;
+        let mut metadata: Metadata = serde_json::from_str(json).unwrap();
+        assert_eq!(metadata.author, Some(None));
+        assert_eq!(metadata.timestamp, None);
+        assert_eq!(metadata.extra["timestamp"], serde_json::json!(1_700_000_000));
+        assert_eq!(
+            serde_json::to_value(&metadata).unwrap(),
+            serde_json::from_str::<serde_json::Value>(json).unwrap()
+        );
+
+        // clearing a null field leaves it null, clearing anything else removes it
+        metadata.set_author("");
+        metadata.set_comment("");
+        assert_eq!(metadata.author, Some(None));
+        assert_eq!(metadata.comment, None);
+        // touching replaces the number rather than writing the key twice
+        metadata.touch("ID24 JSON Editor 1.0", UNIX_EPOCH);
+        assert!(!metadata.extra.contains_key("timestamp"));
+        assert_eq!(
+            serde_json::to_string(&metadata).unwrap(),
+            r#"{"application":"ID24 JSON Editor 1.0","author":null,"timestamp":"1970-01-01T00:00:00Z"}"#
+        );
+    }
+}
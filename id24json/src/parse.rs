@@ -0,0 +1,224 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use std::fmt::{Display, Formatter};
+use super::{version, ID24Json, ID24JsonData, ID24JsonVersion};
+use super::diagnostics::{Diagnostic, Rule, Severity};
+
+#[derive(
+    serde::Serialize, serde::Deserialize, strum_macros::VariantArray,
+    Debug, Default, Clone, Copy, Eq, PartialEq
+)]
+#[serde(rename_all = "lowercase")]
+pub enum ParseMode {
+    // reject anything that doesn't follow the spec, including the known quirks
+    Strict,
+    // accept the quirks found in real world lumps and report them as warnings
+    #[default]
+    Lenient
+}
+
+impl Display for ParseMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ParseMode::Strict  => "Strict",
+            ParseMode::Lenient => "Lenient",
+        })
+    }
+}
+
+impl ID24Json {
+    // on failure, every problem that caused the lump to be rejected is returned
+    pub fn parse(text: &str, mode: ParseMode) -> Result<Self, Vec<Diagnostic>> {
+        let mut value: serde_json::Value = serde_json::from_str(text)
+            .map_err(|why| vec![Diagnostic::new(Rule::InvalidJson, "", why.to_string())])?;
+        // older versions are upgraded before anything else looks at the data
+        version::migrate(&mut value);
+        if let (Some(lump), Some(version)) = (
+            value.get("type").and_then(serde_json::Value::as_str),
+            value.get("version").cloned().and_then(|version| serde_json::from_value::<ID24JsonVersion>(version).ok())
+        ) && let Some(diagnostic) = version::check(lump, version)
+            && diagnostic.severity == Severity::Error {
+            return Err(vec![diagnostic]);
+        }
+        // official GAMECONFs use pwadfiles, so remember which spelling the lump used
+        let legacy_pwadfiles = value.get("data")
+            .and_then(|data| data.get("pwadfiles"))
+            .is_some();
+        let mut json: Self = serde_json::from_value(value)
+            .map_err(|why| vec![Diagnostic::new(Rule::InvalidJson, "", why.to_string())])?;
+        json.legacy_pwadfiles = legacy_pwadfiles && matches!(json.data, ID24JsonData::GAMECONF { .. });
+
+        if mode == ParseMode::Strict {
+            let mut diagnostics = json.validate();
+            for diagnostic in &mut diagnostics {
+                if diagnostic.rule.is_quirk() {
+                    diagnostic.severity = Severity::Error;
+                }
+            }
+            if super::diagnostics::has_errors(&diagnostics) {
+                return Err(diagnostics);
+            }
+        }
+        Ok(json)
+    }
+
+    // rewrite the known quirks into their spec conformant form
+    // null fillflats are left alone since there's no way to know what flat was intended
+    pub fn normalise(&mut self) {
+        self.metadata.get_or_insert_with(Default::default);
+        self.legacy_pwadfiles = false;
+        match &mut self.data {
+            ID24JsonData::SBARDEF { statusbars, .. } => {
+                for statusbar in statusbars {
+                    statusbar.normalise();
+                }
+            },
+            ID24JsonData::Interlevel { layers, .. } => super::interlevel::normalise(layers.as_mut()),
+            _ => ()
+        }
+    }
+
+    pub(super) fn validate_quirks(&self, diagnostics: &mut Vec<Diagnostic>) {
+        if self.metadata.is_none() {
+            diagnostics.push(Diagnostic::new(
+                Rule::NullMetadata,
+                "/metadata",
+                "Metadata must be an object"
+            ));
+        }
+        if self.legacy_pwadfiles {
+            diagnostics.push(Diagnostic::new(
+                Rule::LegacyPwadFiles,
+                "/data/pwadfiles",
+                "The spec calls this field pwads"
+            ));
+        }
+    }
+
+    // serializes the lump, keeping the field spellings it was loaded with
+    pub fn to_value(&self) -> serde_json::Result<serde_json::Value> {
+        let mut value = serde_json::to_value(self)?;
+        if self.legacy_pwadfiles
+            && let Some(data) = value.get_mut("data").and_then(serde_json::Value::as_object_mut)
+            && let Some(pwads) = data.remove("pwads") {
+            data.insert("pwadfiles".to_owned(), pwads);
+        }
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    const LOR_SBARDEF: &str = r#"{
+        "type": "statusbar",
+        "version": "1.0.0",
+        "metadata": null,
+        "data":
+        {
+            "numberfonts": [ { "name": "BigRed", "type": 0, "stem": "STT" } ],
+            "statusbars":
+            [
+                {
+                    "height": 32,
+                    "fullscreenrender": false,
+                    "fillflat": null,
+                    "children": null
+                }
+            ]
+        }
+    }"#;
+    const LOR_GAMECONF: &str = r#"{
+        "type": "gameconf",
+        "version": "1.0.0",
+        "metadata": { },
+        "data":
+        {
+            "title": "Legacy of Rust",
+            "pwadfiles": [ "id1.wad" ]
+        }
+    }"#;
+    #[test]
+    fn strict_rejects_quirks() {
+        let diagnostics = ID24Json::parse(LOR_SBARDEF, ParseMode::Strict).unwrap_err();
+        let rules: Vec<Rule> = diagnostics.iter().map(|d| d.rule).collect();
+        assert_eq!(rules, vec![Rule::NullMetadata, Rule::NullFillFlat, Rule::NullStatusBarChildren]);
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Error));
+
+        let diagnostics = ID24Json::parse(LOR_GAMECONF, ParseMode::Strict).unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::LegacyPwadFiles);
+    }
+    #[test]
+    fn lenient_warns_on_quirks() {
+        let json = ID24Json::parse(LOR_SBARDEF, ParseMode::Lenient).unwrap();
+        let diagnostics = json.validate();
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0].path, "/metadata");
+        assert_eq!(diagnostics[1].path, "/data/statusbars/0/fillflat");
+        assert_eq!(diagnostics[2].path, "/data/statusbars/0/children");
+        assert!(diagnostics.iter().all(|d| d.severity == Severity::Warning));
+    }
+    #[test]
+    fn strict_accepts_spec_lumps() {
+        let json = r#"{
+            "type": "gameconf",
+            "version": "1.0.0",
+            "metadata": { },
+            "data": { "pwads": [ "id1.wad" ] }
+        }"#;
+        let json = ID24Json::parse(json, ParseMode::Strict).unwrap();
+        let value = json.to_value().unwrap();
+        assert_eq!(value["data"]["pwads"], serde_json::json!(["id1.wad"]));
+    }
+    #[test]
+    fn lenient_keeps_spelling_unless_normalised() {
+        let mut json = ID24Json::parse(LOR_GAMECONF, ParseMode::Lenient).unwrap();
+        let value = json.to_value().unwrap();
+        assert_eq!(value["data"]["pwadfiles"], serde_json::json!(["id1.wad"]));
+        assert!(value["data"].get("pwads").is_none());
+
+        json.normalise();
+        assert!(json.validate().is_empty());
+        let value = json.to_value().unwrap();
+        assert_eq!(value["data"]["pwads"], serde_json::json!(["id1.wad"]));
+        assert!(value["data"].get("pwadfiles").is_none());
+    }
+    #[test]
+    fn normalise_sbardef() {
+        let mut json = ID24Json::parse(LOR_SBARDEF, ParseMode::Lenient).unwrap();
+        json.normalise();
+        let diagnostics = json.validate();
+        // fillflat can't be fixed automatically
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::NullFillFlat);
+        let value = json.to_value().unwrap();
+        assert_eq!(value["metadata"], serde_json::json!({}));
+        assert_eq!(value["data"]["statusbars"][0]["children"], serde_json::json!([]));
+    }
+    #[test]
+    fn touch_null_metadata() {
+        let mut json = ID24Json::parse(LOR_SBARDEF, ParseMode::Lenient).unwrap();
+        assert!(json.metadata.is_none());
+        json.touch("test");
+        assert_eq!(json.metadata.unwrap().application, Some(Some("test".to_owned())));
+    }
+    #[test]
+    fn invalid_json() {
+        let diagnostics = ID24Json::parse("{", ParseMode::Lenient).unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::InvalidJson);
+    }
+}
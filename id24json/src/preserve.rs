@@ -0,0 +1,453 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+// saving that only touches the parts of a file that were actually edited
+// the original text is parsed into a tree that remembers every bit of whitespace and how each scalar was spelled,
+// then the serialized lump is written out by walking that tree and reusing whatever still matches
+
+use serde_json::Value;
+use super::ID24Json;
+
+// a JSON value as it was written in the original text
+enum Node {
+    // numbers, strings, booleans and null, exactly as written
+    Scalar(String),
+    // the string is the whitespace before the closing bracket
+    Object(Vec<Item>, String),
+    Array(Vec<Item>, String),
+}
+
+struct Item {
+    before: String,
+    // raw key, decoded key and everything between the key and the value, None for array elements
+    key: Option<(String, String, String)>,
+    value: Node,
+    // whitespace between the value and the comma after it
+    after: String,
+}
+
+struct Document {
+    before: String,
+    root: Node,
+    after: String,
+}
+
+struct Parser<'a> {
+    text: &'a str,
+    pos: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn error(&self, what: &str) -> String {
+        format!("{what} at byte {}", self.pos)
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.text.as_bytes().get(self.pos).copied()
+    }
+
+    fn whitespace(&mut self) -> String {
+        let start = self.pos;
+        while matches!(self.peek(), Some(b' ' | b'\t' | b'\n' | b'\r')) {
+            self.pos += 1;
+        }
+        self.text[start..self.pos].to_owned()
+    }
+
+    fn expect(&mut self, byte: u8) -> Result<(), String> {
+        if self.peek() == Some(byte) {
+            self.pos += 1;
+            Ok(())
+        } else {
+            Err(self.error(&format!("expected '{}'", byte as char)))
+        }
+    }
+
+    fn string(&mut self) -> Result<&'a str, String> {
+        let start = self.pos;
+        self.expect(b'"')?;
+        loop {
+            match self.peek() {
+                Some(b'"') => {
+                    self.pos += 1;
+                    return Ok(&self.text[start..self.pos]);
+                },
+                Some(b'\\') => self.pos += 2,
+                Some(_) => self.pos += 1,
+                None => return Err(self.error("unterminated string")),
+            }
+        }
+    }
+
+    fn value(&mut self) -> Result<Node, String> {
+        match self.peek() {
+            Some(b'{') => {
+                self.pos += 1;
+                let (items, close) = self.items(b'}', true)?;
+                Ok(Node::Object(items, close))
+            },
+            Some(b'[') => {
+                self.pos += 1;
+                let (items, close) = self.items(b']', false)?;
+                Ok(Node::Array(items, close))
+            },
+            Some(b'"') => Ok(Node::Scalar(self.string()?.to_owned())),
+            Some(_) => {
+                let start = self.pos;
+                while !matches!(self.peek(), None | Some(b' ' | b'\t' | b'\n' | b'\r' | b',' | b']' | b'}')) {
+                    self.pos += 1;
+                }
+                if start == self.pos {
+                    return Err(self.error("expected a value"));
+                }
+                Ok(Node::Scalar(self.text[start..self.pos].to_owned()))
+            },
+            None => Err(self.error("unexpected end of input")),
+        }
+    }
+
+    fn items(&mut self, close: u8, keyed: bool) -> Result<(Vec<Item>, String), String> {
+        let mut items = Vec::new();
+        let mut before = self.whitespace();
+        if self.peek() == Some(close) {
+            self.pos += 1;
+            return Ok((items, before));
+        }
+        loop {
+            let key = if keyed {
+                let raw = self.string()?;
+                let name = serde_json::from_str(raw).map_err(|why| self.error(&why.to_string()))?;
+                let start = self.pos;
+                self.whitespace();
+                self.expect(b':')?;
+                self.whitespace();
+                Some((raw.to_owned(), name, self.text[start..self.pos].to_owned()))
+            } else {
+                None
+            };
+            let value = self.value()?;
+            let after = self.whitespace();
+            match self.peek() {
+                Some(b',') => {
+                    self.pos += 1;
+                    items.push(Item { before, key, value, after });
+                    before = self.whitespace();
+                },
+                Some(c) if c == close => {
+                    self.pos += 1;
+                    items.push(Item { before, key, value, after: String::new() });
+                    return Ok((items, after));
+                },
+                _ => return Err(self.error("expected ',' or a closing bracket")),
+            }
+        }
+    }
+}
+
+impl Document {
+    fn parse(text: &str) -> Result<Self, String> {
+        let mut parser = Parser { text, pos: 0 };
+        let before = parser.whitespace();
+        let root = parser.value()?;
+        let after = parser.whitespace();
+        if parser.pos != text.len() {
+            return Err(parser.error("trailing characters"));
+        }
+        Ok(Self { before, root, after })
+    }
+}
+
+// how new or changed values get laid out
+struct Style {
+    indent: String,
+    newline: &'static str,
+    colon: String,
+}
+
+impl Default for Style {
+    // same layout as serde_json::to_string_pretty
+    fn default() -> Self {
+        Self {
+            indent: "  ".to_owned(),
+            newline: "\n",
+            colon: ": ".to_owned(),
+        }
+    }
+}
+
+impl Style {
+    fn detect(text: &str, root: &Node) -> Self {
+        let mut style = Self::default();
+        if text.contains("\r\n") {
+            style.newline = "\r\n";
+        }
+        if let Some(indent) = detect_indent(root, "") {
+            style.indent = indent;
+        }
+        if let Node::Object(items, _) = root
+            && let Some((_, _, colon)) = items.first().and_then(|item| item.key.as_ref()) {
+            style.colon.clone_from(colon);
+        }
+        style
+    }
+}
+
+// the indentation of the line an item starts on
+fn line_indent<'a>(before: &'a str, parent: &'a str) -> &'a str {
+    before.rfind('\n').map_or(parent, |idx| &before[idx + 1..])
+}
+
+// the first step in indentation between a container and its children
+fn detect_indent(node: &Node, parent: &str) -> Option<String> {
+    let (Node::Object(items, _) | Node::Array(items, _)) = node else {
+        return None;
+    };
+    items.iter().find_map(|item| {
+        let indent = line_indent(&item.before, parent);
+        if item.before.contains('\n') && indent.len() > parent.len() && indent.starts_with(parent) {
+            Some(indent[parent.len()..].to_owned())
+        } else {
+            detect_indent(&item.value, indent)
+        }
+    })
+}
+
+// model floats are f32, which end up as long f64s like 0.30000001192092896 in a Value
+fn write_scalar(value: &Value, out: &mut String) {
+    if let Value::Number(number) = value
+        && !number.is_i64()
+        && !number.is_u64()
+        && let Some(float) = number.as_f64()
+        && f64::from(float as f32) == float {
+        out.push_str(&(float as f32).to_string());
+        if float.fract() == 0.0 && float.abs() < 1e16 {
+            out.push_str(".0");
+        }
+        return;
+    }
+    out.push_str(&value.to_string());
+}
+
+fn write_pretty(value: &Value, indent: &str, style: &Style, out: &mut String) {
+    let inner = format!("{indent}{}", style.indent);
+    match value {
+        Value::Object(map) if !map.is_empty() => {
+            out.push('{');
+            for (idx, (key, value)) in map.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                out.push_str(style.newline);
+                out.push_str(&inner);
+                out.push_str(&Value::String(key.clone()).to_string());
+                out.push_str(&style.colon);
+                write_pretty(value, &inner, style, out);
+            }
+            out.push_str(style.newline);
+            out.push_str(indent);
+            out.push('}');
+        },
+        Value::Array(vec) if !vec.is_empty() => {
+            out.push('[');
+            for (idx, value) in vec.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                out.push_str(style.newline);
+                out.push_str(&inner);
+                write_pretty(value, &inner, style, out);
+            }
+            out.push_str(style.newline);
+            out.push_str(indent);
+            out.push(']');
+        },
+        Value::Object(_) => out.push_str("{}"),
+        Value::Array(_) => out.push_str("[]"),
+        scalar => write_scalar(scalar, out),
+    }
+}
+
+// integers have to match exactly, anything else only has to agree as far as an f32 can tell
+// so that e.g. 1 isn't rewritten as 1.0 and 0.1 isn't rewritten as 0.10000000149011612
+#[allow(clippy::float_cmp)]
+fn same_scalar(raw: &str, value: &Value) -> bool {
+    let Ok(original) = serde_json::from_str::<Value>(raw) else {
+        return false;
+    };
+    match (&original, value) {
+        (Value::Number(a), Value::Number(b)) if a.is_f64() || b.is_f64() => {
+            a.as_f64().map(|a| a as f32) == b.as_f64().map(|b| b as f32)
+        },
+        (a, b) => a == b,
+    }
+}
+
+// writes one item, separators are left to the caller
+fn write_item(item: &Item, value: &Value, indent: &str, style: &Style, out: &mut String) {
+    out.push_str(&item.before);
+    if let Some((raw, _, colon)) = &item.key {
+        out.push_str(raw);
+        out.push_str(colon);
+    }
+    write_node(&item.value, value, line_indent(&item.before, indent), style, out);
+}
+
+// writes an item that wasn't in the original text, laid out like its neighbour
+fn write_new_item(template: &Item, key: Option<&str>, value: &Value, indent: &str, style: &Style, out: &mut String) {
+    out.push_str(&template.before);
+    if let Some(key) = key {
+        out.push_str(&Value::String(key.to_owned()).to_string());
+        out.push_str(template.key.as_ref().map_or(&style.colon, |(_, _, colon)| colon));
+    }
+    write_pretty(value, line_indent(&template.before, indent), style, out);
+}
+
+fn write_node(node: &Node, value: &Value, indent: &str, style: &Style, out: &mut String) {
+    match (node, value) {
+        (Node::Scalar(raw), _) if same_scalar(raw, value) => out.push_str(raw),
+        (Node::Object(items, close), Value::Object(map)) if !items.is_empty() || map.is_empty() => {
+            let find = |key: &str| items.iter().find(|item| item.key.as_ref().is_some_and(|(_, name, _)| name == key));
+            // keys keep their original order, new ones go right after whatever comes before them in the model
+            let mut keys: Vec<&str> = items.iter()
+                .filter_map(|item| item.key.as_ref().map(|(_, name, _)| name.as_str()))
+                .filter(|name| map.contains_key(*name))
+                .collect();
+            let mut previous = None;
+            for key in map.keys() {
+                if !keys.contains(&key.as_str()) {
+                    let idx = previous.and_then(|previous| keys.iter().position(|k| *k == previous)).map_or(0, |idx| idx + 1);
+                    keys.insert(idx, key);
+                }
+                previous = Some(key.as_str());
+            }
+            out.push('{');
+            let mut template = items.first();
+            for (idx, key) in keys.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                match find(key) {
+                    Some(item) => {
+                        write_item(item, &map[*key], indent, style, out);
+                        template = Some(item);
+                    },
+                    None => write_new_item(template.unwrap(), Some(key), &map[*key], indent, style, out),
+                }
+                if idx + 1 < keys.len() && let Some(item) = find(key) {
+                    out.push_str(&item.after);
+                }
+            }
+            out.push_str(close);
+            out.push('}');
+        },
+        (Node::Array(items, close), Value::Array(vec)) if !items.is_empty() || vec.is_empty() => {
+            // elements are matched up by position
+            out.push('[');
+            for (idx, value) in vec.iter().enumerate() {
+                if idx > 0 {
+                    out.push(',');
+                }
+                match items.get(idx) {
+                    Some(item) => write_item(item, value, indent, style, out),
+                    None => write_new_item(items.last().unwrap(), None, value, indent, style, out),
+                }
+                if idx + 1 < vec.len() && let Some(item) = items.get(idx) {
+                    out.push_str(&item.after);
+                }
+            }
+            out.push_str(close);
+            out.push(']');
+        },
+        _ => write_pretty(value, indent, style, out),
+    }
+}
+
+// writes value using the formatting of source wherever the two agree
+pub fn write_preserving(source: &str, value: &Value) -> Result<String, String> {
+    let document = Document::parse(source)?;
+    let style = Style::detect(source, &document.root);
+    let mut out = document.before.clone();
+    write_node(&document.root, value, line_indent(&document.before, ""), &style, &mut out);
+    out.push_str(&document.after);
+    Ok(out)
+}
+
+// pretty prints like serde_json, without the f32 noise
+pub fn write_pretty_string(value: &Value) -> String {
+    let mut out = String::new();
+    write_pretty(value, "", &Style::default(), &mut out);
+    out
+}
+
+impl ID24Json {
+    // source is the text the lump was loaded from, if there is one
+    pub fn to_string_preserving(&self, source: Option<&str>) -> Result<String, String> {
+        let value = self.to_value().map_err(|why| why.to_string())?;
+        match source {
+            Some(source) => write_preserving(source, &value),
+            None => Ok(write_pretty_string(&value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+    use super::super::parse::ParseMode;
+    const SKYDEFS: &str = include_str!("test_files/skydefs_1.json");
+    #[test]
+    fn untouched_lump_is_unchanged() {
+        let json = ID24Json::parse(SKYDEFS, ParseMode::Lenient).unwrap();
+        assert_eq!(json.to_string_preserving(Some(SKYDEFS)).unwrap(), SKYDEFS);
+    }
+    #[test]
+    fn only_edited_values_change() {
+        let mut json = ID24Json::parse(SKYDEFS, ParseMode::Lenient).unwrap();
+        let ID24JsonData::SKYDEFS { skies: Some(skies), .. } = &mut json.data else {
+            panic!("expected skydefs");
+        };
+        skies[0].backgroundtex.scrollx = 0.3;
+        skies[0].backgroundtex.mid = 120;
+        let saved = json.to_string_preserving(Some(SKYDEFS)).unwrap();
+        let changed: Vec<(&str, &str)> = SKYDEFS.lines().zip(saved.lines())
+            .filter(|(a, b)| a != b)
+            .collect();
+        assert_eq!(changed, vec![
+            ("        \"mid\": 100,", "        \"mid\": 120,"),
+            ("        \"scrollx\": 2.625,", "        \"scrollx\": 0.3,"),
+        ]);
+        assert_eq!(SKYDEFS.lines().count(), saved.lines().count());
+    }
+    #[test]
+    fn new_and_removed_keys() {
+        let source = "{\n\t\"a\": 1,\n\t\"b\": { \"x\": 1 },\n\t\"d\": 4\n}\n";
+        let value = serde_json::json!({ "a": 1, "c": [ 1, 2 ], "b": { "x": 1 }, "e": null });
+        assert_eq!(
+            write_preserving(source, &value).unwrap(),
+            "{\n\t\"a\": 1,\n\t\"c\": [\n\t\t1,\n\t\t2\n\t],\n\t\"b\": { \"x\": 1 },\n\t\"e\": null\n}\n"
+        );
+    }
+    #[test]
+    fn pretty_without_source() {
+        let value = serde_json::json!({ "a": [ 1, 2.5f32 ], "b": { }, "c": 0.1f32, "d": 2.0f32 });
+        assert_eq!(write_pretty_string(&value), "{\n  \"a\": [\n    1,\n    2.5\n  ],\n  \"b\": {},\n  \"c\": 0.1,\n  \"d\": 2.0\n}");
+    }
+    #[test]
+    fn compact_arrays_stay_compact() {
+        let source = r#"{ "a": [ 1, 2 ], "b": 3 }"#;
+        let value = serde_json::json!({ "a": [ 1, 2, 4 ], "b": 3 });
+        assert_eq!(write_preserving(source, &value).unwrap(), r#"{ "a": [ 1, 2, 4 ], "b": 3 }"#);
+    }
+}
@@ -0,0 +1,492 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use std::collections::HashSet;
+use super::{finale, interlevel, sbardef, ID24Json, ID24JsonData};
+use super::demoloop::DemoType;
+use super::diagnostics::{escape_pointer_token, Diagnostic, Rule};
+use super::parse::ParseMode;
+
+// references from one lump to another, these can only be checked against whatever else the editor knows about
+// so they're kept separate from validate, which only looks at the lump itself
+#[derive(Clone, Copy, Eq, PartialEq, Hash, Debug)]
+pub enum ReferenceKind {
+    Translation,
+    Interlevel,
+    Finale,
+    Texture,
+    Flat,
+    Graphic,
+    Music,
+    Demo,
+    Tranmap,
+    // the start of the names of a number font's glyphs
+    NumberFont,
+    // the start of a numbered group of lumps, like the bunny scroller's END0 to END6
+    Prefix
+}
+
+impl ReferenceKind {
+    // the type field of the lump a reference has to point at, None for anything that isn't an ID24 JSON lump
+    pub fn type_name(self) -> Option<&'static str> {
+        match self {
+            ReferenceKind::Translation => Some("translation"),
+            ReferenceKind::Interlevel  => Some("interlevel"),
+            ReferenceKind::Finale      => Some("finale"),
+            ReferenceKind::Texture |
+            ReferenceKind::Flat |
+            ReferenceKind::Graphic |
+            ReferenceKind::Music |
+            ReferenceKind::Demo |
+            ReferenceKind::Tranmap |
+            ReferenceKind::NumberFont |
+            ReferenceKind::Prefix => None
+        }
+    }
+}
+
+// WAD lump names are at most 8 characters, and engines look them up upper case
+pub const MAX_WAD_LUMP_NAME_LENGTH: usize = 8;
+
+// what a lump name field accepts while typing in WAD mode
+pub fn filter_wad_lump_name(input: &str) -> String {
+    input.chars().take(MAX_WAD_LUMP_NAME_LENGTH).collect::<String>().to_uppercase()
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Reference {
+    pub kind: ReferenceKind,
+    pub name: String,
+    // JSON pointer to the value holding the name
+    pub path: String
+}
+
+impl Reference {
+    pub fn new(kind: ReferenceKind, name: impl Into<String>, path: impl Into<String>) -> Self {
+        Self {
+            kind,
+            name: name.into(),
+            path: path.into()
+        }
+    }
+}
+
+// lump names are case insensitive, so everything is stored upper case
+#[derive(Clone, Default, Debug)]
+pub struct KnownLumps {
+    lumps: HashSet<(ReferenceKind, String)>
+}
+
+impl KnownLumps {
+    pub fn insert(&mut self, kind: ReferenceKind, name: &str) {
+        self.lumps.insert((kind, name.to_uppercase()));
+    }
+
+    pub fn contains(&self, kind: ReferenceKind, name: &str) -> bool {
+        self.lumps.contains(&(kind, name.to_uppercase()))
+    }
+}
+
+impl ID24JsonData {
+    pub fn references(&self, path: &str) -> Vec<Reference> {
+        self.lump_names(path, false)
+    }
+
+    // unused also includes the fields the lump doesn't use in its current form, they still have to be valid names
+    fn lump_names(&self, path: &str, unused: bool) -> Vec<Reference> {
+        let mut references = Vec::new();
+        match self {
+            Self::GAMECONF { playertranslations, wadtranslation, .. } => {
+                for (idx, name) in playertranslations.iter().flatten().enumerate() {
+                    references.push(Reference::new(
+                        ReferenceKind::Translation, name, format!("{path}/playertranslations/{idx}")
+                    ));
+                }
+                if let Some(name) = wadtranslation {
+                    references.push(Reference::new(ReferenceKind::Translation, name, format!("{path}/wadtranslation")));
+                }
+            },
+            Self::SBARDEF { numberfonts, statusbars, .. } => {
+                sbardef::references(numberfonts, statusbars, path, &mut references);
+            },
+            Self::DEMOLOOP { entries, .. } => {
+                for (idx, entry) in entries.iter().enumerate() {
+                    let path = format!("{path}/entries/{idx}");
+                    match entry.demo_type {
+                        DemoType::ArtScreen => {
+                            push_reference(&mut references, ReferenceKind::Graphic, &entry.primarylump, &path, "primarylump");
+                            push_reference(&mut references, ReferenceKind::Music, &entry.secondarylump, &path, "secondarylump");
+                        },
+                        DemoType::DemoLump => {
+                            push_reference(&mut references, ReferenceKind::Demo, &entry.primarylump, &path, "primarylump");
+                            // secondarylump isn't used by demos
+                            if unused {
+                                push_reference(&mut references, ReferenceKind::Music, &entry.secondarylump, &path, "secondarylump");
+                            }
+                        }
+                    }
+                }
+            },
+            Self::SKYDEFS { skies, flatmapping, .. } => {
+                for (idx, sky) in skies.iter().flatten().enumerate() {
+                    let path = format!("{path}/skies/{idx}");
+                    push_reference(&mut references, ReferenceKind::Texture, &sky.backgroundtex.name, &path, "name");
+                    if let Some(foregroundtex) = &sky.foregroundtex {
+                        push_reference(&mut references, ReferenceKind::Texture, &foregroundtex.name, &path, "foregroundtex/name");
+                    }
+                }
+                for (idx, mapping) in flatmapping.iter().flatten().enumerate() {
+                    let path = format!("{path}/flatmapping/{idx}");
+                    push_reference(&mut references, ReferenceKind::Flat, &mapping.flat, &path, "flat");
+                    push_reference(&mut references, ReferenceKind::Texture, &mapping.sky, &path, "sky");
+                }
+            },
+            Self::TRAKINFO(trakinfo) => {
+                // tracks are keyed by lump name, so the pointer is to the key's object
+                for track in &trakinfo.tracks {
+                    let path = format!("{path}/{}", escape_pointer_token(&track.lump));
+                    references.push(Reference::new(ReferenceKind::Music, &track.lump, path.clone()));
+                    if let Some(remixed) = &track.remixed {
+                        push_reference(&mut references, ReferenceKind::Music, remixed, &path, "remixed");
+                    }
+                }
+            },
+            Self::Interlevel { backgroundimage, music, layers, .. } => {
+                push_reference(&mut references, ReferenceKind::Graphic, backgroundimage, path, "backgroundimage");
+                push_reference(&mut references, ReferenceKind::Music, music, path, "music");
+                interlevel::references(layers.as_ref(), path, &mut references);
+            },
+            Self::Finale { music, background, bunny, castrollcall, .. } => {
+                push_reference(&mut references, ReferenceKind::Music, music, path, "music");
+                push_reference(&mut references, ReferenceKind::Graphic, background, path, "background");
+                finale::references(bunny.as_ref(), castrollcall.as_ref(), path, &mut references);
+            },
+            Self::Translation { .. } => ()
+        }
+        references
+    }
+}
+
+// empty names mean the field isn't used
+pub(super) fn push_reference(references: &mut Vec<Reference>, kind: ReferenceKind, name: &str, path: &str, field: &str) {
+    if !name.is_empty() {
+        references.push(Reference::new(kind, name, format!("{path}/{field}")));
+    }
+}
+
+impl ID24Json {
+    pub fn references(&self) -> Vec<Reference> {
+        self.data.references("/data")
+    }
+
+    // reports every translation that isn't defined by a translation lump in known
+    // other kinds of lumps can only be checked against the files they're loaded alongside, see check_lumps
+    pub fn validate_references(&self, known: &KnownLumps) -> Vec<Diagnostic> {
+        self.references()
+            .into_iter()
+            .filter(|reference| reference.kind == ReferenceKind::Translation)
+            .filter(|reference| !known.contains(reference.kind, &reference.name))
+            .map(|reference| Diagnostic::new(
+                Rule::UnknownTranslation,
+                reference.path,
+                format!("Translation '{}' isn't defined by any translation lump", reference.name)
+            ))
+            .collect()
+    }
+
+    pub fn check_lump_names(&self, find: impl Fn(ReferenceKind, &str) -> Lookup) -> Vec<Diagnostic> {
+        check_lump_names(&self.references(), find)
+    }
+
+    // only applies when the lump is going into a WAD, PK3s can refer to files by their long names
+    // every lump name field is checked, since they're all written out whether they're used or not
+    pub fn validate_wad_lump_names(&self) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        for reference in self.data.lump_names("/data", true) {
+            if reference.name.chars().count() > MAX_WAD_LUMP_NAME_LENGTH {
+                diagnostics.push(Diagnostic::new(
+                    Rule::WadLumpNameLength,
+                    reference.path,
+                    format!("'{}' is longer than {MAX_WAD_LUMP_NAME_LENGTH} characters, so it can't name a WAD lump", reference.name)
+                ));
+            } else if reference.name.chars().any(char::is_lowercase) {
+                diagnostics.push(Diagnostic::new(
+                    Rule::WadLumpNameCase,
+                    reference.path,
+                    format!("'{}' isn't upper case, WAD lump names are", reference.name)
+                ));
+            }
+        }
+        diagnostics
+    }
+}
+
+// for references to whole lumps, read returns the text of the named lump if it exists
+// every ID24 JSON lump found has to parse and be the kind of lump the reference expects
+pub fn check_lumps(references: &[Reference], read: impl Fn(&str) -> Option<String>) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for reference in references {
+        let Some(text) = read(&reference.name) else {
+            diagnostics.push(Diagnostic::new(
+                Rule::MissingLump,
+                reference.path.clone(),
+                format!("Lump '{}' doesn't exist", reference.name)
+            ));
+            continue;
+        };
+        let Some(expected) = reference.kind.type_name() else {
+            continue;
+        };
+        match ID24Json::parse(&text, ParseMode::Lenient) {
+            Ok(json) if json.data.type_name() != expected => {
+                diagnostics.push(Diagnostic::new(
+                    Rule::WrongLumpType,
+                    reference.path.clone(),
+                    format!(
+                        "Lump '{}' is a {} lump, expected {}",
+                        reference.name, json.data.type_name(), expected
+                    )
+                ));
+            },
+            Ok(_) => (),
+            Err(errors) => {
+                diagnostics.push(Diagnostic::new(
+                    Rule::InvalidLump,
+                    reference.path.clone(),
+                    format!(
+                        "Lump '{}' couldn't be read: {}",
+                        reference.name,
+                        errors.iter().map(|error| error.message.as_str()).collect::<Vec<_>>().join(", ")
+                    )
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+// what looking a name up in the loaded files found
+#[derive(Clone, PartialEq, Debug)]
+pub enum Lookup {
+    Found,
+    // only found by ignoring case, along with the name that would always work
+    // WAD lump names are upper case, so lower case names and full paths in the wrong case only work in PK3s
+    CaseMismatch(String),
+    Missing,
+    // there's no way to look this kind of name up yet
+    Unknown
+}
+
+// the lumps that have to exist for a reference to work
+fn required_lumps(reference: &Reference) -> Vec<String> {
+    match reference.kind {
+        ReferenceKind::NumberFont => (0..10).map(|digit| format!("{}NUM{digit}", reference.name)).collect(),
+        ReferenceKind::Prefix => vec![format!("{}0", reference.name)],
+        _ => vec![reference.name.clone()]
+    }
+}
+
+// for references to anything but ID24 JSON lumps, find looks a name up in the loaded files
+// translations are left to validate_references and JSON lumps to check_lumps
+pub fn check_lump_names(references: &[Reference], find: impl Fn(ReferenceKind, &str) -> Lookup) -> Vec<Diagnostic> {
+    let mut diagnostics = Vec::new();
+    for reference in references.iter().filter(|reference| reference.kind.type_name().is_none()) {
+        for name in required_lumps(reference) {
+            match find(reference.kind, &name) {
+                Lookup::Found |
+                Lookup::Unknown => (),
+                Lookup::CaseMismatch(found) => diagnostics.push(Diagnostic::new(
+                    Rule::LumpCaseMismatch,
+                    reference.path.clone(),
+                    format!("'{name}' only works because PK3 lookups ignore case, use '{found}'")
+                )),
+                Lookup::Missing => diagnostics.push(Diagnostic::new(
+                    Rule::MissingLump,
+                    reference.path.clone(),
+                    format!("Lump '{name}' doesn't exist in any loaded file")
+                ))
+            }
+        }
+    }
+    diagnostics
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+    use super::super::parse::ParseMode;
+    const SBARDEF: &str = r#"{
+        "type": "statusbar",
+        "version": "1.0.0",
+        "metadata": { },
+        "data": {
+            "numberfonts": [ { "name": "BigRed", "type": 0, "stem": "STT" } ],
+            "statusbars": [
+                {
+                    "height": 32, "fullscreenrender": false, "fillflat": "GRNROCK",
+                    "children": [
+                        {
+                            "canvas": {
+                                "x": 0, "y": 0, "alignment": 0, "conditions": null,
+                                "children": [
+                                    {
+                                        "number": {
+                                            "x": 0, "y": 0, "alignment": 0, "font": "BigRed", "type": 0, "param": 0,
+                                            "maxlength": 3, "tranmap": null, "translation": "CRGOLD",
+                                            "conditions": null, "children": null
+                                        }
+                                    }
+                                ]
+                            }
+                        },
+                        {
+                            "graphic": {
+                                "x": 0, "y": 0, "alignment": 0, "patch": "STBAR",
+                                "tranmap": null, "translation": "crred",
+                                "conditions": null, "children": null
+                            }
+                        }
+                    ]
+                }
+            ]
+        }
+    }"#;
+    #[test]
+    fn sbardef_references() {
+        let data = ID24Json::parse(SBARDEF, ParseMode::Strict).unwrap();
+        assert_eq!(data.references(), vec![
+            Reference::new(ReferenceKind::NumberFont, "STT", "/data/numberfonts/0/stem"),
+            Reference::new(ReferenceKind::Flat, "GRNROCK", "/data/statusbars/0/fillflat"),
+            Reference::new(ReferenceKind::Translation, "CRGOLD", "/data/statusbars/0/children/0/canvas/children/0/number/translation"),
+            Reference::new(ReferenceKind::Graphic, "STBAR", "/data/statusbars/0/children/1/graphic/patch"),
+            Reference::new(ReferenceKind::Translation, "crred", "/data/statusbars/0/children/1/graphic/translation"),
+        ]);
+        let mut known = KnownLumps::default();
+        known.insert(ReferenceKind::Translation, "CRRED");
+        let diagnostics = data.validate_references(&known);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::UnknownTranslation);
+        assert_eq!(diagnostics[0].path, "/data/statusbars/0/children/0/canvas/children/0/number/translation");
+    }
+    #[test]
+    fn gameconf_references() {
+        let mut data = ID24Json::new(ID24JsonData::gameconf());
+        if let ID24JsonData::GAMECONF { playertranslations, wadtranslation, .. } = &mut data.data {
+            *playertranslations = Some(vec!["T_GREEN".to_owned(), "T_INDIGO".to_owned()]);
+            *wadtranslation = Some("T_WAD".to_owned());
+        }
+        let mut known = KnownLumps::default();
+        known.insert(ReferenceKind::Translation, "t_green");
+        known.insert(ReferenceKind::Translation, "T_WAD");
+        let diagnostics = data.validate_references(&known);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].path, "/data/playertranslations/1");
+        assert_eq!(diagnostics[0].severity, diagnostics::Severity::Warning);
+    }
+    #[test]
+    fn wad_lump_names() {
+        let mut data = ID24Json::new(ID24JsonData::skydefs());
+        if let ID24JsonData::SKYDEFS { skies, flatmapping, .. } = &mut data.data {
+            let mut sky = Sky::default();
+            sky.backgroundtex.name = "textures/sky/longsky".to_owned();
+            *skies = Some(vec![sky]);
+            *flatmapping = Some(vec![skydefs::FlatMapping {
+                flat: "f_sky2".to_owned(),
+                sky: "SKY2".to_owned(),
+                extra: Extra::new()
+            }]);
+        }
+        let diagnostics = data.validate_wad_lump_names();
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, Rule::WadLumpNameLength);
+        assert_eq!(diagnostics[0].path, "/data/skies/0/name");
+        assert_eq!(diagnostics[1].rule, Rule::WadLumpNameCase);
+        assert_eq!(diagnostics[1].path, "/data/flatmapping/0/flat");
+        let mut data = ID24Json::new(ID24JsonData::demoloop());
+        if let ID24JsonData::DEMOLOOP { entries, .. } = &mut data.data {
+            entries.push(demoloop::Entry {
+                secondarylump: "d_dm2ttl".to_owned(),
+                ..Default::default()
+            });
+        }
+        let diagnostics = data.validate_wad_lump_names();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::WadLumpNameCase);
+        assert_eq!(diagnostics[0].path, "/data/entries/0/secondarylump");
+        assert_eq!(filter_wad_lump_name("textures/sky"), "TEXTURES");
+        assert_eq!(filter_wad_lump_name("d_runnin"), "D_RUNNIN");
+    }
+    #[test]
+    fn demoloop_references() {
+        let mut data = ID24Json::new(ID24JsonData::demoloop());
+        if let ID24JsonData::DEMOLOOP { entries, .. } = &mut data.data {
+            entries.push(demoloop::Entry::default());
+            entries.push(demoloop::Entry {
+                primarylump: "TITLEPIC".to_owned(),
+                secondarylump: "D_DM2TTL".to_owned(),
+                demo_type: demoloop::DemoType::ArtScreen,
+                ..Default::default()
+            });
+        }
+        assert_eq!(data.references(), vec![
+            Reference::new(ReferenceKind::Demo, "DEMO1", "/data/entries/0/primarylump"),
+            Reference::new(ReferenceKind::Graphic, "TITLEPIC", "/data/entries/1/primarylump"),
+            Reference::new(ReferenceKind::Music, "D_DM2TTL", "/data/entries/1/secondarylump"),
+        ]);
+    }
+    #[test]
+    fn check_missing_lumps() {
+        let data = ID24Json::parse(SBARDEF, ParseMode::Strict).unwrap();
+        let lumps = ["GRNROCK", "STTNUM0", "STTNUM1", "STTNUM2", "STTNUM3", "STTNUM4", "STTNUM5", "STTNUM6", "STTNUM7", "STTNUM8"];
+        let diagnostics = data.check_lump_names(|kind, name| match name {
+            _ if kind == ReferenceKind::Texture => Lookup::Unknown,
+            "STBAR" => Lookup::CaseMismatch("stbar".to_owned()),
+            _ if lumps.contains(&name) => Lookup::Found,
+            _ => Lookup::Missing
+        });
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, Rule::MissingLump);
+        assert_eq!(diagnostics[0].path, "/data/numberfonts/0/stem");
+        assert!(diagnostics[0].message.contains("STTNUM9"));
+        assert_eq!(diagnostics[1].rule, Rule::LumpCaseMismatch);
+        assert_eq!(diagnostics[1].path, "/data/statusbars/0/children/1/graphic/patch");
+    }
+    #[test]
+    fn check_referenced_lumps() {
+        let references = [
+            Reference::new(ReferenceKind::Interlevel, "INTRMAP1", "/MAP01/enteranim"),
+            Reference::new(ReferenceKind::Interlevel, "NOTHERE", "/MAP01/exitanim"),
+            Reference::new(ReferenceKind::Finale, "intrmap1", "/MAP30/endfinale"),
+            Reference::new(ReferenceKind::Finale, "BROKEN", "/MAP31/endfinale"),
+        ];
+        let interlevel = serde_json::to_string(&ID24Json::new(ID24JsonData::Interlevel {
+            backgroundimage: "INTERPIC".to_owned(),
+            music: "D_INTER".to_owned(),
+            layers: None,
+            extra: Extra::new()
+        })).unwrap();
+        let diagnostics = check_lumps(&references, |name| match name.to_uppercase().as_str() {
+            "INTRMAP1" => Some(interlevel.clone()),
+            "BROKEN" => Some("{ \"type\": \"finale\" }".to_owned()),
+            _ => None
+        });
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0].rule, Rule::MissingLump);
+        assert_eq!(diagnostics[0].path, "/MAP01/exitanim");
+        assert_eq!(diagnostics[1].rule, Rule::WrongLumpType);
+        assert_eq!(diagnostics[1].path, "/MAP30/endfinale");
+        assert_eq!(diagnostics[2].rule, Rule::InvalidLump);
+    }
+}
@@ -0,0 +1,685 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ * 
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ * 
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use super::{serialize_vec_non_empty, Extra};
+use super::diagnostics::{check_conditions, check_frames, Diagnostic, Rule};
+use super::references::{push_reference, Reference, ReferenceKind};
+
+// drawing status bars needs doomstruct's images, so it's only built with the render feature
+#[cfg(feature = "render")]
+mod preview;
+#[cfg(feature = "render")]
+pub use preview::{check_number_fonts, render, NumberFontGlyphs, PlayerState, SCREEN_HEIGHT, SCREEN_WIDTH};
+
+// number font glyphs are looked up as <stem>NUM0-9, <stem>MINUS and <stem>PRCNT
+// so the stem has to leave room for the suffix within the 8 character lump name limit
+const MAX_STEM_LENGTH: usize = 4;
+const MAX_STEM_LENGTH_WITH_SIGNS: usize = 3;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct NumberFont {
+    name: String,
+    #[serde(rename = "type")]
+    numberfont_type: NumberFontType,
+    #[cfg_attr(feature = "schema", schemars(length(min = 1, max = MAX_STEM_LENGTH)))]
+    stem: String,
+    #[serde(flatten)]
+    extra: Extra
+}
+
+#[derive(serde_repr::Serialize_repr, serde_repr::Deserialize_repr, Clone, Copy, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema_repr))]
+#[repr(u8)]
+enum NumberFontType {
+    MonoSpacedZero = 0,
+    MonoSpaceWidest = 1,
+    Proportional = 2
+}
+
+// the glyph lump suffixes, in the order NumberFontGlyphs keeps them
+const GLYPH_SUFFIXES: [&str; 12] = [
+    "NUM0", "NUM1", "NUM2", "NUM3", "NUM4", "NUM5", "NUM6", "NUM7", "NUM8", "NUM9", "MINUS", "PRCNT"
+];
+
+impl NumberFont {
+    pub fn glyph_lumps(&self) -> [String; 12] {
+        GLYPH_SUFFIXES.map(|suffix| format!("{}{suffix}", self.stem))
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct StatusBar {
+    height: u16,
+    fullscreenrender: bool,
+    fillflat: Option<String>, // spec says that this can't be null, but it is in LoR :/
+    children: Option<Vec<SBarElem>>, // other children arrays can be null according to spec, but not this one...of course it is in LoR
+    #[serde(flatten)]
+    extra: Extra
+}
+
+impl StatusBar {
+    pub fn fillflat(&self) -> Option<&str> {
+        self.fillflat.as_deref()
+    }
+
+    pub(super) fn normalise(&mut self) {
+        for child in self.children.get_or_insert_with(Vec::new) {
+            child.normalise();
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct SBarElem {
+    // TODO: spec says all these can be undefined but does *not* say they can be null, currently this works for writing but when reading this will allow null to be accepted
+    // I suppose it's maybe okay if we let bad json be fixed up a bit
+    // fields that aren't part of the spec are kept in extra and written back out
+    #[serde(skip_serializing_if = "Option::is_none")]
+    canvas: Option<Canvas>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    graphic: Option<Graphic>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    animation: Option<Animation>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    face: Option<Face>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    facebackground: Option<FaceBG>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    number: Option<Number>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    percent: Option<Percent>,
+    #[serde(flatten)]
+    extra: Extra
+}
+
+impl SBarElem {
+    // empty conditions and children arrays mean the same as null, so rewrite them into the spec conformant form
+    fn normalise(&mut self) {
+        let elems = [
+            self.canvas.as_mut().map(|elem| (&mut elem.conditions, &mut elem.children)),
+            self.graphic.as_mut().map(|elem| (&mut elem.conditions, &mut elem.children)),
+            self.animation.as_mut().map(|elem| (&mut elem.conditions, &mut elem.children)),
+            self.face.as_mut().map(|elem| (&mut elem.conditions, &mut elem.children)),
+            self.facebackground.as_mut().map(|elem| (&mut elem.conditions, &mut elem.children)),
+            self.number.as_mut().map(|elem| (&mut elem.conditions, &mut elem.children)),
+            self.percent.as_mut().map(|elem| (&mut elem.conditions, &mut elem.children)),
+        ];
+        for (conditions, children) in elems.into_iter().flatten() {
+            if conditions.as_ref().is_some_and(Vec::is_empty) {
+                *conditions = None;
+            }
+            if children.as_ref().is_some_and(Vec::is_empty) {
+                *children = None;
+            }
+            for child in children.iter_mut().flatten() {
+                child.normalise();
+            }
+        }
+    }
+
+    fn validate(&self, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+        if let Some(canvas) = &self.canvas {
+            validate_children(&canvas.conditions, canvas.children.as_ref(), &format!("{path}/canvas"), diagnostics);
+        }
+        if let Some(graphic) = &self.graphic {
+            validate_children(&graphic.conditions, graphic.children.as_ref(), &format!("{path}/graphic"), diagnostics);
+        }
+        if let Some(animation) = &self.animation {
+            let path = format!("{path}/animation");
+            check_frames(&animation.frames, &format!("{path}/frames"), diagnostics);
+            validate_children(&animation.conditions, animation.children.as_ref(), &path, diagnostics);
+        }
+        if let Some(face) = &self.face {
+            validate_children(&face.conditions, face.children.as_ref(), &format!("{path}/face"), diagnostics);
+        }
+        if let Some(facebackground) = &self.facebackground {
+            validate_children(&facebackground.conditions, facebackground.children.as_ref(), &format!("{path}/facebackground"), diagnostics);
+        }
+        if let Some(number) = &self.number {
+            validate_children(&number.conditions, number.children.as_ref(), &format!("{path}/number"), diagnostics);
+        }
+        if let Some(percent) = &self.percent {
+            validate_children(&percent.conditions, percent.children.as_ref(), &format!("{path}/percent"), diagnostics);
+        }
+    }
+
+    fn references(&self, path: &str, references: &mut Vec<Reference>) {
+        if let Some(canvas) = &self.canvas {
+            children_references(canvas.children.as_ref(), &format!("{path}/canvas"), references);
+        }
+        if let Some(graphic) = &self.graphic {
+            let path = format!("{path}/graphic");
+            push_reference(references, ReferenceKind::Graphic, &graphic.patch, &path, "patch");
+            lump_references(graphic.tranmap.as_deref(), graphic.translation.as_deref(), &path, references);
+            children_references(graphic.children.as_ref(), &path, references);
+        }
+        if let Some(animation) = &self.animation {
+            let path = format!("{path}/animation");
+            for (idx, frame) in animation.frames.iter().enumerate() {
+                push_reference(references, ReferenceKind::Graphic, &frame.lump, &format!("{path}/frames/{idx}"), "lump");
+            }
+            lump_references(animation.tranmap.as_deref(), animation.translation.as_deref(), &path, references);
+            children_references(animation.children.as_ref(), &path, references);
+        }
+        if let Some(face) = &self.face {
+            children_references(face.children.as_ref(), &format!("{path}/face"), references);
+        }
+        if let Some(facebackground) = &self.facebackground {
+            children_references(facebackground.children.as_ref(), &format!("{path}/facebackground"), references);
+        }
+        if let Some(number) = &self.number {
+            let path = format!("{path}/number");
+            lump_references(number.tranmap.as_deref(), number.translation.as_deref(), &path, references);
+            children_references(number.children.as_ref(), &path, references);
+        }
+        if let Some(percent) = &self.percent {
+            let path = format!("{path}/percent");
+            lump_references(percent.tranmap.as_deref(), percent.translation.as_deref(), &path, references);
+            children_references(percent.children.as_ref(), &path, references);
+        }
+    }
+}
+
+fn lump_references(tranmap: Option<&str>, translation: Option<&str>, path: &str, references: &mut Vec<Reference>) {
+    if let Some(name) = tranmap {
+        references.push(Reference::new(ReferenceKind::Tranmap, name, format!("{path}/tranmap")));
+    }
+    if let Some(name) = translation {
+        references.push(Reference::new(ReferenceKind::Translation, name, format!("{path}/translation")));
+    }
+}
+
+fn children_references(children: Option<&Vec<SBarElem>>, path: &str, references: &mut Vec<Reference>) {
+    for (idx, child) in children.into_iter().flatten().enumerate() {
+        child.references(&format!("{path}/children/{idx}"), references);
+    }
+}
+
+#[allow(clippy::ref_option)]
+fn validate_children(
+    conditions: &Option<Vec<Condition>>, children: Option<&Vec<SBarElem>>,
+    path: &str, diagnostics: &mut Vec<Diagnostic>
+) {
+    check_conditions(conditions, path, diagnostics);
+    for (idx, child) in children.into_iter().flatten().enumerate() {
+        child.validate(&format!("{path}/children/{idx}"), diagnostics);
+    }
+}
+
+pub(super) fn validate(numberfonts: &[NumberFont], statusbars: &[StatusBar], path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if numberfonts.is_empty() {
+        diagnostics.push(Diagnostic::new(
+            Rule::EmptyNumberFonts,
+            format!("{path}/numberfonts"),
+            "There must be at least one number font"
+        ));
+    }
+    for (idx, font) in numberfonts.iter().enumerate() {
+        let stem_length = font.stem.chars().count();
+        if stem_length == 0 || stem_length > MAX_STEM_LENGTH {
+            diagnostics.push(Diagnostic::new(
+                Rule::NumberFontStemLength,
+                format!("{path}/numberfonts/{idx}/stem"),
+                format!("Number font stem '{}' must be between 1 and {MAX_STEM_LENGTH} characters long", font.stem)
+            ));
+        } else if stem_length > MAX_STEM_LENGTH_WITH_SIGNS {
+            diagnostics.push(Diagnostic::new(
+                Rule::NumberFontStemNoSigns,
+                format!("{path}/numberfonts/{idx}/stem"),
+                format!(
+                    "Number font stem '{}' is longer than {MAX_STEM_LENGTH_WITH_SIGNS} characters, \
+                     so its MINUS and PRCNT glyphs won't fit in a lump name", font.stem
+                )
+            ));
+        }
+    }
+    if statusbars.is_empty() {
+        diagnostics.push(Diagnostic::new(
+            Rule::EmptyStatusBars,
+            format!("{path}/statusbars"),
+            "There must be at least one status bar"
+        ));
+    }
+    for (idx, statusbar) in statusbars.iter().enumerate() {
+        if statusbar.fillflat.is_none() {
+            diagnostics.push(Diagnostic::new(
+                Rule::NullFillFlat,
+                format!("{path}/statusbars/{idx}/fillflat"),
+                "Status bar fill flat must be a flat name"
+            ));
+        }
+        if statusbar.children.is_none() {
+            diagnostics.push(Diagnostic::new(
+                Rule::NullStatusBarChildren,
+                format!("{path}/statusbars/{idx}/children"),
+                "Status bar children must be an array"
+            ));
+        }
+        for (child_idx, child) in statusbar.children.iter().flatten().enumerate() {
+            child.validate(&format!("{path}/statusbars/{idx}/children/{child_idx}"), diagnostics);
+        }
+    }
+}
+
+pub(super) fn references(numberfonts: &[NumberFont], statusbars: &[StatusBar], path: &str, references: &mut Vec<Reference>) {
+    for (idx, font) in numberfonts.iter().enumerate() {
+        push_reference(references, ReferenceKind::NumberFont, &font.stem, &format!("{path}/numberfonts/{idx}"), "stem");
+    }
+    for (idx, statusbar) in statusbars.iter().enumerate() {
+        let path = format!("{path}/statusbars/{idx}");
+        if let Some(fillflat) = &statusbar.fillflat {
+            push_reference(references, ReferenceKind::Flat, fillflat, &path, "fillflat");
+        }
+        children_references(statusbar.children.as_ref(), &path, references);
+    }
+}
+
+type Face = Canvas;
+type FaceBG = Canvas;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct Canvas {
+    x: i16,
+    y: i16,
+    alignment: Alignment,
+    #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+    conditions: Option<Vec<Condition>>,
+    children: Option<Vec<SBarElem>>,
+    #[serde(flatten)]
+    extra: Extra
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct Graphic {
+    x: i16,
+    y: i16,
+    alignment: Alignment,
+    tranmap: Option<String>,
+    translation: Option<String>,
+    #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+    conditions: Option<Vec<Condition>>,
+    children: Option<Vec<SBarElem>>,
+    patch: String,
+    #[serde(flatten)]
+    extra: Extra
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct Animation {
+    x: i16,
+    y: i16,
+    alignment: Alignment,
+    tranmap: Option<String>,
+    translation: Option<String>,
+    #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+    conditions: Option<Vec<Condition>>,
+    children: Option<Vec<SBarElem>>,
+    #[serde(serialize_with = "serialize_vec_non_empty")]
+    #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+    frames: Vec<Frame>,
+    #[serde(flatten)]
+    extra: Extra
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct Frame {
+    lump: String,
+    duration: f32,
+    #[serde(flatten)]
+    extra: Extra
+}
+
+type Percent = Number;
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct Number {
+    x: i16,
+    y: i16,
+    alignment: Alignment,
+    tranmap: Option<String>,
+    translation: Option<String>,
+    #[cfg_attr(feature = "schema", schemars(length(min = 1)))]
+    conditions: Option<Vec<Condition>>,
+    children: Option<Vec<SBarElem>>,
+    font: String,
+    #[serde(rename = "type")]
+    num_type: NumberType,
+    param: u8,
+    maxlength: u8,
+    #[serde(flatten)]
+    extra: Extra
+}
+
+#[derive(serde_repr::Serialize_repr, serde_repr::Deserialize_repr, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema_repr))]
+#[repr(u8)]
+enum NumberType {
+    // TODO: maybe make the ammo names clearer
+    Health = 0,
+    Armor = 1,
+    Frags = 2,
+    AmmoParam = 3,
+    AmmoCurrWeapon = 4,
+    MaxAmmoParam = 5,
+    AmmoParamWeapon = 6,
+    MaxAmmoParamWeapon = 7
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct Condition {
+    condition: ConditionType,
+    param: u8,
+    #[serde(flatten)]
+    extra: Extra
+}
+
+#[derive(serde_repr::Serialize_repr, serde_repr::Deserialize_repr, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema_repr))]
+#[repr(u8)]
+enum ConditionType {
+    WeaponOwned           = 0, // Whether the weapon defined by param is owned
+    WeaponSelected        = 1, // Whether the weapon defined by param is selected
+    WeaponNotSelected     = 2, // Whether the weapon defined by param is not selected
+    WeaponValidAmmo       = 3, // Whether the weapon defined by param has a valid ammo type
+    CurrWeaponValidAmmo   = 4, // Whether the selected weapon has a valid ammo type
+    MatchesCurrWeaponAmmo = 5, // Whether the ammo type defined by param matches the selected weapon’s ammo type
+    AnyWeaponOwned        = 6, // Whether any weapon in a slot defined by param is owned
+    AnyWeaponNotOwned     = 7, // Whether any weapon in a slot defined by param not owned
+    AnyWeaponSelected     = 8, // Whether any weapon in a slot defined by param is selected
+    AnyWeaponNotSelected  = 9, // Whether any weapon in a slot defined by param is not selected
+    ItemOwned             = 10, // Whether the item defined by param is owned
+    ItemNotOwned          = 11, // Whether the item defined by param is not owned
+    GameVersionGreaterEq  = 12, // Whether the current game version is greater than or equal to the feature level defined by param
+    GameVersionLess       = 13, // Whether the current game version is less than the feature level defined by param
+    SessionTypeEqual      = 14, // Whether the session type is equal to the type defined by param
+    SessionTypeNotEqual   = 15, // Whether the session type is not equal to the type defined by param
+    GameModeEqual         = 16, // Whether the game mode is equal to the mode defined by param
+    GameModeNotEqual      = 17, // Whether the game mode is not equal to the mode defined by param
+    HudModeEqual          = 18 // Whether the hud mode is equal to the mode defined by param
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+struct Alignment {
+    horizontal: HoriAlign,
+    vertical: VertAlign
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum VertAlign {
+    Top,
+    Center,
+    Bottom,
+}
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+enum HoriAlign {
+    Left,
+    Center,
+    Right,
+}
+
+impl serde::Serialize for Alignment {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        serializer.serialize_u8(self.to_u8())
+    }
+}
+
+// only the combinations with a single horizontal and a single vertical alignment are valid
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for Alignment {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "Alignment".into()
+    }
+
+    fn json_schema(_: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let values: Vec<u8> = (0..=0b1111).filter(|value| Alignment::from_u8(*value).is_ok()).collect();
+        schemars::json_schema!({
+            "description": "Bitfield: 0/1/2 for left/center/right, plus 0/4/8 for top/center/bottom",
+            "type": "integer",
+            "enum": values
+        })
+    }
+}
+
+impl<'a> serde::Deserialize<'a> for Alignment {
+    fn deserialize<D>(deserializer: D) -> Result<Alignment, D::Error> where D: serde::Deserializer<'a> {
+        let value = u8::deserialize(deserializer)?;
+        Alignment::from_u8(value).map_err(serde::de::Error::custom)
+    }
+}
+
+impl Alignment {
+    fn from_u8(value: u8) -> Result<Self, &'static str> {
+        let horizontal = match value & 0b11 {
+            0b00 => HoriAlign::Left,
+            0b01 => HoriAlign::Center,
+            0b10 => HoriAlign::Right,
+            _ => return Err("Multiple horizontal alignments specified"),
+        };
+        let vertical = match value & 0b1100 {
+            0b0000 => VertAlign::Top,
+            0b0100 => VertAlign::Center,
+            0b1000 => VertAlign::Bottom,
+            _ => return Err("Multiple horizontal alignments specified"),
+        };
+        Ok(Self {
+            horizontal,
+            vertical
+        })
+    }
+
+    fn to_u8(self) -> u8 {
+        let horizontal_bits = match self.horizontal {
+            HoriAlign::Left   => 0b00,
+            HoriAlign::Center => 0b01,
+            HoriAlign::Right  => 0b10
+        };
+        let vertical_bits = match self.vertical {
+            VertAlign::Top    => 0b0000,
+            VertAlign::Center => 0b0100,
+            VertAlign::Bottom => 0b1000
+        };
+
+        horizontal_bits | vertical_bits
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+    #[test]
+    fn read_sbardef() {
+        let json = r#"{
+            "type": "statusbar",
+            "version": "1.0.0",
+            "metadata": null,
+            "data":
+            {
+                "numberfonts":
+                [
+                    {
+                        "name": "BigRed",
+                        "type": 0,
+                        "stem": "STT"
+                    },
+                    {
+                        "name": "SmallGrey",
+                        "type": 1,
+                        "stem": "STG"
+                    },
+                    {
+                        "name": "SmallYellow",
+                        "type": 2,
+                        "stem": "STYS"
+                    }
+                ],
+                "statusbars":
+                [
+                    {
+                        "height": 32,
+                        "fullscreenrender": false,
+                        "fillflat": null,
+                        "children": null
+                    }
+                ]
+            }
+        }"#;
+        let data: ID24Json = serde_json::from_str(json).unwrap();
+        assert_eq!(data.version, ID24JsonVersion { major: 1, minor: 0, revision: 0 });
+        assert_eq!(data.data, ID24JsonData::SBARDEF {
+            numberfonts: vec![
+                NumberFont {
+                    name: "BigRed".to_owned(),
+                    numberfont_type: NumberFontType::MonoSpacedZero,
+                    stem: "STT".to_owned(),
+                    extra: Extra::new()
+                },
+                NumberFont {
+                    name: "SmallGrey".to_owned(),
+                    numberfont_type: NumberFontType::MonoSpaceWidest,
+                    stem: "STG".to_owned(),
+                    extra: Extra::new()
+                },
+                NumberFont {
+                    name: "SmallYellow".to_owned(),
+                    numberfont_type: NumberFontType::Proportional,
+                    stem: "STYS".to_owned(),
+                    extra: Extra::new()
+                },
+            ],
+            statusbars: vec![
+                StatusBar {
+                    height: 32,
+                    fullscreenrender: false,
+                    fillflat: None,
+                    children: None,
+                    extra: Extra::new()
+                }
+            ],
+            extra: Extra::new()
+        });
+        // TODO: add tests for all the children element types, putting them all into this one would be huge
+    }
+    fn elem_with_conditions(conditions: Option<Vec<Condition>>, children: Option<Vec<SBarElem>>) -> SBarElem {
+        SBarElem {
+            canvas: Some(Canvas {
+                x: 0,
+                y: 0,
+                alignment: Alignment { horizontal: HoriAlign::Left, vertical: VertAlign::Top },
+                conditions,
+                children,
+                extra: Extra::new()
+            }),
+            graphic: None,
+            animation: None,
+            face: None,
+            facebackground: None,
+            number: None,
+            percent: None,
+            extra: Extra::new()
+        }
+    }
+    fn statusbar(children: Option<Vec<SBarElem>>) -> StatusBar {
+        StatusBar {
+            height: 32,
+            fullscreenrender: false,
+            fillflat: Some("GRNROCK".to_owned()),
+            children,
+            extra: Extra::new()
+        }
+    }
+    fn font(stem: &str) -> NumberFont {
+        NumberFont {
+            name: "Font".to_owned(),
+            numberfont_type: NumberFontType::Proportional,
+            stem: stem.to_owned(),
+            extra: Extra::new()
+        }
+    }
+    #[test]
+    fn validate_empty_sbardef() {
+        let mut diagnostics = Vec::new();
+        validate(&[], &[], "/data", &mut diagnostics);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, Rule::EmptyNumberFonts);
+        assert_eq!(diagnostics[0].path, "/data/numberfonts");
+        assert_eq!(diagnostics[1].rule, Rule::EmptyStatusBars);
+        assert_eq!(diagnostics[1].path, "/data/statusbars");
+    }
+    #[test]
+    fn validate_stem_length() {
+        let mut diagnostics = Vec::new();
+        validate(&[font("STT"), font("STYS"), font("TOOLONG"), font("")], &[statusbar(Some(Vec::new()))], "/data", &mut diagnostics);
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(diagnostics[0].rule, Rule::NumberFontStemNoSigns);
+        assert_eq!(diagnostics[0].path, "/data/numberfonts/1/stem");
+        assert_eq!(diagnostics[0].severity, diagnostics::Severity::Warning);
+        assert_eq!(diagnostics[1].rule, Rule::NumberFontStemLength);
+        assert_eq!(diagnostics[1].path, "/data/numberfonts/2/stem");
+        assert_eq!(diagnostics[2].rule, Rule::NumberFontStemLength);
+        assert_eq!(diagnostics[2].path, "/data/numberfonts/3/stem");
+    }
+    #[test]
+    fn validate_nested_conditions() {
+        let condition = Condition { condition: ConditionType::WeaponOwned, param: 1, extra: Extra::new() };
+        let inner = elem_with_conditions(Some(Vec::new()), None);
+        let outer = elem_with_conditions(Some(vec![condition]), Some(vec![inner]));
+        let mut diagnostics = Vec::new();
+        validate(&[font("STT")], &[statusbar(Some(vec![outer]))], "/data", &mut diagnostics);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::EmptyConditions);
+        assert_eq!(diagnostics[0].path, "/data/statusbars/0/children/0/canvas/children/0/canvas/conditions");
+    }
+    #[test]
+    fn normalise_empty_conditions() {
+        let inner = elem_with_conditions(Some(Vec::new()), Some(Vec::new()));
+        let mut statusbar = statusbar(Some(vec![elem_with_conditions(Some(Vec::new()), Some(vec![inner]))]));
+        // written as is until normalised
+        let value = serde_json::to_value(&statusbar).unwrap();
+        assert_eq!(value["children"][0]["canvas"]["conditions"], serde_json::json!([]));
+        assert_eq!(value["children"][0]["canvas"]["children"][0]["canvas"]["children"], serde_json::json!([]));
+        statusbar.normalise();
+        let value = serde_json::to_value(&statusbar).unwrap();
+        assert!(value["children"][0]["canvas"]["conditions"].is_null());
+        assert!(value["children"][0]["canvas"]["children"][0]["canvas"]["conditions"].is_null());
+        assert!(value["children"][0]["canvas"]["children"][0]["canvas"]["children"].is_null());
+    }
+    #[test]
+    fn validate_empty_animation_frames() {
+        let json = r#"{
+            "animation": {
+                "x": 0, "y": 0, "alignment": 0,
+                "tranmap": null, "translation": null,
+                "conditions": null, "children": null,
+                "frames": []
+            }
+        }"#;
+        let elem: SBarElem = serde_json::from_str(json).unwrap();
+        let mut diagnostics = Vec::new();
+        validate(&[font("STT")], &[statusbar(Some(vec![elem]))], "/data", &mut diagnostics);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::EmptyFrames);
+        assert_eq!(diagnostics[0].path, "/data/statusbars/0/children/0/animation/frames");
+    }
+}
@@ -0,0 +1,693 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use doomstruct::graphics::Image;
+use super::{Alignment, Condition, ConditionType, Frame, HoriAlign, Number, NumberFont, NumberFontType, NumberType, SBarElem, StatusBar, VertAlign};
+use super::super::diagnostics::{Diagnostic, Rule};
+
+const MINUS_GLYPH: usize = 10;
+const PERCENT_GLYPH: usize = 11;
+
+impl NumberFont {
+    // graphic loads a lump as an image, the editor looks them up in its resource stack
+    pub fn load(&self, mut graphic: impl FnMut(&str) -> Option<Image>) -> NumberFontGlyphs {
+        let lumps = self.glyph_lumps();
+        let glyphs = std::array::from_fn(|idx| graphic(&lumps[idx]));
+        let width = |glyph: &Option<Image>| glyph.as_ref().map(|glyph| image_size(glyph).0);
+        let monowidth = match self.numberfont_type {
+            NumberFontType::MonoSpacedZero  => width(&glyphs[0]),
+            NumberFontType::MonoSpaceWidest => glyphs.iter().filter_map(width).max(),
+            NumberFontType::Proportional    => None
+        };
+        NumberFontGlyphs { lumps, glyphs, monowidth }
+    }
+}
+
+// a number font's glyphs, and how far apart they're drawn
+pub struct NumberFontGlyphs {
+    lumps: [String; 12],
+    glyphs: [Option<Image>; 12],
+    // every glyph takes up this much room in monospaced fonts
+    monowidth: Option<i32>
+}
+
+impl NumberFontGlyphs {
+    // digits, '-' and '%'
+    pub fn glyph(&self, c: char) -> Option<&Image> {
+        let idx = match c {
+            '-' => MINUS_GLYPH,
+            '%' => PERCENT_GLYPH,
+            digit => usize::try_from(digit.to_digit(10)?).ok()?
+        };
+        self.glyphs[idx].as_ref()
+    }
+
+    // the glyph lumps that couldn't be loaded
+    pub fn missing(&self) -> Vec<&str> {
+        self.lumps.iter()
+            .zip(&self.glyphs)
+            .filter(|(_, glyph)| glyph.is_none())
+            .map(|(lump, _)| lump.as_str())
+            .collect()
+    }
+
+    // how far the next glyph is drawn after this one
+    pub fn advance(&self, glyph: &Image) -> i32 {
+        self.monowidth.unwrap_or_else(|| image_size(glyph).0)
+    }
+
+    // glyphs the font doesn't have take up no room
+    pub fn text_width(&self, text: &str) -> i32 {
+        text.chars().filter_map(|c| self.glyph(c)).map(|glyph| self.advance(glyph)).sum()
+    }
+}
+
+// calls used for every number and percent element, and whether it's a percent
+fn visit_numbers<'a>(elem: &'a SBarElem, used: &mut impl FnMut(&'a Number, bool)) {
+    let children = [
+        elem.canvas.as_ref().and_then(|canvas| canvas.children.as_ref()),
+        elem.graphic.as_ref().and_then(|graphic| graphic.children.as_ref()),
+        elem.animation.as_ref().and_then(|animation| animation.children.as_ref()),
+        elem.face.as_ref().and_then(|face| face.children.as_ref()),
+        elem.facebackground.as_ref().and_then(|facebackground| facebackground.children.as_ref()),
+        elem.number.as_ref().and_then(|number| number.children.as_ref()),
+        elem.percent.as_ref().and_then(|percent| percent.children.as_ref())
+    ];
+    if let Some(number) = &elem.number {
+        used(number, false);
+    }
+    if let Some(percent) = &elem.percent {
+        used(percent, true);
+    }
+    for child in children.into_iter().flatten().flatten() {
+        visit_numbers(child, used);
+    }
+}
+
+// the signs that number fonts are drawn with but don't have
+// digits aren't checked here, every font needs all ten so the lump checks already report them
+// glyphs gives each font's loaded glyphs, fonts it has none for yet aren't checked
+pub fn check_number_fonts<'a>(
+    numberfonts: &[NumberFont], statusbars: &[StatusBar], path: &str,
+    glyphs: impl Fn(&NumberFont) -> Option<&'a NumberFontGlyphs>
+) -> Vec<Diagnostic> {
+    let mut percents = std::collections::HashSet::new();
+    let mut minuses = std::collections::HashSet::new();
+    for child in statusbars.iter().flat_map(|statusbar| statusbar.children.iter().flatten()) {
+        visit_numbers(child, &mut |number, percent| {
+            if percent {
+                percents.insert(number.font.as_str());
+            }
+            // frags are the only number that goes below zero
+            if number.num_type == NumberType::Frags {
+                minuses.insert(number.font.as_str());
+            }
+        });
+    }
+    let mut diagnostics = Vec::new();
+    for (idx, font) in numberfonts.iter().enumerate() {
+        let (needs_percent, needs_minus) = (percents.contains(font.name.as_str()), minuses.contains(font.name.as_str()));
+        let Some(glyphs) = glyphs(font).filter(|_| needs_percent || needs_minus) else {
+            continue;
+        };
+        let missing = glyphs.missing();
+        let lumps = font.glyph_lumps();
+        for (needed, glyph, usage) in [
+            (needs_percent, &lumps[PERCENT_GLYPH], "percents"),
+            (needs_minus, &lumps[MINUS_GLYPH], "frag counts")
+        ] {
+            if needed && missing.contains(&glyph.as_str()) {
+                diagnostics.push(Diagnostic::new(
+                    Rule::NumberFontMissingGlyph,
+                    format!("{path}/numberfonts/{idx}/stem"),
+                    format!("Number font '{}' is used for {usage}, but there's no {glyph} glyph", font.name)
+                ));
+            }
+        }
+    }
+    diagnostics
+}
+
+// what the previewed player has, for deciding which elements are shown and what numbers say
+#[derive(Clone, PartialEq, Debug)]
+pub struct PlayerState {
+    pub health: i32,
+    pub armor: i32,
+    pub frags: i32,
+    // clip, shell, cell, rocket
+    pub ammo: [i32; 4],
+    pub max_ammo: [i32; 4],
+    // fist, pistol, shotgun, chaingun, rocket launcher, plasma rifle, BFG, chainsaw, super shotgun
+    pub weapons: [bool; 9],
+    pub selected_weapon: u8,
+    pub items: Vec<u8>,
+    // which of the 4 player colours the face background is
+    pub player: u8,
+    pub game_version: u8,
+    pub session_type: u8,
+    pub game_mode: u8,
+    pub hud_mode: u8
+}
+
+impl Default for PlayerState {
+    // a fresh single player start
+    fn default() -> Self {
+        Self {
+            health: 100,
+            armor: 0,
+            frags: 0,
+            ammo: [50, 0, 0, 0],
+            max_ammo: [200, 50, 300, 50],
+            weapons: [true, true, false, false, false, false, false, false, false],
+            selected_weapon: 1,
+            items: Vec::new(),
+            player: 0,
+            game_version: u8::MAX,
+            session_type: 0,
+            game_mode: 0,
+            hud_mode: 0
+        }
+    }
+}
+
+// the ammo type each weapon uses, the fist and chainsaw don't use any
+const WEAPON_AMMO: [Option<u8>; 9] = [None, Some(0), Some(1), Some(0), Some(3), Some(2), Some(2), None, Some(1)];
+// the number key each weapon is selected with
+const WEAPON_SLOTS: [u8; 9] = [1, 2, 3, 4, 5, 6, 7, 1, 3];
+
+impl PlayerState {
+    fn weapon_ammo(&self, weapon: u8) -> Option<u8> {
+        WEAPON_AMMO.get(usize::from(weapon)).copied().flatten()
+    }
+
+    fn ammo(&self, ammo: Option<u8>) -> Option<i32> {
+        self.ammo.get(usize::from(ammo?)).copied()
+    }
+
+    fn max_ammo(&self, ammo: Option<u8>) -> Option<i32> {
+        self.max_ammo.get(usize::from(ammo?)).copied()
+    }
+
+    fn slot_weapons(&self, slot: u8) -> impl Iterator<Item = u8> + '_ {
+        (0u8..).zip(WEAPON_SLOTS).filter(move |(_, weapon_slot)| *weapon_slot == slot).map(|(weapon, _)| weapon)
+    }
+
+    fn owns(&self, weapon: u8) -> bool {
+        self.weapons.get(usize::from(weapon)).copied().unwrap_or(false)
+    }
+
+    fn check(&self, condition: &Condition) -> bool {
+        let param = condition.param;
+        match condition.condition {
+            ConditionType::WeaponOwned           => self.owns(param),
+            ConditionType::WeaponSelected        => self.selected_weapon == param,
+            ConditionType::WeaponNotSelected     => self.selected_weapon != param,
+            ConditionType::WeaponValidAmmo       => self.weapon_ammo(param).is_some(),
+            ConditionType::CurrWeaponValidAmmo   => self.weapon_ammo(self.selected_weapon).is_some(),
+            ConditionType::MatchesCurrWeaponAmmo => self.weapon_ammo(self.selected_weapon) == Some(param),
+            ConditionType::AnyWeaponOwned        => self.slot_weapons(param).any(|weapon| self.owns(weapon)),
+            ConditionType::AnyWeaponNotOwned     => !self.slot_weapons(param).any(|weapon| self.owns(weapon)),
+            ConditionType::AnyWeaponSelected     => self.slot_weapons(param).any(|weapon| weapon == self.selected_weapon),
+            ConditionType::AnyWeaponNotSelected  => !self.slot_weapons(param).any(|weapon| weapon == self.selected_weapon),
+            ConditionType::ItemOwned             => self.items.contains(&param),
+            ConditionType::ItemNotOwned          => !self.items.contains(&param),
+            ConditionType::GameVersionGreaterEq  => self.game_version >= param,
+            ConditionType::GameVersionLess       => self.game_version < param,
+            ConditionType::SessionTypeEqual      => self.session_type == param,
+            ConditionType::SessionTypeNotEqual   => self.session_type != param,
+            ConditionType::GameModeEqual         => self.game_mode == param,
+            ConditionType::GameModeNotEqual      => self.game_mode != param,
+            ConditionType::HudModeEqual          => self.hud_mode == param
+        }
+    }
+
+    #[allow(clippy::ref_option)]
+    fn shows(&self, conditions: &Option<Vec<Condition>>) -> bool {
+        conditions.iter().flatten().all(|condition| self.check(condition))
+    }
+
+    // None for ammo counts of weapons that don't use ammo, those aren't drawn at all
+    fn number(&self, number: &Number) -> Option<i32> {
+        let param = number.param;
+        match number.num_type {
+            NumberType::Health             => Some(self.health),
+            NumberType::Armor              => Some(self.armor),
+            NumberType::Frags              => Some(self.frags),
+            NumberType::AmmoParam          => self.ammo(Some(param)),
+            NumberType::AmmoCurrWeapon     => self.ammo(self.weapon_ammo(self.selected_weapon)),
+            NumberType::MaxAmmoParam       => self.max_ammo(Some(param)),
+            NumberType::AmmoParamWeapon    => self.ammo(self.weapon_ammo(param)),
+            NumberType::MaxAmmoParamWeapon => self.max_ammo(self.weapon_ammo(param))
+        }
+    }
+
+    // the straight ahead face for how hurt the player is
+    fn face(&self) -> String {
+        if self.health <= 0 {
+            return "STFDEAD0".to_owned();
+        }
+        let pain = (100 - self.health.min(100)) * 5 / 101;
+        format!("STFST{pain}1")
+    }
+}
+
+pub const SCREEN_WIDTH: usize = 320;
+pub const SCREEN_HEIGHT: usize = 200;
+
+impl Alignment {
+    // where the top left of something this big goes for it to be aligned at x, y
+    fn top_left(self, x: i32, y: i32, width: i32, height: i32) -> (i32, i32) {
+        let x = match self.horizontal {
+            HoriAlign::Left   => x,
+            HoriAlign::Center => x - width / 2,
+            HoriAlign::Right  => x - width
+        };
+        let y = match self.vertical {
+            VertAlign::Top    => y,
+            VertAlign::Center => y - height / 2,
+            VertAlign::Bottom => y - height
+        };
+        (x, y)
+    }
+}
+
+fn image_size(image: &Image) -> (i32, i32) {
+    (i32::try_from(image.width).unwrap_or(i32::MAX), i32::try_from(image.height).unwrap_or(i32::MAX))
+}
+
+// draws a status bar the way it'd look on a 320x200 screen
+// graphic and flat load lumps as images, anything they can't find is left out
+// translations and translucency aren't applied
+// time is in seconds, for picking animation frames
+pub fn render(
+    statusbar: &StatusBar, numberfonts: &[NumberFont], player: &PlayerState, time: f32,
+    mut graphic: impl FnMut(&str) -> Option<Image>, flat: impl FnOnce(&str) -> Option<Image>
+) -> Image {
+    let fonts = numberfonts.iter().map(|font| (font.name.as_str(), font.load(&mut graphic))).collect();
+    let mut renderer = Renderer { image: Image::new(SCREEN_WIDTH, SCREEN_HEIGHT), fonts, player, time, graphic };
+    let top = SCREEN_HEIGHT.saturating_sub(usize::from(statusbar.height));
+    // the fill flat is what's seen around the bar on wider screens, so it goes behind everything
+    if !statusbar.fullscreenrender
+        && let Some(fill) = statusbar.fillflat.as_deref().and_then(flat)
+        && fill.width > 0 && fill.height > 0 {
+        for y in top..SCREEN_HEIGHT {
+            for x in 0..SCREEN_WIDTH {
+                if let Some(pixel) = fill.get(x % fill.width, y % fill.height) {
+                    renderer.image.set(x, y, pixel);
+                }
+            }
+        }
+    }
+    for child in statusbar.children.iter().flatten() {
+        renderer.draw_elem(child, 0, 0);
+    }
+    renderer.image
+}
+
+impl StatusBar {
+    // whether the bar changes over time for this player, only animations with more than one frame that are shown do
+    pub fn animates(&self, player: &PlayerState) -> bool {
+        shows_animation(self.children.as_ref(), player)
+    }
+}
+
+fn shows_animation(children: Option<&Vec<SBarElem>>, player: &PlayerState) -> bool {
+    children.into_iter().flatten().any(|elem| {
+        // children are only drawn when their parent is
+        let children = [
+            elem.canvas.as_ref().filter(|canvas| player.shows(&canvas.conditions)).map(|canvas| &canvas.children),
+            elem.graphic.as_ref().filter(|graphic| player.shows(&graphic.conditions)).map(|graphic| &graphic.children),
+            elem.animation.as_ref().filter(|animation| player.shows(&animation.conditions)).map(|animation| &animation.children),
+            elem.face.as_ref().filter(|face| player.shows(&face.conditions)).map(|face| &face.children),
+            elem.facebackground.as_ref().filter(|facebackground| player.shows(&facebackground.conditions)).map(|facebackground| &facebackground.children),
+            elem.number.as_ref().filter(|number| player.shows(&number.conditions)).map(|number| &number.children),
+            elem.percent.as_ref().filter(|percent| player.shows(&percent.conditions)).map(|percent| &percent.children)
+        ];
+        elem.animation.as_ref().is_some_and(|animation| player.shows(&animation.conditions) && animation.frames.len() > 1)
+            || children.into_iter().flatten().any(|children| shows_animation(children.as_ref(), player))
+    })
+}
+
+struct Renderer<'a, G: FnMut(&str) -> Option<Image>> {
+    image: Image,
+    fonts: Vec<(&'a str, NumberFontGlyphs)>,
+    player: &'a PlayerState,
+    time: f32,
+    graphic: G
+}
+
+impl<G: FnMut(&str) -> Option<Image>> Renderer<'_, G> {
+    // children are positioned from their parent's x and y, whatever the parent's alignment
+    fn draw_children(&mut self, children: Option<&Vec<SBarElem>>, x: i32, y: i32) {
+        for child in children.into_iter().flatten() {
+            self.draw_elem(child, x, y);
+        }
+    }
+
+    fn draw_elem(&mut self, elem: &SBarElem, parent_x: i32, parent_y: i32) {
+        if let Some(canvas) = &elem.canvas
+            && self.player.shows(&canvas.conditions) {
+            let (x, y) = (parent_x + i32::from(canvas.x), parent_y + i32::from(canvas.y));
+            self.draw_children(canvas.children.as_ref(), x, y);
+        }
+        if let Some(graphic) = &elem.graphic
+            && self.player.shows(&graphic.conditions) {
+            let (x, y) = (parent_x + i32::from(graphic.x), parent_y + i32::from(graphic.y));
+            self.draw_lump(&graphic.patch, x, y, graphic.alignment);
+            self.draw_children(graphic.children.as_ref(), x, y);
+        }
+        if let Some(animation) = &elem.animation
+            && self.player.shows(&animation.conditions) {
+            let (x, y) = (parent_x + i32::from(animation.x), parent_y + i32::from(animation.y));
+            if let Some(frame) = animation_frame(&animation.frames, self.time) {
+                self.draw_lump(&frame.lump, x, y, animation.alignment);
+            }
+            self.draw_children(animation.children.as_ref(), x, y);
+        }
+        if let Some(face) = &elem.face
+            && self.player.shows(&face.conditions) {
+            let (x, y) = (parent_x + i32::from(face.x), parent_y + i32::from(face.y));
+            self.draw_lump(&self.player.face(), x, y, face.alignment);
+            self.draw_children(face.children.as_ref(), x, y);
+        }
+        if let Some(facebackground) = &elem.facebackground
+            && self.player.shows(&facebackground.conditions) {
+            let (x, y) = (parent_x + i32::from(facebackground.x), parent_y + i32::from(facebackground.y));
+            self.draw_lump(&format!("STFB{}", self.player.player), x, y, facebackground.alignment);
+            self.draw_children(facebackground.children.as_ref(), x, y);
+        }
+        if let Some(number) = &elem.number
+            && self.player.shows(&number.conditions) {
+            let (x, y) = (parent_x + i32::from(number.x), parent_y + i32::from(number.y));
+            self.draw_number(number, x, y, false);
+            self.draw_children(number.children.as_ref(), x, y);
+        }
+        if let Some(percent) = &elem.percent
+            && self.player.shows(&percent.conditions) {
+            let (x, y) = (parent_x + i32::from(percent.x), parent_y + i32::from(percent.y));
+            self.draw_number(percent, x, y, true);
+            self.draw_children(percent.children.as_ref(), x, y);
+        }
+    }
+
+    // like vanilla, a patch's offsets move it from wherever the alignment puts it
+    fn draw_lump(&mut self, name: &str, x: i32, y: i32, alignment: Alignment) {
+        let Some(patch) = (self.graphic)(name) else {
+            return;
+        };
+        let (width, height) = image_size(&patch);
+        let (x, y) = alignment.top_left(x, y, width, height);
+        self.image.draw(&patch, x - patch.left_offset, y - patch.top_offset);
+    }
+
+    fn draw_number(&mut self, number: &Number, x: i32, y: i32, percent: bool) {
+        let (Some(value), Some((_, font))) = (
+            self.player.number(number),
+            self.fonts.iter().find(|(name, _)| *name == number.font)
+        ) else {
+            return;
+        };
+        let mut text = number_text(value, number.maxlength);
+        if percent {
+            text.push('%');
+        }
+        let height = text.chars().filter_map(|c| font.glyph(c)).map(|glyph| image_size(glyph).1).max().unwrap_or(0);
+        let (mut x, y) = number.alignment.top_left(x, y, font.text_width(&text), height);
+        for glyph in text.chars().filter_map(|c| font.glyph(c)) {
+            self.image.draw(glyph, x - glyph.left_offset, y - glyph.top_offset);
+            x += font.advance(glyph);
+        }
+    }
+}
+
+// the frames loop forever, each shown for its duration in seconds
+fn animation_frame(frames: &[Frame], time: f32) -> Option<&Frame> {
+    let total: f32 = frames.iter().map(|frame| frame.duration.max(0.0)).sum();
+    if total <= 0.0 {
+        return frames.first();
+    }
+    let mut time = time.rem_euclid(total);
+    frames.iter().find(|frame| {
+        time -= frame.duration.max(0.0);
+        time < 0.0
+    }).or(frames.last())
+}
+
+// numbers too long for maxlength are clamped to the most it has room for
+fn number_text(value: i32, maxlength: u8) -> String {
+    let digits = u32::from(maxlength).min(9);
+    // the minus sign takes up one of the digits
+    let digits = if value < 0 { digits.saturating_sub(1) } else { digits };
+    if digits == 0 {
+        return String::new();
+    }
+    let max = 10i32.pow(digits) - 1;
+    value.clamp(-max, max).to_string()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+    use super::super::super::*;
+    fn statusbar(children: Option<Vec<SBarElem>>) -> StatusBar {
+        StatusBar {
+            height: 32,
+            fullscreenrender: false,
+            fillflat: Some("GRNROCK".to_owned()),
+            children,
+            extra: Extra::new()
+        }
+    }
+    fn font(stem: &str) -> NumberFont {
+        NumberFont {
+            name: "Font".to_owned(),
+            numberfont_type: NumberFontType::Proportional,
+            stem: stem.to_owned(),
+            extra: Extra::new()
+        }
+    }
+    // solid images, the colour says which lump was drawn
+    fn solid(width: usize, height: usize, colour: u8) -> Image {
+        let mut image = Image::new(width, height);
+        for y in 0..height {
+            for x in 0..width {
+                image.set(x, y, [colour, colour, colour, 0xFF]);
+            }
+        }
+        image
+    }
+    fn test_graphic(name: &str) -> Option<Image> {
+        match name {
+            "STBAR" => Some(solid(320, 32, 1)),
+            "STFST01" => Some(solid(24, 29, 2)),
+            "STFB0" => Some(solid(34, 30, 3)),
+            "FRAME1" => Some(solid(4, 4, 4)),
+            "FRAME2" => Some(solid(4, 4, 5)),
+            "STTNUM0" => Some(solid(2, 4, 10)),
+            "STTNUM1" => Some(solid(1, 4, 11)),
+            "STTNUM9" => Some(solid(2, 4, 19)),
+            "STTPRCNT" => Some(solid(3, 4, 20)),
+            _ => None
+        }
+    }
+    #[test]
+    fn render_statusbar() {
+        let json = r#"{
+            "height": 32,
+            "fullscreenrender": false,
+            "fillflat": "GRNROCK",
+            "children": [
+                {
+                    "graphic": {
+                        "x": 160, "y": 200, "alignment": 9,
+                        "tranmap": null, "translation": null, "conditions": null,
+                        "patch": "STBAR",
+                        "children": [
+                            { "facebackground": { "x": -17, "y": -32, "alignment": 0, "conditions": null, "children": null } },
+                            { "face": { "x": -17, "y": -32, "alignment": 0, "conditions": null, "children": null } }
+                        ]
+                    }
+                },
+                {
+                    "canvas": {
+                        "x": 10, "y": 170, "alignment": 0,
+                        "conditions": [ { "condition": 1, "param": 1 } ],
+                        "children": [
+                            {
+                                "percent": {
+                                    "x": 50, "y": 0, "alignment": 2,
+                                    "tranmap": null, "translation": null, "conditions": null, "children": null,
+                                    "font": "BigRed", "type": 0, "param": 0, "maxlength": 3
+                                }
+                            },
+                            {
+                                "number": {
+                                    "x": 0, "y": 10, "alignment": 0,
+                                    "tranmap": null, "translation": null, "conditions": null, "children": null,
+                                    "font": "BigRed", "type": 4, "param": 0, "maxlength": 1
+                                }
+                            }
+                        ]
+                    }
+                },
+                {
+                    "animation": {
+                        "x": 0, "y": 0, "alignment": 0,
+                        "tranmap": null, "translation": null, "conditions": null, "children": null,
+                        "frames": [ { "lump": "FRAME1", "duration": 0.5 }, { "lump": "FRAME2", "duration": 0.25 } ]
+                    }
+                },
+                {
+                    "graphic": {
+                        "x": 300, "y": 0, "alignment": 0,
+                        "tranmap": null, "translation": null, "children": null,
+                        "conditions": [ { "condition": 0, "param": 2 } ],
+                        "patch": "STBAR"
+                    }
+                }
+            ]
+        }"#;
+        let statusbar: StatusBar = serde_json::from_str(json).unwrap();
+        let fonts = [NumberFont { name: "BigRed".to_owned(), ..font("STT") }];
+        let flat = |name: &str| (name == "GRNROCK").then(|| solid(64, 64, 9));
+        let image = render(&statusbar, &fonts, &PlayerState::default(), 0.6, test_graphic, flat);
+        assert_eq!((image.width, image.height), (SCREEN_WIDTH, SCREEN_HEIGHT));
+        // centred on x and sitting on the bottom of the screen
+        assert_eq!(image.get(0, 168), Some([1, 1, 1, 0xFF]));
+        assert_eq!(image.get(0, 167), Some([0, 0, 0, 0]));
+        // the face is drawn over its background, both from the bar's alignment point
+        assert_eq!(image.get(143, 168), Some([2, 2, 2, 0xFF]));
+        assert_eq!(image.get(143 + 30, 168), Some([3, 3, 3, 0xFF]));
+        // 100% is 8 wide and right aligned at x 60, so the glyphs go 1 0 0 % from x 52
+        assert_eq!(image.get(51, 170), Some([1, 1, 1, 0xFF]));
+        assert_eq!(image.get(52, 170), Some([11, 11, 11, 0xFF]));
+        assert_eq!(image.get(53, 170), Some([10, 10, 10, 0xFF]));
+        assert_eq!(image.get(57, 170), Some([20, 20, 20, 0xFF]));
+        // the pistol's 50 bullets clamped to a single digit
+        assert_eq!(image.get(10, 180), Some([19, 19, 19, 0xFF]));
+        // the second frame shows from 0.5 to 0.75 seconds
+        assert_eq!(image.get(0, 0), Some([5, 5, 5, 0xFF]));
+        assert_eq!(render(&statusbar, &fonts, &PlayerState::default(), 0.8, test_graphic, flat).get(0, 0), Some([4, 4, 4, 0xFF]));
+        // no shotgun, so that graphic isn't drawn
+        assert_eq!(image.get(300, 0), Some([0, 0, 0, 0]));
+    }
+    #[test]
+    fn statusbar_animates() {
+        let json = r#"[
+            {
+                "canvas": {
+                    "x": 0, "y": 0, "alignment": 0,
+                    "conditions": [ { "condition": 0, "param": 2 } ],
+                    "children": [
+                        {
+                            "animation": {
+                                "x": 0, "y": 0, "alignment": 0,
+                                "tranmap": null, "translation": null, "conditions": null, "children": null,
+                                "frames": [ { "lump": "FRAME1", "duration": 0.5 }, { "lump": "FRAME2", "duration": 0.25 } ]
+                            }
+                        }
+                    ]
+                }
+            },
+            {
+                "animation": {
+                    "x": 0, "y": 0, "alignment": 0,
+                    "tranmap": null, "translation": null, "conditions": null, "children": null,
+                    "frames": [ { "lump": "FRAME1", "duration": 0.5 } ]
+                }
+            }
+        ]"#;
+        let bar = statusbar(Some(serde_json::from_str(json).unwrap()));
+        // the only animation that changes is inside a canvas that needs the shotgun
+        assert!(!bar.animates(&PlayerState::default()));
+        let mut player = PlayerState::default();
+        player.weapons[2] = true;
+        assert!(bar.animates(&player));
+        assert!(!statusbar(None).animates(&player));
+    }
+    #[test]
+    fn load_number_fonts() {
+        let glyph = |name: &str| match name {
+            "STTNUM0" => Some(solid(3, 4, 10)),
+            "STTNUM1" => Some(solid(1, 4, 11)),
+            "STTPRCNT" => Some(solid(5, 4, 20)),
+            name if name.starts_with("STTNUM") => Some(solid(2, 4, 12)),
+            _ => None
+        };
+        let font_type = |numberfont_type| NumberFont { numberfont_type, ..font("STT") };
+
+        let proportional = font_type(NumberFontType::Proportional).load(glyph);
+        assert_eq!(proportional.missing(), vec!["STTMINUS"]);
+        assert_eq!(proportional.text_width("10%"), 1 + 3 + 5);
+        // no minus glyph, so it takes up no room
+        assert_eq!(proportional.text_width("-1"), 1);
+
+        let zero = font_type(NumberFontType::MonoSpacedZero).load(glyph);
+        assert_eq!(zero.text_width("10%"), 3 * 3);
+        let widest = font_type(NumberFontType::MonoSpaceWidest).load(glyph);
+        assert_eq!(widest.text_width("10%"), 3 * 5);
+
+        let missing = font_type(NumberFontType::MonoSpacedZero).load(|_| None);
+        assert_eq!(missing.missing().len(), 12);
+        assert_eq!(missing.text_width("100"), 0);
+    }
+    #[test]
+    fn check_missing_signs() {
+        let json = r#"[
+            {
+                "percent": {
+                    "x": 0, "y": 0, "alignment": 0,
+                    "tranmap": null, "translation": null, "conditions": null,
+                    "font": "Font", "type": 0, "param": 0, "maxlength": 3,
+                    "children": [
+                        {
+                            "number": {
+                                "x": 0, "y": 0, "alignment": 0,
+                                "tranmap": null, "translation": null, "conditions": null, "children": null,
+                                "font": "Other", "type": 2, "param": 0, "maxlength": 2
+                            }
+                        }
+                    ]
+                }
+            }
+        ]"#;
+        let children: Vec<SBarElem> = serde_json::from_str(json).unwrap();
+        let fonts = [font("STT"), NumberFont { name: "Other".to_owned(), ..font("STG") }, NumberFont { name: "Unused".to_owned(), ..font("STU") }];
+        let loaded: Vec<NumberFontGlyphs> = fonts.iter().map(|font| font.load(|_| None)).collect();
+        let glyphs = |font: &NumberFont| fonts.iter().position(|other| other == font).map(|idx| &loaded[idx]);
+        let diagnostics = check_number_fonts(&fonts, &[statusbar(Some(children.clone()))], "/data", glyphs);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, Rule::NumberFontMissingGlyph);
+        assert_eq!(diagnostics[0].path, "/data/numberfonts/0/stem");
+        assert!(diagnostics[0].message.contains("STTPRCNT"));
+        assert_eq!(diagnostics[1].path, "/data/numberfonts/1/stem");
+        assert!(diagnostics[1].message.contains("STGMINUS"));
+        assert_eq!(diagnostics[1].severity, diagnostics::Severity::Warning);
+
+        let diagnostics = check_number_fonts(&fonts, &[statusbar(Some(Vec::new()))], "/data", glyphs);
+        assert!(diagnostics.is_empty());
+        // fonts that haven't been loaded are left alone
+        let diagnostics = check_number_fonts(&fonts, &[statusbar(Some(children))], "/data", |_| None);
+        assert!(diagnostics.is_empty());
+    }
+    #[test]
+    fn number_text_clamping() {
+        assert_eq!(number_text(100, 3), "100");
+        assert_eq!(number_text(1234, 3), "999");
+        assert_eq!(number_text(-50, 2), "-9");
+        assert_eq!(number_text(-5, 1), "");
+        assert_eq!(number_text(7, 0), "");
+    }
+}
@@ -0,0 +1,69 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+// JSON Schemas generated from the same types the editor uses, for validation and completion in other editors
+
+use std::path::{Path, PathBuf};
+use schemars::generate::SchemaSettings;
+use serde_json::{json, Value};
+use super::{version, ID24Json};
+
+// the schema for every lump type is the full ID24Json schema, pinned to one value of the type field
+// that way the variant's data schema is the only one that can match
+pub fn schema(lump: &str) -> Value {
+    let generator = SchemaSettings::draft2020_12().into_generator();
+    let mut schema = generator.into_root_schema_for::<ID24Json>().to_value();
+    let mut properties = json!({ "type": { "const": lump } });
+    if let Some(current) = version::current(lump) {
+        properties["version"] = json!({ "pattern": format!("^{}\\.[0-9]+\\.[0-9]+$", current.major) });
+    }
+    if let Some(schema) = schema.as_object_mut() {
+        schema.insert("title".to_owned(), json!(format!("ID24 {lump} lump")));
+        if let Some(all_of) = schema.entry("allOf").or_insert_with(|| json!([])).as_array_mut() {
+            all_of.push(json!({ "properties": properties, "required": ["type"] }));
+        }
+    }
+    schema
+}
+
+// writes <lump>.schema.json for every lump type into dir
+pub fn write_schemas(dir: &Path) -> Result<Vec<PathBuf>, String> {
+    version::lumps().map(|lump| {
+        let path = dir.join(format!("{lump}.schema.json"));
+        let text = serde_json::to_string_pretty(&schema(lump)).map_err(|why| why.to_string())?;
+        std::fs::write(&path, text)
+            .map_err(|why| format!("failed to write {}: {why}", path.display()))?;
+        Ok(path)
+    }).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    #[test]
+    fn skydefs_schema() {
+        let schema = schema("skydefs");
+        assert_eq!(schema["$schema"], "https://json-schema.org/draft/2020-12/schema");
+        assert_eq!(schema["allOf"][0]["properties"]["type"]["const"], "skydefs");
+        let defs = schema["$defs"].as_object().unwrap();
+        assert!(defs.contains_key("Sky"));
+        assert!(defs.contains_key("SkyType"));
+    }
+    #[test]
+    fn bitfield_schemas() {
+        let schema = schema("statusbar").to_string();
+        assert!(schema.contains("[0,1,2,4,5,6,8,9,10]"));
+        assert!(schema.contains("[0,1,2,4,4096,4097,4098,4100]"));
+    }
+}
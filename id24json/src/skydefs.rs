@@ -0,0 +1,316 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use std::fmt::{Display, Formatter};
+use super::Extra;
+use super::diagnostics::{Diagnostic, Rule};
+
+// PSX Doom's 37 fire colours, coldest first, as the nearest ramps in the PC PLAYPAL
+// black, then the dark to bright reds, the oranges, and the yellows up to white
+pub const PSX_FIRE_PALETTE: [u8; 37] = [
+    0,
+    191, 190, 189, 188, 187, 186, 185, 184, 183, 182, 181, 180, 179, 178, 177, 176,
+    223, 222, 221, 220, 219, 218, 217, 216, 215, 214, 213, 212, 211, 210, 209, 208,
+    231, 230, 224, 4
+];
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Fire {
+    pub updatetime: f32,
+    pub palette: Vec<u8>,
+    #[serde(flatten)]
+    pub extra: Extra
+}
+
+impl Default for Fire {
+    fn default() -> Self {
+        Self {
+            updatetime: 0.05715, // 2 tics
+            palette: PSX_FIRE_PALETTE.to_vec(),
+            extra: Extra::new()
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct SkyTex {
+    pub name: String,
+    pub mid: u16,
+    pub scrollx: f32,
+    pub scrolly: f32,
+    pub scalex: f32,
+    pub scaley: f32,
+    #[serde(flatten)]
+    pub extra: Extra
+}
+
+impl Default for SkyTex {
+    fn default() -> Self {
+        Self {
+            name: "SKY1".to_owned(),
+            mid: 100,
+            scrollx: 0.0,
+            scrolly: 0.0,
+            scalex: 1.0,
+            scaley: 1.0,
+            extra: Extra::new()
+        }
+    }
+}
+
+#[derive(serde_repr::Serialize_repr, serde_repr::Deserialize_repr, strum_macros::VariantArray, PartialEq, Clone, Copy, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema_repr))]
+#[repr(u8)]
+pub enum SkyType {
+    Standard = 0,
+    Fire = 1,
+    WithForeground = 2
+}
+
+impl Display for SkyType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SkyType::Standard        => "Standard",
+            SkyType::Fire            => "Fire",
+            SkyType::WithForeground  => "With Foreground",
+        })
+    }
+}
+
+// keys of SkyTex, which sit directly in a sky for its background texture
+const SKYTEX_KEYS: [&str; 6] = ["name", "mid", "scrollx", "scrolly", "scalex", "scaley"];
+
+#[derive(serde::Serialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct Sky {
+    #[serde(rename = "type")]
+    pub sky_type: SkyType,
+    // unknown keys go in the sky's extra, so this one's is always empty
+    #[serde(flatten)]
+    pub backgroundtex: SkyTex,
+    pub fire: Option<Fire>,
+    pub foregroundtex: Option<SkyTex>,
+    #[serde(flatten)]
+    pub extra: Extra
+}
+
+// both flattened structs would see every unknown key, so the known ones are picked out by hand
+impl<'a> serde::Deserialize<'a> for Sky {
+    fn deserialize<D>(deserializer: D) -> Result<Sky, D::Error> where D: serde::Deserializer<'a> {
+        #[derive(serde::Deserialize)]
+        struct Known {
+            #[serde(rename = "type")]
+            sky_type: SkyType,
+            fire: Option<Fire>,
+            foregroundtex: Option<SkyTex>
+        }
+        let mut extra = Extra::deserialize(deserializer)?;
+        let mut take = |keys: &[&str]| -> Extra {
+            keys.iter().filter_map(|key| Some((key.to_string(), extra.shift_remove(*key)?))).collect()
+        };
+        let backgroundtex = take(&SKYTEX_KEYS);
+        let known = take(&["type", "fire", "foregroundtex"]);
+        let backgroundtex: SkyTex = serde_json::from_value(serde_json::Value::Object(backgroundtex)).map_err(serde::de::Error::custom)?;
+        let known: Known = serde_json::from_value(serde_json::Value::Object(known)).map_err(serde::de::Error::custom)?;
+        Ok(Sky {
+            sky_type: known.sky_type,
+            backgroundtex,
+            fire: known.fire,
+            foregroundtex: known.foregroundtex,
+            extra
+        })
+    }
+}
+
+impl Default for Sky {
+    fn default() -> Self {
+        Self {
+            sky_type: SkyType::Standard,
+            backgroundtex: SkyTex::default(),
+            fire: None,
+            foregroundtex: None,
+            extra: Extra::new()
+        }
+    }
+}
+
+impl Sky {
+    // fire and foregroundtex must be non-null only for the matching sky type
+    pub(super) fn validate(&self, path: &str, diagnostics: &mut Vec<Diagnostic>) {
+        let wants_fire = self.sky_type == SkyType::Fire;
+        if wants_fire != self.fire.is_some() {
+            diagnostics.push(Diagnostic::new(
+                Rule::SkyFireMismatch,
+                format!("{path}/fire"),
+                if wants_fire {
+                    "Fire skies must have fire properties"
+                } else {
+                    "Fire properties must be null unless the sky type is Fire"
+                }
+            ));
+        }
+        let wants_foreground = self.sky_type == SkyType::WithForeground;
+        if wants_foreground != self.foregroundtex.is_some() {
+            diagnostics.push(Diagnostic::new(
+                Rule::SkyForegroundMismatch,
+                format!("{path}/foregroundtex"),
+                if wants_foreground {
+                    "Skies with a foreground must have a foreground texture"
+                } else {
+                    "Foreground texture must be null unless the sky type is With Foreground"
+                }
+            ));
+        }
+    }
+}
+
+#[derive(serde::Serialize, serde::Deserialize, Clone, PartialEq, Debug)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+pub struct FlatMapping {
+    pub flat: String,
+    pub sky: String,
+    #[serde(flatten)]
+    pub extra: Extra
+}
+
+impl Default for FlatMapping {
+    fn default() -> Self {
+        Self {
+            flat: "F_SKY1".to_owned(),
+            sky: "SKY1".to_owned(),
+            extra: Extra::new()
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+    #[test]
+    fn read_simple_skydefs() {
+        let json = r#"{
+            "type": "skydefs",
+            "version": "1.0.0",
+            "metadata": { },
+            "data":
+            {
+                "skies":
+                [
+                    {
+                        "type": 0,
+                        "name": "SKY1",
+                        "mid": 100,
+                        "scrollx": 1,
+                        "scrolly": 2,
+                        "scalex": 3,
+                        "scaley": 4,
+		                "fire": null,
+                        "foregroundtex": null
+                    }
+                ],
+                "flatmapping":
+                [
+                    {
+                        "flat" : "FLAT1",
+                        "sky": "SKY1"
+                    }
+                ]
+            }
+        }"#;
+        let data: ID24Json = serde_json::from_str(json).unwrap();
+        assert_eq!(data.version, ID24JsonVersion { major: 1, minor: 0, revision: 0 });
+        assert_eq!(data.data, ID24JsonData::SKYDEFS {
+            skies: Some(vec![Sky {
+                sky_type: SkyType::Standard,
+                backgroundtex: SkyTex {
+                    name: "SKY1".to_owned(),
+                    mid: 100,
+                    scrollx: 1.0,
+                    scrolly: 2.0,
+                    scalex: 3.0,
+                    scaley: 4.0,
+                    extra: Extra::new()
+                },
+                fire: None,
+                foregroundtex: None,
+                extra: Extra::new()
+            }]),
+            flatmapping: Some(vec![FlatMapping {
+                flat: "FLAT1".to_owned(),
+                sky: "SKY1".to_owned(),
+                extra: Extra::new()
+            }]),
+            extra: Extra::new()
+        });
+        let json = r#"{
+            "type": "skydefs",
+            "version": "1.0.0",
+            "metadata": { },
+            "data":
+            {
+                "skies": null,
+                "flatmapping": null
+            }
+        }"#;
+        let data: ID24Json = serde_json::from_str(json).unwrap();
+        assert_eq!(data.version, ID24JsonVersion { major: 1, minor: 0, revision: 0 });
+        assert_eq!(data.data, ID24JsonData::skydefs());
+    }
+    #[test]
+    // TODO: make this a real test and add more test files
+    fn skydefs_test_2() {
+        let data: ID24Json = serde_json::from_str(
+            include_str!("test_files/skydefs_1.json")
+        ).unwrap();
+        assert_eq!(data.version, ID24JsonVersion { major: 1, minor: 0, revision: 0 });
+    }
+    #[test]
+    fn validate_sky_type_mismatch() {
+        let mut sky = Sky::default();
+        let mut diagnostics = Vec::new();
+        sky.validate("/data/skies/0", &mut diagnostics);
+        assert!(diagnostics.is_empty());
+
+        sky.sky_type = SkyType::Fire;
+        sky.foregroundtex = Some(SkyTex::default());
+        sky.validate("/data/skies/0", &mut diagnostics);
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(diagnostics[0].rule, Rule::SkyFireMismatch);
+        assert_eq!(diagnostics[0].path, "/data/skies/0/fire");
+        assert_eq!(diagnostics[1].rule, Rule::SkyForegroundMismatch);
+        assert_eq!(diagnostics[1].path, "/data/skies/0/foregroundtex");
+
+        diagnostics.clear();
+        sky.sky_type = SkyType::WithForeground;
+        sky.validate("/data/skies/0", &mut diagnostics);
+        assert!(diagnostics.is_empty());
+
+        sky.sky_type = SkyType::Standard;
+        sky.foregroundtex = None;
+        sky.fire = Some(Fire::default());
+        sky.validate("/data/skies/0", &mut diagnostics);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::SkyFireMismatch);
+    }
+    #[test]
+    fn validate_test_file() {
+        let data: ID24Json = serde_json::from_str(
+            include_str!("test_files/skydefs_1.json")
+        ).unwrap();
+        assert!(data.validate().is_empty());
+    }
+}
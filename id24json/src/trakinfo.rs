@@ -0,0 +1,125 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+// TRAKINFO isn't part of the ID24 spec, this is based on the lump shipped with the 2024 re-release
+// data is an object keyed by music lump name, so there's no room for anything other than tracks
+// anything unknown about a track is kept in its extra fields
+
+use serde::de::Error as _;
+use serde::ser::SerializeMap;
+use super::Extra;
+
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct TrakInfo {
+    // kept in file order
+    pub tracks: Vec<Track>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Track {
+    // the music lump this entry applies to, e.g. D_E1M1
+    pub lump: String,
+    // the lump played instead when remixed music is enabled
+    pub remixed: Option<String>,
+    pub extra: Extra,
+}
+
+// everything but the lump name, which is the key in the file
+#[derive(serde::Serialize, serde::Deserialize)]
+#[cfg_attr(feature = "schema", derive(schemars::JsonSchema))]
+struct TrackData {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    remixed: Option<String>,
+    #[serde(flatten)]
+    extra: Extra,
+}
+
+impl TrakInfo {
+    pub fn track(&self, lump: &str) -> Option<&Track> {
+        self.tracks.iter().find(|track| track.lump.eq_ignore_ascii_case(lump))
+    }
+}
+
+impl serde::Serialize for TrakInfo {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error> where S: serde::Serializer {
+        let mut map = serializer.serialize_map(Some(self.tracks.len()))?;
+        for track in &self.tracks {
+            map.serialize_entry(&track.lump, &TrackData {
+                remixed: track.remixed.clone(),
+                extra: track.extra.clone(),
+            })?;
+        }
+        map.end()
+    }
+}
+
+impl<'a> serde::Deserialize<'a> for TrakInfo {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error> where D: serde::Deserializer<'a> {
+        let map = serde_json::Map::<String, serde_json::Value>::deserialize(deserializer)?;
+        let tracks = map.into_iter().map(|(lump, value)| {
+            let data: TrackData = serde_json::from_value(value)
+                .map_err(|why| D::Error::custom(format!("track {lump}: {why}")))?;
+            Ok(Track { lump, remixed: data.remixed, extra: data.extra })
+        }).collect::<Result<_, D::Error>>()?;
+        Ok(Self { tracks })
+    }
+}
+
+#[cfg(feature = "schema")]
+impl schemars::JsonSchema for TrakInfo {
+    fn schema_name() -> std::borrow::Cow<'static, str> {
+        "TrakInfo".into()
+    }
+
+    fn json_schema(generator: &mut schemars::SchemaGenerator) -> schemars::Schema {
+        let track = generator.subschema_for::<TrackData>();
+        schemars::json_schema!({
+            "description": "Tracks keyed by music lump name",
+            "type": "object",
+            "additionalProperties": track
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::super::*;
+    use super::super::parse::ParseMode;
+    const TRAKINFO: &str = include_str!("test_files/trakinfo.json");
+    #[test]
+    fn read_trakinfo() {
+        let json = ID24Json::parse(TRAKINFO, ParseMode::Lenient).unwrap();
+        let ID24JsonData::TRAKINFO(trakinfo) = &json.data else {
+            panic!("expected trakinfo");
+        };
+        let lumps: Vec<&str> = trakinfo.tracks.iter().map(|track| track.lump.as_str()).collect();
+        assert_eq!(lumps, vec!["D_E1M1", "D_RUNNIN", "D_INTRO"]);
+        assert_eq!(trakinfo.track("d_runnin").unwrap().remixed.as_deref(), Some("D_RUNNIR"));
+        assert_eq!(trakinfo.track("D_INTRO").unwrap().remixed, None);
+        assert_eq!(trakinfo.track("D_INTRO").unwrap().extra["loop"], serde_json::json!(false));
+    }
+    #[test]
+    fn round_trip() {
+        let json = ID24Json::parse(TRAKINFO, ParseMode::Lenient).unwrap();
+        let original: serde_json::Value = serde_json::from_str(TRAKINFO).unwrap();
+        assert_eq!(json.to_value().unwrap(), original);
+        assert_eq!(json.to_string_preserving(Some(TRAKINFO)).unwrap(), TRAKINFO);
+    }
+    #[test]
+    fn rejects_bad_tracks() {
+        let json = r#"{ "type": "trakinfo", "version": "1.0.0", "metadata": { }, "data": { "D_E1M1": { "remixed": 5 } } }"#;
+        let diagnostics = ID24Json::parse(json, ParseMode::Lenient).unwrap_err();
+        assert!(diagnostics[0].message.contains("D_E1M1"));
+    }
+}
@@ -0,0 +1,113 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use super::diagnostics::{Diagnostic, Rule};
+
+// one entry for every palette index
+pub const TABLE_SIZE: usize = 256;
+
+// a table that leaves every colour alone
+pub fn identity() -> Vec<u8> {
+    (0..=u8::MAX).collect()
+}
+
+// maps the source range onto the destination range, spreading it out or squashing it as needed
+// works the same way as ZDoom's "112:127=96:111" ranges, a reversed range flips the gradient
+pub fn remap(table: &mut [u8], source: (u8, u8), dest: (u8, u8)) {
+    let ((source_start, source_end), (dest_start, dest_end)) = if source.0 > source.1 {
+        ((source.1, source.0), (dest.1, dest.0))
+    } else {
+        (source, dest)
+    };
+    let source_length = i32::from(source_end) - i32::from(source_start);
+    let dest_length = i32::from(dest_end) - i32::from(dest_start);
+    for index in source_start..=source_end {
+        let Some(entry) = table.get_mut(usize::from(index)) else {
+            break;
+        };
+        let offset = i32::from(index) - i32::from(source_start);
+        let value = if source_length == 0 {
+            i32::from(dest_start)
+        } else {
+            i32::from(dest_start) + (offset * dest_length).div_euclid(source_length)
+        };
+        // always in range since it's between dest_start and dest_end
+        *entry = u8::try_from(value).unwrap_or(u8::MAX);
+    }
+}
+
+pub(super) fn validate(table: &[u8], path: &str, diagnostics: &mut Vec<Diagnostic>) {
+    if table.len() != TABLE_SIZE {
+        diagnostics.push(Diagnostic::new(
+            Rule::TranslationTableSize,
+            format!("{path}/table"),
+            format!("Translation tables must have exactly {TABLE_SIZE} entries, found {}", table.len())
+        ));
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::*;
+    use super::super::parse::ParseMode;
+    #[test]
+    fn remap_ranges() {
+        let mut table = identity();
+        // doom's green player ramp to red
+        remap(&mut table, (112, 127), (176, 191));
+        assert_eq!(table[111], 111);
+        assert_eq!(table[112], 176);
+        assert_eq!(table[127], 191);
+        assert_eq!(table[128], 128);
+
+        // squashed into half the space
+        let mut table = identity();
+        remap(&mut table, (0, 15), (32, 39));
+        assert_eq!(&table[0..16], &[32, 32, 32, 33, 33, 34, 34, 35, 35, 36, 36, 37, 37, 38, 38, 39]);
+
+        // reversed
+        let mut table = identity();
+        remap(&mut table, (10, 12), (22, 20));
+        assert_eq!(&table[10..13], &[22, 21, 20]);
+        let mut reversed = identity();
+        remap(&mut reversed, (12, 10), (20, 22));
+        assert_eq!(table, reversed);
+
+        // single entry
+        let mut table = identity();
+        remap(&mut table, (5, 5), (9, 200));
+        assert_eq!(table[5], 9);
+    }
+    #[test]
+    fn read_translation() {
+        let data = ID24Json::parse(include_str!("test_files/translation.json"), ParseMode::Strict).unwrap();
+        assert!(data.validate().is_empty());
+        // the nulls in its metadata are kept
+        assert_eq!(data.to_value().unwrap()["metadata"]["author"], serde_json::Value::Null);
+        let ID24JsonData::Translation { table, .. } = &data.data else {
+            panic!("expected a translation");
+        };
+        let mut expected = identity();
+        remap(&mut expected, (112, 127), (176, 191));
+        assert_eq!(table, &expected);
+    }
+    #[test]
+    fn validate_table_size() {
+        let mut diagnostics = Vec::new();
+        validate(&[0; 255], "/data", &mut diagnostics);
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::TranslationTableSize);
+    }
+}
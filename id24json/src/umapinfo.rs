@@ -0,0 +1,466 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+// UMAPINFO isn't JSON, but ID24 hooks its lumps into maps through it:
+// enteranim and exitanim name Interlevel lumps, endfinale names a Finale lump
+// every key is kept as written, in order, so keys this editor doesn't know about survive a save
+// comments and whitespace are kept too, maps that haven't been edited are written back exactly as they were
+
+use std::fmt::{Display, Formatter, Write as _};
+use super::references::{Reference, ReferenceKind};
+
+#[derive(Clone, Default, PartialEq, Debug)]
+pub struct UMapInfo {
+    pub maps: Vec<MapEntry>,
+    // whitespace and comments after the last map
+    after: String,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct MapEntry {
+    // e.g. MAP01 or E1M1
+    pub name: String,
+    pub fields: Vec<Field>,
+    // whitespace and comments before the map, and before its closing brace
+    before: String,
+    end: String,
+    // the map exactly as it was written, None for new maps
+    source: Option<String>,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub struct Field {
+    pub key: String,
+    // most keys take one value, some like intertext and episode take a comma separated list
+    pub values: Vec<FieldValue>,
+    // whitespace and comments before the key, empty for new keys
+    pub before: String,
+}
+
+#[derive(Clone, PartialEq, Debug)]
+pub enum FieldValue {
+    String(String),
+    // kept as written so saving doesn't reformat them
+    Number(String),
+    // bare words, e.g. clear, true, or the actor and action names in bossaction
+    Identifier(String),
+}
+
+// the keys that point at ID24 JSON lumps and the kind of lump they point at
+pub const ID24_KEYS: &[(&str, ReferenceKind)] = &[
+    ("enteranim", ReferenceKind::Interlevel),
+    ("exitanim",  ReferenceKind::Interlevel),
+    ("endfinale", ReferenceKind::Finale),
+];
+
+impl MapEntry {
+    pub fn new(name: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            fields: Vec::new(),
+            before: String::new(),
+            end: String::new(),
+            source: None,
+        }
+    }
+
+    // keys are case insensitive, the last one wins if a key is repeated
+    pub fn get(&self, key: &str) -> Option<&Field> {
+        self.fields.iter().rev().find(|field| field.key.eq_ignore_ascii_case(key))
+    }
+
+    // the value of a key that takes a single string, e.g. levelname or enteranim
+    pub fn get_string(&self, key: &str) -> Option<&str> {
+        match self.get(key)?.values.as_slice() {
+            [FieldValue::String(value)] => Some(value),
+            _ => None
+        }
+    }
+
+    // replaces every occurrence of the key, or removes it if value is None
+    // a new key goes at the end
+    pub fn set_string(&mut self, key: &str, value: Option<String>) {
+        let Some(value) = value else {
+            self.fields.retain(|field| !field.key.eq_ignore_ascii_case(key));
+            return;
+        };
+        let values = vec![FieldValue::String(value)];
+        match self.fields.iter().position(|field| field.key.eq_ignore_ascii_case(key)) {
+            Some(first) => {
+                self.fields[first].values = values;
+                let mut idx = 0;
+                self.fields.retain(|field| {
+                    let keep = idx <= first || !field.key.eq_ignore_ascii_case(key);
+                    idx += 1;
+                    keep
+                });
+            },
+            None => self.fields.push(Field { key: key.to_owned(), values, before: String::new() })
+        }
+    }
+
+    // the source, if the map still says exactly what it was parsed from
+    fn unedited_source(&self) -> Option<&str> {
+        let source = self.source.as_deref()?;
+        let mut parser = Parser::new(source);
+        parser.next_token().ok()?;
+        let original = parser.parse_map().ok()?;
+        (original.name == self.name && original.fields == self.fields && original.end == self.end).then_some(source)
+    }
+}
+
+impl UMapInfo {
+    pub fn map(&self, name: &str) -> Option<&MapEntry> {
+        self.maps.iter().find(|map| map.name.eq_ignore_ascii_case(name))
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        Parser::new(text).parse()
+    }
+
+    // every Interlevel and Finale lump named by a map, paths are /<map>/<key>
+    pub fn references(&self) -> Vec<Reference> {
+        let mut references = Vec::new();
+        for map in &self.maps {
+            for (key, kind) in ID24_KEYS {
+                if let Some(name) = map.get_string(key) {
+                    references.push(Reference::new(*kind, name, format!("/{}/{key}", map.name)));
+                }
+            }
+        }
+        references
+    }
+}
+
+impl Display for FieldValue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldValue::String(value) => {
+                f.write_char('"')?;
+                for c in value.chars() {
+                    match c {
+                        '"' => f.write_str("\\\"")?,
+                        '\\' => f.write_str("\\\\")?,
+                        '\n' => f.write_str("\\n")?,
+                        c => f.write_char(c)?
+                    }
+                }
+                f.write_char('"')
+            },
+            FieldValue::Number(value) |
+            FieldValue::Identifier(value) => f.write_str(value)
+        }
+    }
+}
+
+// edited maps are written out again, keeping the comments between their keys
+// new maps and keys are laid out one key per line, with a blank line between maps
+impl Display for UMapInfo {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (idx, map) in self.maps.iter().enumerate() {
+            match map.before.as_str() {
+                "" if idx > 0 => f.write_str("\n\n")?,
+                before => f.write_str(before)?
+            }
+            if let Some(source) = map.unedited_source() {
+                f.write_str(source)?;
+                continue;
+            }
+            write!(f, "MAP {}\n{{", map.name)?;
+            for field in &map.fields {
+                let values = field.values.iter().map(ToString::to_string).collect::<Vec<_>>();
+                let before = if field.before.is_empty() { "\n\t" } else { &field.before };
+                write!(f, "{before}{} = {}", field.key, values.join(", "))?;
+            }
+            let end = if map.end.is_empty() { "\n" } else { &map.end };
+            write!(f, "{end}}}")?;
+        }
+        match self.after.as_str() {
+            "" if !self.maps.is_empty() => f.write_str("\n"),
+            after => f.write_str(after)
+        }
+    }
+}
+
+#[derive(Clone, PartialEq, Debug)]
+enum Token {
+    Word(String),
+    String(String),
+    Symbol(char),
+}
+
+#[derive(Clone)]
+struct Parser<'a> {
+    text: &'a str,
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+    // byte offset of the next char
+    pos: usize,
+    line: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(text: &'a str) -> Self {
+        Self {
+            text,
+            chars: text.chars().peekable(),
+            pos: 0,
+            line: 1,
+        }
+    }
+
+    fn error<T>(&self, message: impl Display) -> Result<T, String> {
+        Err(format!("line {}: {message}", self.line))
+    }
+
+    fn bump(&mut self) -> Option<char> {
+        let c = self.chars.next();
+        if let Some(c) = c {
+            self.pos += c.len_utf8();
+        }
+        if c == Some('\n') {
+            self.line += 1;
+        }
+        c
+    }
+
+    // the whitespace and comments up to the next token
+    fn trivia(&mut self) -> Result<String, String> {
+        let start = self.pos;
+        self.skip_whitespace_and_comments()?;
+        Ok(self.text[start..self.pos].to_owned())
+    }
+
+    fn skip_whitespace_and_comments(&mut self) -> Result<(), String> {
+        loop {
+            match self.chars.peek() {
+                Some(c) if c.is_whitespace() => {
+                    self.bump();
+                },
+                Some('/') => {
+                    let mut lookahead = self.chars.clone();
+                    lookahead.next();
+                    match lookahead.next() {
+                        Some('/') => {
+                            while self.bump().is_some_and(|c| c != '\n') {}
+                        },
+                        Some('*') => {
+                            self.bump();
+                            self.bump();
+                            let mut last = '\0';
+                            loop {
+                                let Some(c) = self.bump() else {
+                                    return self.error("unterminated comment");
+                                };
+                                if last == '*' && c == '/' {
+                                    break;
+                                }
+                                last = c;
+                            }
+                        },
+                        _ => return Ok(())
+                    }
+                },
+                _ => return Ok(())
+            }
+        }
+    }
+
+    fn next_token(&mut self) -> Result<Option<Token>, String> {
+        self.skip_whitespace_and_comments()?;
+        let Some(&c) = self.chars.peek() else {
+            return Ok(None);
+        };
+        match c {
+            '{' | '}' | '=' | ',' => {
+                self.bump();
+                Ok(Some(Token::Symbol(c)))
+            },
+            '"' => {
+                self.bump();
+                let mut value = String::new();
+                loop {
+                    match self.bump() {
+                        Some('"') => break,
+                        Some('\\') => match self.bump() {
+                            Some('n') => value.push('\n'),
+                            Some(c) => value.push(c),
+                            None => return self.error("unterminated string")
+                        },
+                        Some(c) => value.push(c),
+                        None => return self.error("unterminated string")
+                    }
+                }
+                Ok(Some(Token::String(value)))
+            },
+            _ => {
+                let mut word = String::new();
+                while let Some(&c) = self.chars.peek()
+                    && !c.is_whitespace() && !matches!(c, '{' | '}' | '=' | ',' | '"') {
+                    if c == '/' && self.chars.clone().nth(1).is_some_and(|next| next == '/' || next == '*') {
+                        break;
+                    }
+                    word.push(c);
+                    self.bump();
+                }
+                Ok(Some(Token::Word(word)))
+            }
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), String> {
+        match self.next_token()? {
+            Some(Token::Symbol(c)) if c == symbol => Ok(()),
+            Some(token) => self.error(format!("expected '{symbol}', found {token:?}")),
+            None => self.error(format!("expected '{symbol}', found the end of the file"))
+        }
+    }
+
+    fn parse(mut self) -> Result<UMapInfo, String> {
+        let mut maps = Vec::new();
+        loop {
+            let before = self.trivia()?;
+            let start = self.pos;
+            match self.next_token()? {
+                Some(Token::Word(word)) if word.eq_ignore_ascii_case("map") => {
+                    let mut map = self.parse_map()?;
+                    map.before = before;
+                    map.source = Some(self.text[start..self.pos].to_owned());
+                    maps.push(map);
+                },
+                Some(token) => return self.error(format!("expected MAP, found {token:?}")),
+                None => return Ok(UMapInfo { maps, after: before })
+            }
+        }
+    }
+
+    fn parse_map(&mut self) -> Result<MapEntry, String> {
+        let name = match self.next_token()? {
+            Some(Token::Word(name)) => name,
+            token => return self.error(format!("expected a map name, found {token:?}"))
+        };
+        self.expect_symbol('{')?;
+        let mut map = MapEntry::new(name);
+        loop {
+            let before = self.trivia()?;
+            match self.next_token()? {
+                Some(Token::Symbol('}')) => {
+                    map.end = before;
+                    break;
+                },
+                Some(Token::Word(key)) => {
+                    self.expect_symbol('=')?;
+                    let mut values = vec![self.parse_value()?];
+                    // looked at without taking it, since whatever isn't a comma belongs to the next key
+                    loop {
+                        let mut lookahead = self.clone();
+                        if lookahead.next_token()? != Some(Token::Symbol(',')) {
+                            break;
+                        }
+                        *self = lookahead;
+                        values.push(self.parse_value()?);
+                    }
+                    map.fields.push(Field { key, values, before });
+                },
+                Some(token) => return self.error(format!("expected a key in map {}, found {token:?}", map.name)),
+                None => return self.error(format!("map {} is missing its closing '}}'", map.name))
+            }
+        }
+        Ok(map)
+    }
+
+    fn parse_value(&mut self) -> Result<FieldValue, String> {
+        match self.next_token()? {
+            Some(Token::String(value)) => Ok(FieldValue::String(value)),
+            Some(Token::Word(word)) if word.starts_with(|c: char| c.is_ascii_digit() || c == '-' || c == '+' || c == '.') => {
+                Ok(FieldValue::Number(word))
+            },
+            Some(Token::Word(word)) => Ok(FieldValue::Identifier(word)),
+            Some(token) => self.error(format!("expected a value, found {token:?}")),
+            None => self.error("expected a value, found the end of the file")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    const UMAPINFO: &str = include_str!("test_files/umapinfo.txt");
+    #[test]
+    fn read_umapinfo() {
+        let umapinfo = UMapInfo::parse(UMAPINFO).unwrap();
+        assert_eq!(umapinfo.maps.len(), 3);
+        let map01 = umapinfo.map("map01").unwrap();
+        assert_eq!(map01.get_string("LevelName"), Some("Entryway"));
+        assert_eq!(map01.get_string("enteranim"), Some("INTRMAP1"));
+        assert_eq!(map01.get("label").unwrap().values, vec![FieldValue::Identifier("clear".to_owned())]);
+        assert_eq!(map01.get("par").unwrap().values, vec![FieldValue::Number("30".to_owned())]);
+        let map02 = umapinfo.map("MAP02").unwrap();
+        assert_eq!(map02.get("intertext").unwrap().values, vec![
+            FieldValue::String("Once you beat the big badasses".to_owned()),
+            FieldValue::String("you will \"win\"".to_owned()),
+        ]);
+        assert_eq!(map02.fields.iter().filter(|field| field.key == "bossaction").count(), 2);
+    }
+    #[test]
+    fn write_umapinfo() {
+        let mut umapinfo = UMapInfo::parse(UMAPINFO).unwrap();
+        assert_eq!(umapinfo.to_string(), UMAPINFO);
+
+        // an edited map is written out again, but its comments stay where they were
+        umapinfo.maps[1].set_string("exitanim", Some("INTRMAP2".to_owned()));
+        umapinfo.maps[2].set_string("enteranim", Some("INTRMAP3".to_owned()));
+        let text = umapinfo.to_string();
+        assert!(text.starts_with("// a small UMAPINFO using the ID24 keys\nMAP MAP01\n{\n\tlevelname = \"Entryway\"\n"));
+        assert!(text.contains("\tlevelname = \"Underhalls\"\n\t/* intertext can be split\n\t   over several strings */\n\tintertext = "));
+        assert!(text.contains("\texitanim = \"INTRMAP2\"\n"));
+        assert!(text.ends_with("\tendfinale = \"FINMAP30\"\n\tenteranim = \"INTRMAP3\"\n}\n"));
+        assert_eq!(UMapInfo::parse(&text).unwrap().to_string(), text);
+
+        let mut new = UMapInfo::default();
+        new.maps.push(MapEntry::new("MAP01"));
+        new.maps[0].set_string("levelname", Some("Entryway".to_owned()));
+        new.maps.push(MapEntry::new("MAP02"));
+        assert_eq!(new.to_string(), "MAP MAP01\n{\n\tlevelname = \"Entryway\"\n}\n\nMAP MAP02\n{\n}\n");
+    }
+    #[test]
+    fn set_string() {
+        let mut map = MapEntry::new("MAP01");
+        map.set_string("enteranim", Some("A".to_owned()));
+        map.fields.push(Field { key: "EnterAnim".to_owned(), values: vec![FieldValue::String("B".to_owned())], before: String::new() });
+        map.set_string("levelname", Some("Entryway".to_owned()));
+        map.set_string("enteranim", Some("C".to_owned()));
+        assert_eq!(map.fields.len(), 2);
+        assert_eq!(map.fields[0].key, "enteranim");
+        assert_eq!(map.get_string("ENTERANIM"), Some("C"));
+        map.set_string("enteranim", None);
+        assert_eq!(map.fields.len(), 1);
+    }
+    #[test]
+    fn umapinfo_references() {
+        let umapinfo = UMapInfo::parse(UMAPINFO).unwrap();
+        assert_eq!(umapinfo.references(), vec![
+            Reference::new(ReferenceKind::Interlevel, "INTRMAP1", "/MAP01/enteranim"),
+            Reference::new(ReferenceKind::Interlevel, "INTRMAP1", "/map02/exitanim"),
+            Reference::new(ReferenceKind::Finale, "FINMAP30", "/MAP30/endfinale"),
+        ]);
+    }
+    #[test]
+    fn parse_errors() {
+        assert_eq!(UMapInfo::parse("MAP MAP01\n{\n\tlevelname \"Entryway\"\n}"),
+            Err("line 3: expected '=', found String(\"Entryway\")".to_owned()));
+        assert_eq!(UMapInfo::parse("MAP MAP01 {\n/* unfinished"), Err("line 2: unterminated comment".to_owned()));
+        assert!(UMapInfo::parse("MAP MAP01 {").is_err());
+        assert!(UMapInfo::parse("levelname = \"Entryway\"").is_err());
+    }
+}
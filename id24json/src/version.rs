@@ -0,0 +1,181 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use serde_json::Value;
+use super::ID24JsonVersion;
+use super::diagnostics::{Diagnostic, Rule};
+
+// the version each lump type is written with, keyed by the name used in the type field
+// every version with the same major is readable, older ones are upgraded with MIGRATIONS first
+// TODO: bump these when new revisions of the spec come out
+const CURRENT_VERSIONS: &[(&str, ID24JsonVersion)] = &[
+    ("gameconf",    ID24JsonVersion::new(1, 0, 0)),
+    ("demoloop",    ID24JsonVersion::new(1, 0, 0)),
+    ("statusbar",   ID24JsonVersion::new(1, 0, 0)),
+    ("skydefs",     ID24JsonVersion::new(1, 0, 0)),
+    ("trakinfo",    ID24JsonVersion::new(1, 0, 0)),
+    ("interlevel",  ID24JsonVersion::new(1, 0, 0)),
+    ("finale",      ID24JsonVersion::new(1, 0, 0)),
+    ("translation", ID24JsonVersion::new(1, 0, 0)),
+];
+
+// upgrades the data of a lump from one version to the next
+pub(super) struct Migration {
+    pub lump: &'static str,
+    pub from: ID24JsonVersion,
+    pub to: ID24JsonVersion,
+    pub apply: fn(&mut Value),
+}
+
+// no spec revisions have needed one yet
+// when one does, add a function that rewrites the old data into the new layout and list it here
+const MIGRATIONS: &[Migration] = &[];
+
+// every lump type in the table
+pub fn lumps() -> impl Iterator<Item = &'static str> {
+    CURRENT_VERSIONS.iter().map(|(name, _)| *name)
+}
+
+pub fn current(lump: &str) -> Option<ID24JsonVersion> {
+    CURRENT_VERSIONS.iter()
+        .find(|(name, _)| *name == lump)
+        .map(|(_, version)| *version)
+}
+
+// an error for versions that can't be read at all, a warning for ones newer than this editor knows about
+// revisions don't change the format, so only a newer minor version gets the warning
+pub(super) fn check(lump: &str, version: ID24JsonVersion) -> Option<Diagnostic> {
+    let current = current(lump)?;
+    if version.major != current.major {
+        Some(Diagnostic::new(
+            Rule::UnsupportedVersion,
+            "/version",
+            format!("{lump} version {version} is not supported, only {}.x versions can be read", current.major)
+        ))
+    } else if version.minor > current.minor {
+        Some(Diagnostic::new(
+            Rule::NewerVersion,
+            "/version",
+            format!("{lump} version {version} is newer than {current}, some fields may not be understood")
+        ))
+    } else {
+        None
+    }
+}
+
+// runs every migration from the lump's version onwards and updates the version to match
+// returns whether anything was changed
+pub(super) fn migrate(value: &mut Value) -> bool {
+    migrate_with(value, MIGRATIONS)
+}
+
+fn migrate_with(value: &mut Value, migrations: &[Migration]) -> bool {
+    let (Some(lump), Some(mut version)) = (
+        value.get("type").and_then(Value::as_str).map(str::to_owned),
+        value.get("version").cloned().and_then(|version| serde_json::from_value::<ID24JsonVersion>(version).ok())
+    ) else {
+        return false;
+    };
+    let mut migrated = false;
+    // every migration can only be needed once, so a table that loops back on itself stops there
+    for _ in 0..migrations.len() {
+        let Some(migration) = migrations.iter().find(|m| m.lump == lump && m.from == version) else {
+            break;
+        };
+        if let Some(data) = value.get_mut("data") {
+            (migration.apply)(data);
+        }
+        version = migration.to;
+        migrated = true;
+    }
+    if migrated {
+        value["version"] = Value::String(version.to_string());
+    }
+    migrated
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use super::super::ID24Json;
+    use super::super::parse::ParseMode;
+    use super::super::diagnostics::Severity;
+    #[test]
+    fn check_versions() {
+        assert_eq!(check("skydefs", ID24JsonVersion::new(1, 0, 0)), None);
+        assert_eq!(check("skydefs", ID24JsonVersion::new(2, 0, 0)).unwrap().rule, Rule::UnsupportedVersion);
+        assert_eq!(check("skydefs", ID24JsonVersion::new(0, 9, 0)).unwrap().rule, Rule::UnsupportedVersion);
+        assert_eq!(check("skydefs", ID24JsonVersion::new(1, 1, 0)).unwrap().rule, Rule::NewerVersion);
+        assert_eq!(check("skydefs", ID24JsonVersion::new(1, 0, 5)), None);
+        assert_eq!(check("not a lump", ID24JsonVersion::new(9, 0, 0)), None);
+    }
+    #[test]
+    fn migrations_chain() {
+        fn rename_skies(data: &mut Value) {
+            if let Some(skies) = data.as_object_mut().and_then(|data| data.remove("sky_list")) {
+                data["skies"] = skies;
+            }
+        }
+        fn add_flatmapping(data: &mut Value) {
+            data["flatmapping"] = Value::Null;
+        }
+        let migrations = [
+            Migration { lump: "skydefs", from: ID24JsonVersion::new(0, 8, 0), to: ID24JsonVersion::new(0, 9, 0), apply: rename_skies },
+            Migration { lump: "skydefs", from: ID24JsonVersion::new(0, 9, 0), to: ID24JsonVersion::new(1, 0, 0), apply: add_flatmapping },
+        ];
+        let mut value = serde_json::json!({
+            "type": "skydefs",
+            "version": "0.8.0",
+            "metadata": { },
+            "data": { "sky_list": null }
+        });
+        assert!(migrate_with(&mut value, &migrations));
+        assert_eq!(value["version"], "1.0.0");
+        assert_eq!(value["data"], serde_json::json!({ "skies": null, "flatmapping": null }));
+
+        // already current, nothing to do
+        assert!(!migrate_with(&mut value, &migrations));
+    }
+    #[test]
+    fn migration_cycle_stops() {
+        fn nothing(_: &mut Value) {}
+        let migrations = [
+            Migration { lump: "skydefs", from: ID24JsonVersion::new(0, 8, 0), to: ID24JsonVersion::new(0, 9, 0), apply: nothing },
+            Migration { lump: "skydefs", from: ID24JsonVersion::new(0, 9, 0), to: ID24JsonVersion::new(0, 8, 0), apply: nothing },
+        ];
+        let mut value = serde_json::json!({ "type": "skydefs", "version": "0.8.0", "metadata": { }, "data": { } });
+        assert!(migrate_with(&mut value, &migrations));
+        assert_eq!(value["version"], "0.8.0");
+    }
+    #[test]
+    fn parse_checks_version() {
+        let json = |version: &str| format!(r#"{{
+            "type": "demoloop",
+            "version": "{version}",
+            "metadata": {{ }},
+            "data": {{ "entries": [ {{ "primarylump": "DEMO1", "secondarylump": "", "duration": 0, "type": 1, "outrowwipe": 1 }} ] }}
+        }}"#);
+        let diagnostics = ID24Json::parse(&json("2.0.0"), ParseMode::Lenient).unwrap_err();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::UnsupportedVersion);
+
+        let lump = ID24Json::parse(&json("1.2.0"), ParseMode::Strict).unwrap();
+        let diagnostics = lump.validate();
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].rule, Rule::NewerVersion);
+        assert_eq!(diagnostics[0].severity, Severity::Warning);
+
+        assert!(ID24Json::parse(&json("1.0.0"), ParseMode::Strict).unwrap().validate().is_empty());
+    }
+}
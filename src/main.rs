@@ -14,15 +14,28 @@
 
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide the console window on Windows in release
 
-mod id24json;
 mod pages;
 mod widgets;
 mod config;
 
 use id24json::{ID24Json, ID24JsonData};
+use id24json::diagnostics::{self, Diagnostic};
+use id24json::parse::ParseMode;
+use id24json::references::{self as lump_references, KnownLumps, Lookup, ReferenceKind};
+use id24json::sbardef::{NumberFont, NumberFontGlyphs};
+use id24json::umapinfo::UMapInfo;
+use doomstruct::archive::{Archive, ArchiveType};
+use doomstruct::graphics::{self, Palette};
+use doomstruct::pk3::Namespace;
+use doomstruct::resources::{LumpLocation, Resolution, ResourceStack};
+use doomstruct::textures::TextureSet;
+use widgets::extra_fields;
 
 use std::fmt::{Display, Formatter};
 use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::time::Duration;
 use cosmic::{widget, Application};
 use cosmic::cosmic_config::{self, CosmicConfigEntry};
 use cosmic::widget::{menu, nav_bar};
@@ -31,6 +44,7 @@ use cosmic::iced::keyboard::{Key, Modifiers};
 use cosmic::iced::{event, keyboard, Length, Subscription};
 use cosmic::prelude::*;
 use cosmic::widget::menu::{Action, ItemWidth};
+use cosmic::app::context_drawer;
 use strum::IntoEnumIterator;
 
 // TODO: figure out if a context page is how the different editors should be shown
@@ -38,13 +52,15 @@ use strum::IntoEnumIterator;
 // TODO: figure out how to bundle icons on windows/mac
 // maybe make a pr to libcosmic for that
 
-// TODO: write rust port of MTrop's DoomStruct or something like it, existing options are unmaintained and too unfinished
-// focus first on the subset that will allow extracting the assets needed for more graphical editors and verifying that lumps referenced in json exist
+// doomstruct is the start of a rust port of MTrop's DoomStruct, existing options are unmaintained and too unfinished
+// TODO: focus first on the subset that will allow extracting the assets needed for more graphical editors
 
 // TODO: clean up module structure and imports, dont really want super long qualified names but need to avoid clashes too
 
-// TODO: add WAD/PK3 setting in settings page, in WAD mode force all lump fields to 8 characters max and uppercase
-// make it persist between sessions, but override it if the user loads from a WAD/PK3 (currently loading from json files directly is all that's supported)
+// TODO: lump names in UMAPINFO aren't held to the WAD/PK3 setting yet
+
+// written into the metadata of every saved lump
+const APPLICATION: &str = concat!("ID24 JSON Editor ", env!("CARGO_PKG_VERSION"));
 
 struct Flags {
     config: config::Config,
@@ -53,6 +69,20 @@ struct Flags {
 
 // TODO: add cli options for loading files and maybe doing a couple other things
 fn main() -> cosmic::iced::Result {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    if args.first().is_some_and(|arg| arg == "--check") {
+        let (mode, paths) = match args.get(1) {
+            Some(arg) if arg == "--strict" => (ParseMode::Strict, &args[2..]),
+            _ => (ParseMode::Lenient, &args[1..])
+        };
+        std::process::exit(check_files(paths, mode));
+    }
+    #[cfg(feature = "schema")]
+    if args.first().is_some_and(|arg| arg == "--schema") {
+        let dir = args.get(1).map_or(".", String::as_str);
+        std::process::exit(export_schemas(std::path::Path::new(dir)));
+    }
+
     let settings = cosmic::app::Settings::default();
     let (config, config_handler) = match cosmic_config::Config::new(EditorModel::APP_ID, config::Config::VERSION) {
         Ok(config_handler) => {
@@ -71,6 +101,62 @@ fn main() -> cosmic::iced::Result {
     cosmic::app::run::<EditorModel>(settings, flags)
 }
 
+// prints a full diagnostics report for every file, for use in CI
+// returns the exit code, which is non-zero if any file failed to parse or has errors
+fn check_files(paths: &[String], mode: ParseMode) -> i32 {
+    let mut exit_code = 0;
+    for path in paths {
+        let text = match std::fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(why) => {
+                println!("{path}: failed to read: {why}");
+                exit_code = 1;
+                continue;
+            }
+        };
+        let file_path = std::path::Path::new(path);
+        let diagnostics = if file_path.file_stem().is_some_and(|stem| stem.eq_ignore_ascii_case("umapinfo")) {
+            match UMapInfo::parse(&text) {
+                Ok(umapinfo) => lump_references::check_lumps(
+                    &umapinfo.references(),
+                    |name| read_sibling_lump(file_path, name)
+                ),
+                Err(why) => vec![Diagnostic::new(diagnostics::Rule::InvalidUMapInfo, "", why)],
+            }
+        } else {
+            match ID24Json::parse(&text, mode) {
+                Ok(json) => json.validate(),
+                Err(diagnostics) => diagnostics,
+            }
+        };
+        println!("{path}: {} problem(s)", diagnostics.len());
+        for diagnostic in &diagnostics {
+            println!("  {diagnostic}");
+        }
+        if diagnostics::has_errors(&diagnostics) {
+            exit_code = 1;
+        }
+    }
+    exit_code
+}
+
+// writes a JSON Schema for every lump type into dir, returns the exit code
+#[cfg(feature = "schema")]
+fn export_schemas(dir: &std::path::Path) -> i32 {
+    match id24json::schema::write_schemas(dir) {
+        Ok(paths) => {
+            for path in paths {
+                println!("wrote {}", path.display());
+            }
+            0
+        },
+        Err(why) => {
+            println!("{why}");
+            1
+        }
+    }
+}
+
 // TODO: consider using std::mem::discriminant instead of this
 #[derive(strum_macros::EnumIter, Debug, Clone, Copy, PartialEq, Eq, Hash)]
 enum LumpType {
@@ -80,19 +166,23 @@ enum LumpType {
     SKYDEFS,
     Interlevel,
     Finale,
-    TRAKINFO
+    TRAKINFO,
+    Translation,
+    UMAPINFO
 }
 
 impl Display for LumpType {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
-            LumpType::GAMECONF   => "GAMECONF",
-            LumpType::DEMOLOOP   => "DEMOLOOP",
-            LumpType::SBARDEF    => "SBARDEF",
-            LumpType::SKYDEFS    => "SKYDEFS",
-            LumpType::Interlevel => "Interlevel",
-            LumpType::Finale     => "Finale",
-            LumpType::TRAKINFO   => "TRAKINFO",
+            LumpType::GAMECONF    => "GAMECONF",
+            LumpType::DEMOLOOP    => "DEMOLOOP",
+            LumpType::SBARDEF     => "SBARDEF",
+            LumpType::SKYDEFS     => "SKYDEFS",
+            LumpType::Interlevel  => "Interlevel",
+            LumpType::Finale      => "Finale",
+            LumpType::TRAKINFO    => "TRAKINFO",
+            LumpType::Translation => "Translation",
+            LumpType::UMAPINFO    => "UMAPINFO",
         })
     }
 }
@@ -100,13 +190,14 @@ impl Display for LumpType {
 impl From<&ID24JsonData> for LumpType {
     fn from(data: &ID24JsonData) -> Self {
         match data {
-            ID24JsonData::GAMECONF   { .. } => LumpType::GAMECONF,
-            ID24JsonData::DEMOLOOP   { .. } => LumpType::DEMOLOOP,
-            ID24JsonData::SBARDEF    { .. } => LumpType::SBARDEF,
-            ID24JsonData::SKYDEFS    { .. } => LumpType::SKYDEFS,
-            ID24JsonData::Interlevel { .. } => LumpType::Interlevel,
-            ID24JsonData::Finale     { .. } => LumpType::Finale,
-            ID24JsonData::TRAKINFO   { .. } => LumpType::TRAKINFO,
+            ID24JsonData::GAMECONF    { .. } => LumpType::GAMECONF,
+            ID24JsonData::DEMOLOOP    { .. } => LumpType::DEMOLOOP,
+            ID24JsonData::SBARDEF     { .. } => LumpType::SBARDEF,
+            ID24JsonData::SKYDEFS     { .. } => LumpType::SKYDEFS,
+            ID24JsonData::Interlevel  { .. } => LumpType::Interlevel,
+            ID24JsonData::Finale      { .. } => LumpType::Finale,
+            ID24JsonData::TRAKINFO    { .. } => LumpType::TRAKINFO,
+            ID24JsonData::Translation { .. } => LumpType::Translation,
         }
     }
 }
@@ -121,6 +212,9 @@ enum MyMenuAction {
     Open,
     Save,
     SaveAs,
+    LoadResources,
+    #[cfg(feature = "schema")]
+    ExportSchemas,
     Quit
 }
 
@@ -132,6 +226,9 @@ impl menu::Action for MyMenuAction {
             MyMenuAction::Open   => Message::MenuOpen,
             MyMenuAction::Save   => Message::MenuSave,
             MyMenuAction::SaveAs => Message::MenuSaveAs,
+            MyMenuAction::LoadResources => Message::LoadResources,
+            #[cfg(feature = "schema")]
+            MyMenuAction::ExportSchemas => Message::MenuExportSchemas,
             MyMenuAction::Quit   => Message::Quit,
         }
     }
@@ -148,11 +245,95 @@ struct EditorModel {
     config_handler: Option<cosmic_config::Config>,
     error_status: Option<String>,
     current_file: Option<url::Url>,
+    // the lump being edited when current_file is a WAD or PK3, the full path for PK3s
+    current_lump: Option<String>,
     json: ID24Json,
+    // edited instead of json while the UMAPINFO page is active
+    umapinfo: UMapInfo,
+    // the text of the file json was loaded from, used so saving only changes what was edited
+    source: Option<String>,
+    diagnostics: Vec<Diagnostic>,
+    // shown after opening a WAD or PK3, until a lump is picked or it's cancelled
+    lump_picker: Option<LumpPicker>,
+    // lumps opened during this session, so references to them from other lumps can be checked
+    known_lumps: KnownLumps,
+    // the IWAD and PWADs from the GAMECONF that goes with the current file, and the current file if it's an archive
+    resources: ResourceStack,
+    // every wall texture defined in resources
+    textures: TextureSet,
+    // PLAYPAL's first palette, None until resources with one are loaded
+    palette: Option<Palette>,
+    // number font glyphs are decoded once rather than on every edit, until the resources change
+    number_fonts: Vec<(NumberFont, NumberFontGlyphs)>,
+    // None until resources have been loaded
+    loaded_from: Option<ResourceFiles>,
     // TODO: should these be optional and be None when not active?
     skydefs_page: pages::skydefs::Page,
+    sbardef_page: pages::sbardef::Page,
     gameconf_page: pages::gameconf::Page,
     demoloop_page: pages::demoloop::Page,
+    settings_page: pages::settings::Page,
+    metadata_page: pages::metadata::Page,
+    translation_page: pages::translation::Page,
+    finale_page: pages::finale::Page,
+    umapinfo_page: pages::umapinfo::Page,
+}
+
+// the lumps in a WAD or PK3 that can be edited
+#[derive(Debug, Clone)]
+struct LumpPicker {
+    url: url::Url,
+    archive: Arc<Archive>,
+    file_name: String,
+    // lump index and the lump's type
+    lumps: Vec<(usize, &'static str)>,
+}
+
+// what the resources are loaded from, they're only loaded again when this changes or a reload is asked for
+#[derive(Clone, PartialEq, Debug)]
+struct ResourceFiles {
+    iwad: Option<String>,
+    pwads: Vec<String>,
+    dirs: Vec<PathBuf>,
+    // the file being edited, when it's an archive
+    edited: Option<PathBuf>,
+}
+
+// everything read from the resources, IWADs have thousands of lumps so this is done off the UI thread
+#[derive(Clone, Debug)]
+struct LoadedResources {
+    files: ResourceFiles,
+    resources: ResourceStack,
+    textures: TextureSet,
+    palette: Option<Palette>,
+    // the translation lumps in the resources
+    translations: Vec<String>,
+    errors: Vec<String>,
+}
+
+impl LoadedResources {
+    fn load(files: ResourceFiles, mode: ParseMode) -> Self {
+        let (mut resources, mut errors) = ResourceStack::load(files.iwad.as_deref(), &files.pwads, &files.dirs);
+        // the file being edited goes on top, unless the GAMECONF already loads it
+        if let Some(path) = &files.edited
+            && !resources.resources().iter().any(|resource| &resource.path == path)
+            && let Err(why) = resources.open(path) {
+            errors.push(why);
+        }
+        let mut translations = Vec::new();
+        for resource in resources.resources() {
+            for (idx, type_name) in id24_lumps(&resource.archive, mode) {
+                if Some(type_name) == ReferenceKind::Translation.type_name()
+                    && let Some(name) = resource.archive.lump_name(idx) {
+                    translations.push(name.to_owned());
+                }
+            }
+        }
+        let (textures, texture_errors) = TextureSet::load(&resources);
+        errors.extend(texture_errors);
+        let palette = graphics::load_palette(&resources).ok();
+        Self { files, resources, textures, palette, translations, errors }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -160,17 +341,35 @@ enum Message {
     // TODO: split each editor into its own module with its own message type
     GameconfMessage(pages::gameconf::Message),
     SkydefsMessage(pages::skydefs::Message),
+    SbardefMessage(pages::sbardef::Message),
     DemoloopMessage(pages::demoloop::Message),
+    SettingsMessage(pages::settings::Message),
+    MetadataMessage(pages::metadata::Message),
+    TranslationMessage(pages::translation::Message),
+    FinaleMessage(pages::finale::Message),
+    UMapInfoMessage(pages::umapinfo::Message),
+    ToggleMetadata,
     InitJSON(LumpType),
-    LoadJSON(Box<ID24Json>),
+    LoadJSON(Box<ID24Json>, String),
+    LoadUMapInfo(Box<UMapInfo>),
+    PickLump(LumpPicker),
+    LoadResources,
+    ResourcesLoaded(Box<LoadedResources>),
+    OpenLump(usize),
+    CancelLumpPicker,
     CloseToast(widget::ToastId),
     MenuOpen,
     MenuSave,
     MenuSaveAs,
+    #[cfg(feature = "schema")]
+    MenuExportSchemas,
     Open(url::Url),
     Save(url::Url),
+    #[cfg(feature = "schema")]
+    ExportSchemas(url::Url),
     Quit,
     CloseError,
+    JumpTo(String),
     Error(String),
     ErrorConsole(String),
     Key(Modifiers, Key),
@@ -183,6 +382,12 @@ impl From<pages::skydefs::Message> for Message {
     }
 }
 
+impl From<pages::sbardef::Message> for Message {
+    fn from(message: pages::sbardef::Message) -> Self {
+        Message::SbardefMessage(message)
+    }
+}
+
 impl From<pages::gameconf::Message> for Message {
     fn from(message: pages::gameconf::Message) -> Self {
         Message::GameconfMessage(message)
@@ -195,6 +400,279 @@ impl From<pages::demoloop::Message> for Message {
     }
 }
 
+impl From<pages::settings::Message> for Message {
+    fn from(message: pages::settings::Message) -> Self {
+        Message::SettingsMessage(message)
+    }
+}
+
+impl From<pages::metadata::Message> for Message {
+    fn from(message: pages::metadata::Message) -> Self {
+        Message::MetadataMessage(message)
+    }
+}
+
+impl From<pages::translation::Message> for Message {
+    fn from(message: pages::translation::Message) -> Self {
+        Message::TranslationMessage(message)
+    }
+}
+
+impl From<pages::finale::Message> for Message {
+    fn from(message: pages::finale::Message) -> Self {
+        Message::FinaleMessage(message)
+    }
+}
+
+impl From<pages::umapinfo::Message> for Message {
+    fn from(message: pages::umapinfo::Message) -> Self {
+        Message::UMapInfoMessage(message)
+    }
+}
+
+// lumps are referenced by name, which for a loose json file is its file name
+fn lump_name(url: &url::Url) -> Option<String> {
+    let file_name = url.path_segments()?.next_back()?;
+    let stem = file_name.rsplit_once('.').map_or(file_name, |(stem, _)| stem);
+    (!stem.is_empty()).then(|| stem.to_uppercase())
+}
+
+fn archive_type(url: &url::Url) -> Option<ArchiveType> {
+    ArchiveType::from_path(std::path::Path::new(url.path()))
+}
+
+// the archive type from the settings comes first
+fn archive_filters(file_type: &config::FileType) -> [cosmic::dialog::file_chooser::FileFilter; 2] {
+    use cosmic::dialog::file_chooser::FileFilter;
+    let wad = FileFilter::new("WAD Files").extension("wad");
+    let pk3 = FileFilter::new("PK3 Files").extension("pk3").extension("zip");
+    match file_type {
+        config::FileType::Wad => [wad, pk3],
+        config::FileType::Zip => [pk3, wad],
+    }
+}
+
+// every lump in the archive that parses as ID24 JSON, the rest can't be edited here
+fn id24_lumps(archive: &Archive, mode: ParseMode) -> Vec<(usize, &'static str)> {
+    (0..archive.len())
+        .filter_map(|idx| {
+            let data = archive.lump_data(idx)?;
+            // skip everything that obviously isn't json before looking any further, IWADs have thousands of lumps
+            if data.iter().find(|byte| !byte.is_ascii_whitespace()) != Some(&b'{') {
+                return None;
+            }
+            let text = std::str::from_utf8(&data).ok()?;
+            let json = ID24Json::parse(text, mode).ok()?;
+            Some((idx, json.data.type_name()))
+        })
+        .collect()
+}
+
+fn is_umapinfo(url: &url::Url) -> bool {
+    lump_name(url).as_deref() == Some("UMAPINFO")
+}
+
+// loose lumps are looked up next to the file being edited, e.g. INTRMAP1.json next to UMAPINFO.txt
+fn read_sibling_lump(file: &std::path::Path, name: &str) -> Option<String> {
+    std::fs::read_dir(file.parent()?).ok()?
+        .flatten()
+        .map(|entry| entry.path())
+        .find(|path| path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("json"))
+            && path.file_stem().is_some_and(|stem| stem.eq_ignore_ascii_case(name)))
+        .and_then(|path| std::fs::read_to_string(path).ok())
+}
+
+impl EditorModel {
+    fn editing_umapinfo(&self) -> bool {
+        self.nav.active_data::<LumpType>() == Some(&LumpType::UMAPINFO)
+    }
+
+    // diagnostics can only be jumped to on pages that have something to select
+    fn can_jump(&self) -> bool {
+        self.editing_umapinfo() || matches!(
+            LumpType::from(&self.json.data),
+            LumpType::SKYDEFS | LumpType::DEMOLOOP | LumpType::SBARDEF | LumpType::Finale
+        )
+    }
+
+    // the name other lumps refer to this one by
+    fn current_lump_name(&self) -> Option<String> {
+        let file = self.current_file.as_ref()?;
+        match archive_type(file) {
+            Some(ArchiveType::Wad) => self.current_lump.clone(),
+            Some(ArchiveType::Pk3) => self.current_lump.as_deref().map(|path| doomstruct::pk3::short_name(path).to_uppercase()),
+            None => lump_name(file)
+        }
+    }
+
+    // the settings decide unless the lump came out of an archive, then that archive's rules apply
+    fn file_type(&self) -> config::FileType {
+        match self.current_file.as_ref().and_then(archive_type) {
+            Some(ArchiveType::Wad) => config::FileType::Wad,
+            Some(ArchiveType::Pk3) => config::FileType::Zip,
+            None => self.config.file_type
+        }
+    }
+
+    // the GAMECONF being edited, otherwise the one in the same archive or folder as the current file
+    fn resource_files(&self) -> (Option<String>, Vec<String>) {
+        let gameconf = if !self.editing_umapinfo() && matches!(self.json.data, ID24JsonData::GAMECONF { .. }) {
+            Some(self.json.data.clone())
+        } else {
+            self.current_file.as_ref().and_then(|url| {
+                let path = url.to_file_path().ok()?;
+                let text = if archive_type(url).is_some() {
+                    String::from_utf8(Archive::open(&path).ok()?.read("GAMECONF")?.into_owned()).ok()?
+                } else {
+                    read_sibling_lump(&path, "GAMECONF")?
+                };
+                ID24Json::parse(&text, ParseMode::Lenient).ok().map(|json| json.data)
+            })
+        };
+        match gameconf {
+            Some(ID24JsonData::GAMECONF { iwad, pwadfiles, .. }) => (iwad, pwadfiles.unwrap_or_default()),
+            _ => (None, Vec::new())
+        }
+    }
+
+    // GAMECONF only has file names, they're looked for next to the current file, then where source ports look
+    fn resource_dirs(&self) -> Vec<PathBuf> {
+        let mut dirs: Vec<PathBuf> = self.current_file.as_ref()
+            .and_then(|url| url.to_file_path().ok())
+            .and_then(|path| path.parent().map(ToOwned::to_owned))
+            .into_iter()
+            .collect();
+        dirs.extend(std::env::var_os("DOOMWADDIR").map(PathBuf::from));
+        if let Some(paths) = std::env::var_os("DOOMWADPATH") {
+            dirs.extend(std::env::split_paths(&paths));
+        }
+        dirs
+    }
+
+    // the lump written when saving into an archive, new lumps get the name the game looks for
+    // in a PK3 that's a json file in the root
+    // the open lump's name is only kept when it's going into the same kind of archive, WAD names aren't PK3 paths
+    fn archive_lump_name(&self, archive_type: ArchiveType) -> Option<String> {
+        if let Some(name) = &self.current_lump
+            && self.current_file.as_ref().and_then(crate::archive_type) == Some(archive_type) {
+            return Some(name.clone());
+        }
+        let name = if self.editing_umapinfo() { "UMAPINFO" } else { self.json.data.lump_name()? };
+        Some(match archive_type {
+            ArchiveType::Wad => name.to_owned(),
+            ArchiveType::Pk3 if self.editing_umapinfo() => format!("{}.txt", name.to_lowercase()),
+            ArchiveType::Pk3 => format!("{}.json", name.to_lowercase()),
+        })
+    }
+
+    // opening another lump only loads the resources again when they'd come from different files
+    fn load_resources(&mut self, reload: bool) -> cosmic::Task<cosmic::Action<Message>> {
+        let (iwad, pwads) = self.resource_files();
+        let files = ResourceFiles {
+            iwad,
+            pwads,
+            dirs: self.resource_dirs(),
+            edited: self.current_file.as_ref()
+                .filter(|url| archive_type(url).is_some())
+                .and_then(|url| url.to_file_path().ok())
+        };
+        // the lump is checked against what's already loaded until the new resources are ready
+        self.refresh_sky_preview();
+        self.refresh_statusbar_preview();
+        self.revalidate();
+        if !reload && self.loaded_from.as_ref() == Some(&files) {
+            return Task::none();
+        }
+        self.loaded_from = Some(files.clone());
+        let mode = self.config.parse_mode;
+        cosmic::task::future(async move {
+            Message::ResourcesLoaded(Box::new(LoadedResources::load(files, mode)))
+        })
+    }
+
+    fn revalidate(&mut self) {
+        if self.editing_umapinfo() {
+            let file = self.current_file.as_ref().and_then(|url| url.to_file_path().ok());
+            self.diagnostics = lump_references::check_lumps(
+                &self.umapinfo.references(),
+                |name| file.as_deref()
+                    .and_then(|file| read_sibling_lump(file, name))
+                    .or_else(|| String::from_utf8(self.resources.read(name)?.into_owned()).ok())
+            );
+            return;
+        }
+        self.diagnostics = self.json.validate();
+        self.diagnostics.extend(self.json.validate_references(&self.known_lumps));
+        if self.file_type() == config::FileType::Wad {
+            self.diagnostics.extend(self.json.validate_wad_lump_names());
+        }
+        if self.resources.has_iwad() {
+            self.diagnostics.extend(self.json.check_lump_names(|kind, name| self.lookup_lump(kind, name)));
+        }
+        // glyphs have to be decoded to be found, so this waits for a PLAYPAL like the preview does
+        if let ID24JsonData::SBARDEF { numberfonts, statusbars, .. } = &self.json.data
+            && let Some(palette) = &self.palette {
+            self.number_fonts.retain(|(cached, _)| numberfonts.contains(cached));
+            for font in numberfonts {
+                if !self.number_fonts.iter().any(|(cached, _)| cached == font) {
+                    let glyphs = font.load(|name| graphics::load_graphic(&self.resources, name, palette).ok());
+                    self.number_fonts.push((font.clone(), glyphs));
+                }
+            }
+            let number_fonts = &self.number_fonts;
+            self.diagnostics.extend(id24json::sbardef::check_number_fonts(
+                numberfonts, statusbars, "/data",
+                |font| number_fonts.iter().find(|(cached, _)| cached == font).map(|(_, glyphs)| glyphs)
+            ));
+        }
+    }
+
+    // renders whatever textures the selected sky now uses
+    fn refresh_sky_preview(&mut self) {
+        let (resources, textures, palette) = (&self.resources, &self.textures, self.palette.as_ref());
+        self.skydefs_page.refresh_preview(
+            &self.json,
+            |name| textures.render(name, resources, palette?).ok()
+        );
+    }
+
+    // only drawn once there's a PLAYPAL to draw with
+    fn refresh_statusbar_preview(&mut self) {
+        let Some(palette) = &self.palette else {
+            return;
+        };
+        let resources = &self.resources;
+        self.sbardef_page.refresh_preview(
+            &self.json,
+            |name| graphics::load_graphic(resources, name, palette).ok(),
+            |name| graphics::load_flat(resources, name, palette).ok()
+        );
+    }
+
+    // how a referenced name resolves in the loaded resources
+    fn lookup_lump(&self, kind: ReferenceKind, name: &str) -> Lookup {
+        // textures are made up from patches, they're only lumps in a PK3's textures folder
+        if kind == ReferenceKind::Texture && self.textures.find(name).is_some() {
+            return Lookup::Found;
+        }
+        let namespace = match kind {
+            ReferenceKind::Graphic | ReferenceKind::NumberFont | ReferenceKind::Prefix => Namespace::Graphics,
+            ReferenceKind::Flat => Namespace::Flats,
+            ReferenceKind::Music => Namespace::Music,
+            ReferenceKind::Texture => Namespace::Textures,
+            _ => Namespace::Global
+        };
+        let is_wad = |location: LumpLocation| self.resources.resources()[location.resource].archive.archive_type() == ArchiveType::Wad;
+        match self.resources.resolve(name, namespace) {
+            Resolution::Found(location) | Resolution::CaseMismatch(location, _)
+                if kind == ReferenceKind::Texture && is_wad(location) => Lookup::Missing,
+            Resolution::Found(_) => Lookup::Found,
+            Resolution::CaseMismatch(_, name) => Lookup::CaseMismatch(name),
+            Resolution::Missing => Lookup::Missing
+        }
+    }
+}
+
 fn convert_action_message<M, N: From<M>>(action: cosmic::Action<M>) -> cosmic::Action<N> {
     match action {
         cosmic::Action::None => cosmic::Action::None,
@@ -246,10 +724,27 @@ impl cosmic::Application for EditorModel {
             config_handler: flags.config_handler,
             error_status: None,
             current_file: None,
+            current_lump: None,
             json: ID24Json::default(),
+            umapinfo: UMapInfo::default(),
+            source: None,
+            diagnostics: Vec::new(),
+            lump_picker: None,
+            known_lumps: KnownLumps::default(),
+            resources: ResourceStack::new(),
+            textures: TextureSet::default(),
+            palette: None,
+            number_fonts: Vec::new(),
+            loaded_from: None,
             gameconf_page: pages::gameconf::Page::default(),
             skydefs_page: pages::skydefs::Page::default(),
+            sbardef_page: pages::sbardef::Page::default(),
             demoloop_page: pages::demoloop::Page::default(),
+            settings_page: pages::settings::Page,
+            metadata_page: pages::metadata::Page,
+            translation_page: pages::translation::Page::default(),
+            finale_page: pages::finale::Page::default(),
+            umapinfo_page: pages::umapinfo::Page::default(),
         };
         app.set_header_title("ID24 JSON Editor".to_owned());
         let command = app.set_window_title("ID24 JSON Editor".to_owned());
@@ -267,6 +762,9 @@ impl cosmic::Application for EditorModel {
                     menu::Item::Button("Open", None, MyMenuAction::Open),
                     menu::Item::Button("Save", None, MyMenuAction::Save),
                     menu::Item::Button("Save As", None, MyMenuAction::SaveAs),
+                    menu::Item::Button("Reload Resources", None, MyMenuAction::LoadResources),
+                    #[cfg(feature = "schema")]
+                    menu::Item::Button("Export Schemas", None, MyMenuAction::ExportSchemas),
                     menu::Item::Button("Quit", None, MyMenuAction::Quit)
                 ],
             ),
@@ -275,6 +773,38 @@ impl cosmic::Application for EditorModel {
         vec![menu_bar.into()]
     }
 
+    fn header_end(&self) -> Vec<Element<'_, Self::Message>> {
+        vec![widget::button::text("Metadata").on_press(Message::ToggleMetadata).into()]
+    }
+
+    // metadata is shared by every lump type, so it lives in the drawer instead of on each page
+    fn context_drawer(&self) -> Option<context_drawer::ContextDrawer<'_, Self::Message>> {
+        if !self.core.window.show_context {
+            return None;
+        }
+        Some(context_drawer::context_drawer(
+            self.metadata_page.view(&self.json).map(Message::MetadataMessage),
+            Message::ToggleMetadata
+        ).title("Metadata"))
+    }
+
+    fn dialog(&self) -> Option<Element<'_, Self::Message>> {
+        let picker = self.lump_picker.as_ref()?;
+        let lumps_list = picker.lumps.iter().fold(
+            widget::list_column(),
+            |acc, (idx, type_name)|
+                acc.add(widget::button::text(format!("{} ({type_name})", picker.archive.name(*idx).unwrap_or_default()))
+                    .on_press(Message::OpenLump(*idx))
+                    .width(Length::Fill)
+                    .class(widget::button::ButtonClass::Text))
+        );
+        Some(widget::dialog()
+            .title(format!("Open a lump from {}", picker.file_name))
+            .control(widget::container(widget::scrollable(lumps_list)).max_height(400.0))
+            .secondary_action(widget::button::standard("Cancel").on_press(Message::CancelLumpPicker))
+            .into())
+    }
+
     fn nav_model(&self) -> Option<&nav_bar::Model> {
         Some(&self.nav)
     }
@@ -290,7 +820,7 @@ impl cosmic::Application for EditorModel {
     }
 
     fn subscription(&self) -> Subscription<Self::Message> {
-        event::listen_with(|event, status, _window_id| match event {
+        let keys = event::listen_with(|event, status, _window_id| match event {
             event::Event::Keyboard(keyboard::Event::KeyPressed { modifiers, key, .. }) => {
                 match status {
                     event::Status::Ignored => Some(Message::Key(modifiers, key)),
@@ -298,7 +828,23 @@ impl cosmic::Application for EditorModel {
                 }
             }
             _ => None,
-        })
+        });
+        // redraw scrolling and burning skies at the game's 35 tics a second, but only while they're on screen
+        if self.nav.active_data::<LumpType>() == Some(&LumpType::SKYDEFS) && self.skydefs_page.animating(&self.json) {
+            Subscription::batch([
+                keys,
+                cosmic::iced::time::every(Duration::from_millis(1000 / 35))
+                    .map(|now| Message::SkydefsMessage(pages::skydefs::Message::Tick(now)))
+            ])
+        } else if self.nav.active_data::<LumpType>() == Some(&LumpType::SBARDEF) && self.sbardef_page.animating(&self.json) {
+            Subscription::batch([
+                keys,
+                cosmic::iced::time::every(Duration::from_millis(1000 / 35))
+                    .map(|now| Message::SbardefMessage(pages::sbardef::Message::Tick(now)))
+            ])
+        } else {
+            keys
+        }
     }
 
     #[allow(clippy::too_many_lines)]
@@ -306,11 +852,16 @@ impl cosmic::Application for EditorModel {
     fn update(&mut self, message: Self::Message) -> cosmic::Task<cosmic::Action<Self::Message>> {
         match message {
             Message::MenuOpen => {
+                let [archive_filter, other_archive_filter] = archive_filters(&self.config.file_type);
                 return cosmic::task::future(async {
                     use cosmic::dialog::file_chooser;
                     let filter = file_chooser::FileFilter::new("JSON Files").extension("json");
+                    let umapinfo_filter = file_chooser::FileFilter::new("UMAPINFO").glob("UMAPINFO*").glob("umapinfo*");
                     let dialog = file_chooser::open::Dialog::new()
-                        .filter(filter);
+                        .filter(filter)
+                        .filter(archive_filter)
+                        .filter(other_archive_filter)
+                        .filter(umapinfo_filter);
                     match dialog.open_file().await {
                         Ok(response) => Message::Open(response.url().to_owned()),
                         Err(file_chooser::Error::Cancelled) => Message::ErrorConsole("File dialog closed".to_owned()),
@@ -326,11 +877,14 @@ impl cosmic::Application for EditorModel {
             },
             Message::MenuSaveAs => {
                 // TODO: make this change the current file for saving
+                let [archive_filter, other_archive_filter] = archive_filters(&self.config.file_type);
                 return cosmic::task::future(async {
                     use cosmic::dialog::file_chooser;
                     let filter = file_chooser::FileFilter::new("JSON Files").extension("json");
                     let dialog = file_chooser::save::Dialog::new()
-                        .filter(filter);
+                        .filter(filter)
+                        .filter(archive_filter)
+                        .filter(other_archive_filter);
                     match dialog.save_file().await {
                         Ok(response) => match response.url() {
                             Some(url) => Message::Save(url.to_owned()),
@@ -341,8 +895,40 @@ impl cosmic::Application for EditorModel {
                     }
                 });
             },
+            #[cfg(feature = "schema")]
+            Message::MenuExportSchemas => {
+                return cosmic::task::future(async {
+                    use cosmic::dialog::file_chooser;
+                    let dialog = file_chooser::open::Dialog::new()
+                        .title("Export Schemas");
+                    match dialog.open_folder().await {
+                        Ok(response) => Message::ExportSchemas(response.url().to_owned()),
+                        Err(file_chooser::Error::Cancelled) => Message::ErrorConsole("Folder dialog closed".to_owned()),
+                        Err(why) => Message::Error(why.to_string()),
+                    }
+                });
+            },
+            #[cfg(feature = "schema")]
+            Message::ExportSchemas(url) => {
+                let Ok(dir) = url.to_file_path() else {
+                    return self.update(Message::Error(format!("{url} is not a local folder")));
+                };
+                match id24json::schema::write_schemas(&dir) {
+                    Ok(paths) => {
+                        return self.toasts
+                            .push(widget::toaster::Toast::new(format!("Exported {} schemas to {}", paths.len(), dir.display())))
+                            .map(cosmic::Action::App);
+                    },
+                    Err(why) => return self.update(Message::Error(why)),
+                }
+            },
             Message::Open(url) => {
-                self.current_file = Some(url.clone());
+                // an archive isn't the file being edited, the lump picked from it is
+                if archive_type(&url).is_none() {
+                    self.current_file = Some(url.clone());
+                    self.current_lump = None;
+                }
+                let mode = self.config.parse_mode;
                 // TODO: async doesnt do anything here, just a remnant from when using tokio, which was incompatible with serde
                 return cosmic::task::future(async move {
                     let path = match url.scheme() {
@@ -352,8 +938,25 @@ impl cosmic::Application for EditorModel {
                         }
                     };
 
-                    let mut file = match std::fs::File::open(&path) {
-                        Ok(file) => file,
+                    if archive_type(&url).is_some() {
+                        let archive = match Archive::open(&path) {
+                            Ok(archive) => archive,
+                            Err(why) => return Message::Error(why),
+                        };
+                        let lumps = id24_lumps(&archive, mode);
+                        if lumps.is_empty() {
+                            return Message::Error(format!("{} has no ID24 JSON lumps", path.display()));
+                        }
+                        return Message::PickLump(LumpPicker {
+                            url,
+                            archive: Arc::new(archive),
+                            file_name: path.file_name().map(|name| name.to_string_lossy().into_owned()).unwrap_or_default(),
+                            lumps
+                        });
+                    }
+
+                    let text = match std::fs::read_to_string(&path) {
+                        Ok(text) => text,
                         Err(why) => {
                             return Message::Error(format!(
                                 "failed to open {}: {why}",
@@ -362,23 +965,61 @@ impl cosmic::Application for EditorModel {
                         }
                     };
 
-                    let json = match serde_json::from_reader(&mut file) {
+                    if is_umapinfo(&url) {
+                        return match UMapInfo::parse(&text) {
+                            Ok(umapinfo) => Message::LoadUMapInfo(Box::new(umapinfo)),
+                            Err(why) => Message::Error(format!("Failed to parse UMAPINFO: {why}")),
+                        };
+                    }
+
+                    let json = match ID24Json::parse(&text, mode) {
                         Ok(json) => json,
-                        Err(why) => return Message::Error(format!("Failed to parse JSON: {why}")),
+                        Err(diagnostics) => return Message::Error(format!(
+                            "Failed to parse JSON: {}",
+                            diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+                        )),
                     };
 
-                    Message::LoadJSON(json)
+                    Message::LoadJSON(Box::new(json), text)
                 });
             },
             Message::Save(url) => {
-                // TODO: probably should stop the user from doing invalid things sooner
-                if let Err(why) = self.json.data.verify() {
-                    // TODO: this should probably be a popup that is required to be dismissed
-                    return self.update(Message::Error(format!("Failed to verify JSON: {why}")));
-                }
+                // missing lumps don't stop UMAPINFO from being saved, they might just not be written yet
+                let text = if self.editing_umapinfo() {
+                    self.umapinfo.to_string()
+                } else {
+                    if self.config.normalise_on_save {
+                        self.json.normalise();
+                    }
+                    // TODO: probably should stop the user from doing invalid things sooner
+                    self.revalidate();
+                    if diagnostics::has_errors(&self.diagnostics) {
+                        // TODO: this should probably be a popup that is required to be dismissed
+                        return self.update(Message::Error(format!(
+                            "Failed to verify JSON: {} problem(s) found, see the list below",
+                            self.diagnostics.len()
+                        )));
+                    }
+                    self.json.touch(APPLICATION);
+                    match self.json.to_string_preserving(self.source.as_deref()) {
+                        Ok(text) => text,
+                        Err(why) => return self.update(Message::Error(format!("Failed to write JSON: {why}"))),
+                    }
+                };
+                let archive_lump = match archive_type(&url) {
+                    Some(archive_type) => match self.archive_lump_name(archive_type) {
+                        Some(name) => Some(name),
+                        None => return self.update(Message::Error(format!(
+                            "{} lumps don't have a standard name, open one from the archive to replace it",
+                            LumpType::from(&self.json.data)
+                        ))),
+                    },
+                    None => None
+                };
                 // TODO: maybe move this into Save As somehow, dont need to be setting it every time we save
                 // and/or make a message just for this. would need to figure out how to send multiple messages from Open
                 self.current_file = Some(url.clone());
+                self.current_lump.clone_from(&archive_lump);
                 // TODO: do this properly without the dummy message
                 let message = || {
                     let path = match url.scheme() {
@@ -388,47 +1029,197 @@ impl cosmic::Application for EditorModel {
                         }
                     };
 
-                    let mut file = match std::fs::File::create(&path) {
-                        Ok(file) => file,
-                        Err(why) => {
-                            return Message::Error(format!(
-                                "failed to create {}: {why}",
-                                path.display()
-                            ));
-                        }
-                    };
+                    // every other lump in the archive is written back untouched, saving as a new WAD makes a PWAD
+                    if let Some(name) = &archive_lump {
+                        let archive = if path.exists() { Archive::open(&path) } else { Archive::new(&path) };
+                        let result = archive
+                            .and_then(|mut archive| {
+                                archive.set(name, text.clone().into_bytes())?;
+                                archive.save(&path)
+                            });
+                        return match result {
+                            Ok(()) => Message::Dummy,
+                            Err(why) => Message::Error(why),
+                        };
+                    }
 
-                    if let Err(why) = serde_json::to_writer_pretty(&mut file, &self.json) {
-                        return Message::Error(format!("Failed to write JSON: {why}"));
-                    };
+                    if let Err(why) = std::fs::write(&path, &text) {
+                        return Message::Error(format!(
+                            "failed to write {}: {why}",
+                            path.display()
+                        ));
+                    }
 
                     Message::Dummy
                 };
-                return self.update(message());
+                let message = message();
+                if matches!(message, Message::Dummy) && !self.editing_umapinfo() {
+                    self.source = Some(text);
+                }
+                return self.update(message);
             },
             Message::InitJSON(lump) => {
                 self.skydefs_page.reset_index();
+                self.source = None;
+                self.current_lump = None;
                 match lump {
-                    LumpType::GAMECONF => self.json.data = ID24JsonData::gameconf(),
-                    LumpType::SKYDEFS => self.json.data = ID24JsonData::skydefs(),
-                    LumpType::DEMOLOOP => self.json.data = ID24JsonData::demoloop(),
+                    LumpType::GAMECONF => self.json = ID24Json::new(ID24JsonData::gameconf()),
+                    LumpType::SKYDEFS => self.json = ID24Json::new(ID24JsonData::skydefs()),
+                    LumpType::DEMOLOOP => self.json = ID24Json::new(ID24JsonData::demoloop()),
+                    LumpType::Finale => {
+                        self.json = ID24Json::new(ID24JsonData::finale());
+                        self.finale_page.reset_index();
+                    },
+                    LumpType::TRAKINFO => self.json = ID24Json::new(ID24JsonData::trakinfo()),
+                    LumpType::Translation => self.json = ID24Json::new(ID24JsonData::translation()),
+                    LumpType::UMAPINFO => {
+                        self.umapinfo = UMapInfo::default();
+                        self.umapinfo_page.reset_index();
+                    },
                     _ => ()
                 }
+                self.refresh_statusbar_preview();
+                self.revalidate();
             },
-            Message::LoadJSON(json) => {
+            Message::LoadJSON(json, source) => {
                 self.skydefs_page.reset_index();
+                self.finale_page.reset_index();
                 self.json = *json;
+                self.source = Some(source);
+                if matches!(self.json.data, ID24JsonData::Translation { .. })
+                    && let Some(name) = self.current_lump_name() {
+                    self.known_lumps.insert(ReferenceKind::Translation, &name);
+                }
                 // TODO: figure out a nicer way to do this
                 self.nav.activate(*self.nav_ids.get(&(&self.json.data).into()).unwrap());
+                return self.load_resources(false);
+            },
+            Message::LoadResources => return self.load_resources(true),
+            Message::ResourcesLoaded(loaded) => {
+                // other files were asked for while these were loading
+                if self.loaded_from.as_ref() != Some(&loaded.files) {
+                    return Task::none();
+                }
+                let LoadedResources { resources, textures, palette, translations, errors, .. } = *loaded;
+                for name in &translations {
+                    self.known_lumps.insert(ReferenceKind::Translation, name);
+                }
+                self.resources = resources;
+                self.textures = textures;
+                self.palette = palette;
+                self.skydefs_page.clear_preview();
+                self.refresh_sky_preview();
+                self.sbardef_page.clear_preview();
+                self.number_fonts.clear();
+                self.refresh_statusbar_preview();
+                self.revalidate();
+                if !errors.is_empty() {
+                    return self.update(Message::Error(format!("Failed to load resources: {}", errors.join(", "))));
+                }
+            },
+            Message::PickLump(picker) => {
+                // translations in the archive can be referenced by whatever gets picked
+                for (idx, type_name) in &picker.lumps {
+                    if Some(*type_name) == ReferenceKind::Translation.type_name()
+                        && let Some(name) = picker.archive.lump_name(*idx) {
+                        self.known_lumps.insert(ReferenceKind::Translation, name);
+                    }
+                }
+                self.lump_picker = Some(picker);
+            },
+            Message::CancelLumpPicker => self.lump_picker = None,
+            Message::OpenLump(idx) => {
+                let Some(picker) = self.lump_picker.take() else {
+                    return Task::none();
+                };
+                let Some(text) = picker.archive.lump_data(idx).and_then(|data| String::from_utf8(data.into_owned()).ok()) else {
+                    return self.update(Message::Error(format!("Lump {idx} in {} isn't text", picker.file_name)));
+                };
+                match ID24Json::parse(&text, self.config.parse_mode) {
+                    Ok(json) => {
+                        self.current_file = Some(picker.url);
+                        self.current_lump = picker.archive.name(idx).map(ToOwned::to_owned);
+                        return self.update(Message::LoadJSON(Box::new(json), text));
+                    },
+                    Err(diagnostics) => return self.update(Message::Error(format!(
+                        "Failed to parse JSON: {}",
+                        diagnostics.iter().map(ToString::to_string).collect::<Vec<_>>().join("\n")
+                    ))),
+                }
+            },
+            Message::LoadUMapInfo(umapinfo) => {
+                self.umapinfo = *umapinfo;
+                self.umapinfo_page.reset_index();
+                self.nav.activate(self.nav_ids[&LumpType::UMAPINFO]);
+                return self.load_resources(false);
+            },
+            Message::UMapInfoMessage(message) => {
+                let task = self.umapinfo_page.update(&mut self.umapinfo, message).map(convert_action_message);
+                self.revalidate();
+                return task;
             },
             Message::SkydefsMessage(message) => {
-                return self.skydefs_page.update(&mut self.json, message).map(convert_action_message);
+                // ticks only move the preview along, nothing to check
+                let tick = matches!(message, pages::skydefs::Message::Tick(_));
+                let task = self.skydefs_page.update(&mut self.json, message).map(convert_action_message);
+                if tick {
+                    return task;
+                }
+                self.refresh_sky_preview();
+                self.revalidate();
+                return task;
+            },
+            Message::SbardefMessage(message) => {
+                // nothing in the json changes, the preview just has to catch up
+                let task = self.sbardef_page.update(message).map(convert_action_message);
+                self.refresh_statusbar_preview();
+                return task;
             },
             Message::GameconfMessage(message) => {
-                return self.gameconf_page.update(&mut self.json, message).map(convert_action_message);
+                let task = self.gameconf_page.update(&mut self.json, message).map(convert_action_message);
+                self.revalidate();
+                return task;
             },
             Message::DemoloopMessage(message) => {
-                return self.demoloop_page.update(&mut self.json, message).map(convert_action_message);
+                let task = self.demoloop_page.update(&mut self.json, message).map(convert_action_message);
+                self.revalidate();
+                return task;
+            },
+            Message::TranslationMessage(message) => {
+                let task = self.translation_page.update(&mut self.json, message).map(convert_action_message);
+                self.revalidate();
+                return task;
+            },
+            Message::FinaleMessage(message) => {
+                return self.finale_page.update(message).map(convert_action_message);
+            },
+            Message::MetadataMessage(message) => {
+                let task = self.metadata_page.update(&mut self.json, message).map(convert_action_message);
+                self.revalidate();
+                return task;
+            },
+            Message::ToggleMetadata => {
+                self.core.window.show_context = !self.core.window.show_context;
+            },
+            Message::SettingsMessage(message) => {
+                let task = self.settings_page
+                    .update(&mut self.config, self.config_handler.as_ref(), message)
+                    .map(convert_action_message);
+                self.revalidate();
+                return task;
+            },
+            Message::JumpTo(path) => {
+                if self.editing_umapinfo() {
+                    self.umapinfo_page.focus(&self.umapinfo, &path);
+                    return Task::none();
+                }
+                match LumpType::from(&self.json.data) {
+                    LumpType::SKYDEFS => self.skydefs_page.focus(&path),
+                    LumpType::DEMOLOOP => self.demoloop_page.focus(&path),
+                    LumpType::SBARDEF => self.sbardef_page.focus(&path),
+                    LumpType::Finale => self.finale_page.focus(&path),
+                    _ => ()
+                }
             },
             Message::Key(modifiers, key) => {
                 for (key_bind, action) in &self.key_binds {
@@ -453,14 +1244,29 @@ impl cosmic::Application for EditorModel {
             content.push(widget::warning(e).on_close(Message::CloseError).into());
         }
         let main_content: Element<Self::Message> = match self.nav.active_data() {
+            _ if self.nav.active_data::<Page>() == Some(&Page::Settings) => {
+                self.settings_page.view(&self.config).map(Message::SettingsMessage)
+            },
             Some(LumpType::GAMECONF) => {
                 self.gameconf_page.view(&self.json).map(Message::GameconfMessage)
             },
             Some(LumpType::SKYDEFS) => {
-                self.skydefs_page.view(&self.json).map(Message::SkydefsMessage)
+                self.skydefs_page.view(&self.json, self.file_type(), &self.textures, self.palette.as_ref()).map(Message::SkydefsMessage)
+            },
+            Some(LumpType::SBARDEF) => {
+                self.sbardef_page.view(&self.json).map(Message::SbardefMessage)
             },
             Some(LumpType::DEMOLOOP) => {
-                self.demoloop_page.view(&self.json).map(Message::DemoloopMessage)
+                self.demoloop_page.view(&self.json, self.file_type()).map(Message::DemoloopMessage)
+            },
+            Some(LumpType::Translation) => {
+                self.translation_page.view(&self.json).map(Message::TranslationMessage)
+            },
+            Some(LumpType::Finale) => {
+                self.finale_page.view(&self.json).map(Message::FinaleMessage)
+            },
+            Some(LumpType::UMAPINFO) => {
+                self.umapinfo_page.view(&self.umapinfo).map(Message::UMapInfoMessage)
             },
             _ => {
                 widget::container(widget::text::title3("⇐ Select a lump type"))
@@ -472,6 +1278,44 @@ impl cosmic::Application for EditorModel {
 
         content.push(main_content);
 
+        // keys outside of any struct the pages edit, e.g. next to "data" or inside it
+        // pages that show the data's own extra fields aren't repeated here
+        let data_extra = match &self.json.data {
+            ID24JsonData::GAMECONF { .. } | ID24JsonData::Translation { .. } => None,
+            data => data.extra()
+        };
+        let extra_rows: Vec<_> = if self.editing_umapinfo() {
+            Vec::new()
+        } else {
+            extra_fields("Extra fields", &self.json.extra).into_iter()
+                .chain(data_extra.map(|extra| extra_fields("Data extra fields", extra)).unwrap_or_default())
+                .collect()
+        };
+        if !extra_rows.is_empty() {
+            let extra_list = extra_rows.into_iter().fold(
+                widget::list_column(),
+                widget::ListColumn::add
+            );
+            content.push(widget::container(widget::scrollable(extra_list))
+                .max_height(150.0)
+                .into());
+        }
+
+        if !self.diagnostics.is_empty() {
+            let can_jump = self.can_jump();
+            let diagnostics_list = self.diagnostics.iter().fold(
+                widget::list_column(),
+                |acc, diagnostic|
+                    acc.add(widget::button::text(diagnostic.to_string())
+                        .on_press_maybe(can_jump.then(|| Message::JumpTo(diagnostic.path.clone())))
+                        .width(Length::Fill)
+                        .class(widget::button::ButtonClass::Text))
+            );
+            content.push(widget::container(widget::scrollable(diagnostics_list))
+                .max_height(150.0)
+                .into());
+        }
+
         widget::toaster(&self.toasts, widget::column::with_children(content))
     }
 }
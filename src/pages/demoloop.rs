@@ -16,9 +16,11 @@ use cosmic::prelude::*;
 use cosmic::iced::Length;
 use cosmic::widget;
 use strum::VariantArray;
-use crate::id24json::{ID24Json, ID24JsonData};
-use crate::id24json::demoloop::{Entry, DemoType, OutRowWipe};
-use crate::widgets::aligned_row;
+use id24json::{ID24Json, ID24JsonData};
+use id24json::demoloop::{Entry, DemoType, OutRowWipe};
+use id24json::diagnostics::pointer_tokens;
+use crate::config::FileType;
+use crate::widgets::{aligned_row, extra_fields, lump_name_input};
 
 #[derive(Debug, Clone)]
 pub enum Message {
@@ -27,6 +29,8 @@ pub enum Message {
     ChangeDemoType(DemoType),
     ChangeOutRowWipe(OutRowWipe),
     ChangeDuration(f32),
+    ChangePrimaryLump(String),
+    ChangeSecondaryLump(String),
 }
 
 #[derive(Default)]
@@ -35,8 +39,17 @@ pub struct Page {
 }
 
 impl Page {
-    pub fn view<'a>(&'a self, json: &'a ID24Json) -> Element<'a, Message> {
-        if let ID24JsonData::DEMOLOOP { entries } = &json.data {
+    // selects the entry that a diagnostic path points into
+    pub fn focus(&mut self, path: &str) {
+        let mut tokens = pointer_tokens(path).skip(1);
+        if tokens.next().as_deref() == Some("entries")
+            && let Some(idx) = tokens.next().and_then(|idx| idx.parse().ok()) {
+            self.index = Some(idx);
+        }
+    }
+
+    pub fn view<'a>(&'a self, json: &'a ID24Json, file_type: FileType) -> Element<'a, Message> {
+        if let ID24JsonData::DEMOLOOP { entries, .. } = &json.data {
             let mut properties_list = Vec::new();
             if let Some(idx) = self.index {
                 let Entry {
@@ -44,11 +57,14 @@ impl Page {
                     secondarylump,
                     duration,
                     demo_type,
-                    outrowwipe
+                    outrowwipe,
+                    extra
                 } = &entries[idx];
-                properties_list.push(aligned_row("Primary lump", widget::text_input("", primarylump)));
+                let primary_input = lump_name_input("", primarylump, file_type, Message::ChangePrimaryLump);
+                properties_list.push(aligned_row("Primary lump", primary_input));
                 if *demo_type == DemoType::ArtScreen {
-                    properties_list.push(aligned_row("Music lump", widget::text_input("", secondarylump)));
+                    let secondary_input = lump_name_input("", secondarylump, file_type, Message::ChangeSecondaryLump);
+                    properties_list.push(aligned_row("Music lump", secondary_input));
                 }
                 let type_pick = cosmic::iced::widget::pick_list(
                     DemoType::VARIANTS,
@@ -71,6 +87,7 @@ impl Page {
                     Message::ChangeOutRowWipe
                 );
                 properties_list.push(aligned_row("Wipe type:", wipe_pick));
+                properties_list.extend(extra_fields("Extra fields", extra));
             }
 
             let properties_list = properties_list.into_iter().fold(
@@ -121,25 +138,35 @@ impl Page {
     pub fn update(&mut self, json: &mut ID24Json, message: Message) -> Task<cosmic::Action<Message>> {
         match message {
             Message::NewEntry => {
-                if let ID24JsonData::DEMOLOOP { entries } = &mut json.data {
+                if let ID24JsonData::DEMOLOOP { entries, .. } = &mut json.data {
                     entries.push(Entry::default());
                 }
             },
             Message::ChangeDemoType(demo_type) => {
-                if let (ID24JsonData::DEMOLOOP { entries }, Some(idx)) = (&mut json.data, self.index) {
+                if let (ID24JsonData::DEMOLOOP { entries, .. }, Some(idx)) = (&mut json.data, self.index) {
                     entries[idx].demo_type = demo_type;
                 }
             },
             Message::ChangeOutRowWipe(outrowwipe) => {
-                if let (ID24JsonData::DEMOLOOP { entries }, Some(idx)) = (&mut json.data, self.index) {
+                if let (ID24JsonData::DEMOLOOP { entries, .. }, Some(idx)) = (&mut json.data, self.index) {
                     entries[idx].outrowwipe = outrowwipe;
                 }
             },
             Message::ChangeDuration(duration) => {
-                if let (ID24JsonData::DEMOLOOP { entries }, Some(idx)) = (&mut json.data, self.index) {
+                if let (ID24JsonData::DEMOLOOP { entries, .. }, Some(idx)) = (&mut json.data, self.index) {
                     entries[idx].duration = duration;
                 }
             },
+            Message::ChangePrimaryLump(lump) => {
+                if let (ID24JsonData::DEMOLOOP { entries, .. }, Some(idx)) = (&mut json.data, self.index) {
+                    entries[idx].primarylump = lump;
+                }
+            },
+            Message::ChangeSecondaryLump(lump) => {
+                if let (ID24JsonData::DEMOLOOP { entries, .. }, Some(idx)) = (&mut json.data, self.index) {
+                    entries[idx].secondarylump = lump;
+                }
+            },
             Message::SelectEntry(idx) => {
                 self.index = idx;
             },
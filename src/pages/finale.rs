@@ -0,0 +1,145 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use cosmic::prelude::*;
+use cosmic::iced::Length;
+use cosmic::widget;
+use id24json::{ID24Json, ID24JsonData};
+use id24json::diagnostics::pointer_tokens;
+use id24json::finale::CastFrame;
+use crate::widgets::{aligned_row, extra_fields};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SelectCastMember(Option<usize>),
+}
+
+// only shows the finale for now, none of it is editable here
+#[derive(Default)]
+pub struct Page {
+    cast_member: Option<usize>,
+}
+
+fn frame_rows<'a>(heading: &'a str, frames: &'a [CastFrame]) -> Vec<widget::Row<'a, Message>> {
+    let mut rows = vec![widget::row().push(widget::text::heading(heading))];
+    rows.extend(frames.iter().map(|frame| {
+        let mut details = vec![format!("{:.2} seconds", frame.duration)];
+        if frame.flipped {
+            details.push("flipped".to_owned());
+        }
+        if !frame.sound.is_empty() {
+            details.push(format!("plays {}", frame.sound));
+        }
+        aligned_row(&frame.lump, widget::text::body(details.join(", ")))
+    }));
+    rows
+}
+
+impl Page {
+    pub fn reset_index(&mut self) {
+        self.cast_member = None;
+    }
+
+    // selects the cast member that a diagnostic path points into
+    pub fn focus(&mut self, path: &str) {
+        let mut tokens = pointer_tokens(path).skip(1);
+        if tokens.next().as_deref() == Some("castrollcall")
+            && tokens.next().as_deref() == Some("castmembers")
+            && let Some(idx) = tokens.next().and_then(|idx| idx.parse().ok()) {
+            self.cast_member = Some(idx);
+        }
+    }
+
+    pub fn view<'a>(&'a self, json: &'a ID24Json) -> Element<'a, Message> {
+        if let ID24JsonData::Finale { finale_type, music, background, donextmap, bunny, castrollcall, .. } = &json.data {
+            let mut properties_list = vec![
+                aligned_row("Type:", widget::text::body(finale_type.to_string())),
+                aligned_row("Music:", widget::text::body(music.as_str())),
+                aligned_row("Background:", widget::text::body(background.as_str())),
+                aligned_row("Go to next map:", widget::text::body(if *donextmap { "Yes" } else { "No" })),
+            ];
+            if let Some(bunny) = bunny {
+                properties_list.extend([
+                    widget::row().push(widget::text::heading("Bunny scroller")),
+                    aligned_row("Stitch image:", widget::text::body(bunny.stitchimage.as_str())),
+                    aligned_row("Overlay:", widget::text::body(bunny.overlay.as_str())),
+                    aligned_row("Overlay count:", widget::text::body(bunny.overlaycount.to_string())),
+                    aligned_row("Overlay sound:", widget::text::body(bunny.overlaysound.to_string())),
+                    aligned_row("Overlay X:", widget::text::body(bunny.overlayx.to_string())),
+                    aligned_row("Overlay Y:", widget::text::body(bunny.overlayy.to_string())),
+                ]);
+                properties_list.extend(extra_fields("Bunny extra fields", &bunny.extra));
+            }
+            if let Some(castrollcall) = castrollcall {
+                properties_list.extend(extra_fields("Cast roll call extra fields", &castrollcall.extra));
+                if let Some(member) = self.cast_member.and_then(|idx| castrollcall.castmembers.get(idx)) {
+                    properties_list.extend([
+                        widget::row().push(widget::text::heading("Cast member")),
+                        aligned_row("Name:", widget::text::body(member.name.as_str())),
+                        aligned_row("Alert sound:", widget::text::body(member.alertsound.as_str())),
+                        aligned_row("Thing type:", widget::text::body(member.thingtype.to_string())),
+                    ]);
+                    properties_list.extend(frame_rows("Alive frames", &member.aliveframes));
+                    properties_list.extend(frame_rows("Death frames", &member.deathframes));
+                    properties_list.extend(extra_fields("Extra fields", &member.extra));
+                }
+            }
+
+            let properties_list = properties_list.into_iter().fold(
+                widget::list_column(),
+                widget::ListColumn::add
+            );
+
+            let mut content: Vec<Element<'a, Message>> = vec![
+                widget::container(widget::scrollable(properties_list))
+                    .width(Length::FillPortion(2))
+                    .into(),
+            ];
+            if let Some(castrollcall) = castrollcall {
+                let members_list = castrollcall.castmembers.iter().enumerate().fold(
+                    widget::list_column(),
+                    |acc, (idx, member)|
+                        acc.add(widget::button::text(member.name.as_str())
+                            .on_press(Message::SelectCastMember(Some(idx)))
+                            .width(Length::Fill)
+                            .class(match self.cast_member {
+                                Some(i) if i == idx => widget::button::ButtonClass::Suggested,
+                                _ => widget::button::ButtonClass::Text
+                            }))
+                );
+                content.push(widget::divider::vertical::heavy().into());
+                content.push(widget::container(widget::scrollable(members_list))
+                    .width(Length::FillPortion(1))
+                    .into());
+            }
+
+            widget::container(widget::row::with_children(content))
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        } else {
+            widget::container(widget::text::heading("You shouldn't be here."))
+                .center_x(Length::Fill)
+                .center_y(Length::Fill)
+                .into()
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<cosmic::Action<Message>> {
+        match message {
+            Message::SelectCastMember(idx) => self.cast_member = idx,
+        }
+        Task::none()
+    }
+}
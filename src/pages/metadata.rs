@@ -0,0 +1,66 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use cosmic::prelude::*;
+use cosmic::widget;
+use id24json::ID24Json;
+use id24json::metadata::Metadata;
+use crate::widgets::{aligned_row, extra_fields};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ChangeAuthor(String),
+    ChangeComment(String),
+}
+
+#[derive(Default)]
+pub struct Page;
+
+impl Page {
+    pub fn view<'a>(&'a self, json: &'a ID24Json) -> Element<'a, Message> {
+        let metadata = json.metadata.as_ref();
+        let field = |get: fn(&Metadata) -> &Option<Option<String>>| {
+            metadata.and_then(|metadata| get(metadata).as_ref()?.as_deref()).unwrap_or("")
+        };
+        // application and timestamp are filled in on save, so there's no point editing them
+        let author_input = widget::text_input("", field(|m| &m.author))
+            .on_input(Message::ChangeAuthor);
+        let comment_input = widget::text_input("", field(|m| &m.comment))
+            .on_input(Message::ChangeComment);
+
+        let mut properties_list = vec![
+            aligned_row("Application:", widget::text::body(field(|m| &m.application))),
+            aligned_row("Last modified:", widget::text::body(field(|m| &m.timestamp))),
+            aligned_row("Author:", author_input),
+            aligned_row("Comment:", comment_input),
+        ];
+        if let Some(metadata) = metadata {
+            properties_list.extend(extra_fields("Extra fields", &metadata.extra));
+        }
+
+        properties_list.into_iter().fold(
+            widget::list_column(),
+            widget::ListColumn::add
+        ).into()
+    }
+
+    pub fn update(&mut self, json: &mut ID24Json, message: Message) -> Task<cosmic::Action<Message>> {
+        let metadata = json.metadata.get_or_insert_with(Metadata::default);
+        match message {
+            Message::ChangeAuthor(author) => metadata.set_author(&author),
+            Message::ChangeComment(comment) => metadata.set_comment(&comment),
+        }
+        Task::none()
+    }
+}
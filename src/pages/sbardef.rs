@@ -0,0 +1,189 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use std::collections::HashMap;
+use std::time::Instant;
+use cosmic::prelude::*;
+use cosmic::iced::Length;
+use cosmic::widget;
+use doomstruct::graphics::Image;
+use id24json::{ID24Json, ID24JsonData};
+use id24json::diagnostics::pointer_tokens;
+use id24json::references::ReferenceKind;
+use id24json::sbardef::{self, PlayerState};
+use crate::widgets::aligned_row;
+
+const WEAPONS: [&str; 9] = [
+    "Fist", "Pistol", "Shotgun", "Chaingun", "Rocket Launcher", "Plasma Rifle", "BFG 9000", "Chainsaw", "Super Shotgun"
+];
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    SelectStatusBar(usize),
+    ChangeHealth(i32),
+    ChangeArmor(i32),
+    SelectWeapon(&'static str),
+    Tick(Instant),
+}
+
+// only previews for now, the element tree isn't editable here
+#[derive(Default)]
+pub struct Page {
+    statusbar: usize,
+    player: PlayerState,
+    // None until there are resources with a PLAYPAL to draw from
+    preview: Option<Image>,
+    // graphics and the fill flat are decoded once, since animations redraw the bar every tic
+    // they're kept apart by kind because a flat can have the same name as a graphic
+    lumps: HashMap<(ReferenceKind, String), Option<Image>>,
+    start: Option<Instant>,
+    now: Option<Instant>,
+}
+
+impl Page {
+    // for when the resources change, everything has to be loaded again
+    pub fn clear_preview(&mut self) {
+        self.lumps.clear();
+        self.preview = None;
+    }
+
+    // graphic and flat load lumps from the resources
+    pub fn refresh_preview(&mut self, json: &ID24Json, graphic: impl Fn(&str) -> Option<Image>, flat: impl Fn(&str) -> Option<Image>) {
+        let ID24JsonData::SBARDEF { numberfonts, statusbars, .. } = &json.data else {
+            self.preview = None;
+            return;
+        };
+        let Some(statusbar) = statusbars.get(self.statusbar) else {
+            self.preview = None;
+            return;
+        };
+        let time = match (self.start.get_or_insert_with(Instant::now), self.now) {
+            (start, Some(now)) => now.saturating_duration_since(*start).as_secs_f32(),
+            _ => 0.0
+        };
+        let mut fill = None;
+        if let Some(name) = statusbar.fillflat() {
+            fill = self.lumps.entry((ReferenceKind::Flat, name.to_owned())).or_insert_with(|| flat(name)).clone();
+        }
+        let lumps = &mut self.lumps;
+        self.preview = Some(sbardef::render(
+            statusbar, numberfonts, &self.player, time,
+            |name| lumps.entry((ReferenceKind::Graphic, name.to_owned())).or_insert_with(|| graphic(name)).clone(),
+            |_| fill
+        ));
+    }
+
+    // selects the status bar that a diagnostic path points into
+    pub fn focus(&mut self, path: &str) {
+        let mut tokens = pointer_tokens(path).skip(1);
+        if tokens.next().as_deref() == Some("statusbars")
+            && let Some(idx) = tokens.next().and_then(|idx| idx.parse().ok()) {
+            self.statusbar = idx;
+        }
+    }
+
+    // only ticks while something that's shown is animated
+    pub fn animating(&self, json: &ID24Json) -> bool {
+        let ID24JsonData::SBARDEF { statusbars, .. } = &json.data else {
+            return false;
+        };
+        self.preview.is_some() && statusbars.get(self.statusbar).is_some_and(|statusbar| statusbar.animates(&self.player))
+    }
+
+    pub fn view<'a>(&'a self, json: &'a ID24Json) -> Element<'a, Message> {
+        if let ID24JsonData::SBARDEF { statusbars, .. } = &json.data {
+            let indices: Vec<usize> = (0..statusbars.len()).collect();
+            let statusbar_pick = cosmic::iced::widget::pick_list(
+                indices,
+                Some(self.statusbar).filter(|idx| *idx < statusbars.len()),
+                Message::SelectStatusBar
+            );
+            let health_spin = widget::spin_button(
+                self.player.health.to_string(), self.player.health,
+                1, 0, 200,
+                Message::ChangeHealth
+            );
+            let armor_spin = widget::spin_button(
+                self.player.armor.to_string(), self.player.armor,
+                1, 0, 200,
+                Message::ChangeArmor
+            );
+            let weapon_pick = cosmic::iced::widget::pick_list(
+                WEAPONS,
+                WEAPONS.get(usize::from(self.player.selected_weapon)).copied(),
+                Message::SelectWeapon
+            );
+            let preview: Element<'a, Message> = match &self.preview {
+                Some(image) => {
+                    #[allow(clippy::cast_possible_truncation)]
+                    let handle = widget::image::Handle::from_rgba(
+                        image.width as u32, image.height as u32, image.pixels.clone()
+                    );
+                    // doubled, so the view is the size it'd be at 640x400
+                    #[allow(clippy::cast_precision_loss)]
+                    widget::image(handle)
+                        .width(Length::Fixed(image.width as f32 * 2.0))
+                        .height(Length::Fixed(image.height as f32 * 2.0))
+                        .filter_method(cosmic::iced::widget::image::FilterMethod::Nearest)
+                        .into()
+                },
+                None => widget::text::body("Load resources with a PLAYPAL to see the status bar").into()
+            };
+
+            let properties_list = vec![
+                aligned_row("Status bar:", statusbar_pick),
+                aligned_row("Health:", health_spin),
+                aligned_row("Armor:", armor_spin),
+                aligned_row("Weapon:", weapon_pick),
+            ].into_iter().fold(
+                widget::list_column(),
+                widget::ListColumn::add
+            );
+
+            let content = widget::column::with_children(vec![
+                widget::container(preview).center_x(Length::Fill).into(),
+                widget::divider::horizontal::heavy().into(),
+                widget::scrollable(properties_list).into(),
+            ]);
+
+            widget::container(content)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        } else {
+            widget::container(widget::text::heading("You shouldn't be here."))
+                .center_x(Length::Fill)
+                .center_y(Length::Fill)
+                .into()
+        }
+    }
+
+    pub fn update(&mut self, message: Message) -> Task<cosmic::Action<Message>> {
+        match message {
+            Message::SelectStatusBar(idx) => self.statusbar = idx,
+            Message::ChangeHealth(health) => self.player.health = health,
+            Message::ChangeArmor(armor) => self.player.armor = armor,
+            Message::SelectWeapon(name) => {
+                if let Some(weapon) = WEAPONS.iter().position(|weapon| *weapon == name)
+                    && let Ok(selected) = u8::try_from(weapon) {
+                    // can't have a weapon out without owning it
+                    self.player.weapons[weapon] = true;
+                    self.player.selected_weapon = selected;
+                }
+            },
+            Message::Tick(now) => self.now = Some(now),
+        }
+        Task::none()
+    }
+}
@@ -0,0 +1,84 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use cosmic::prelude::*;
+use cosmic::cosmic_config;
+use cosmic::iced::Length;
+use cosmic::widget;
+use strum::VariantArray;
+use crate::config::{Config, FileType};
+use id24json::parse::ParseMode;
+use crate::widgets::aligned_row;
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ChangeFileType(FileType),
+    ChangeParseMode(ParseMode),
+    ToggleNormalise(bool),
+}
+
+#[derive(Default)]
+pub struct Page;
+
+impl Page {
+    pub fn view<'a>(&'a self, config: &'a Config) -> Element<'a, Message> {
+        let file_type_pick = cosmic::iced::widget::pick_list(
+            FileType::VARIANTS,
+            Some(config.file_type),
+            Message::ChangeFileType
+        );
+        let mode_pick = cosmic::iced::widget::pick_list(
+            ParseMode::VARIANTS,
+            Some(config.parse_mode),
+            Message::ChangeParseMode
+        );
+        let normalise_toggle = widget::toggler(config.normalise_on_save)
+            .on_toggle(Message::ToggleNormalise);
+
+        let list = widget::list_column()
+            .add(aligned_row("Lump names for:", file_type_pick))
+            .add(aligned_row("Parsing mode:", mode_pick))
+            .add(aligned_row("Fix known spec deviations on save:", normalise_toggle));
+
+        widget::container(widget::scrollable(list))
+            .center_x(Length::Fill)
+            .center_y(Length::Shrink)
+            .into()
+    }
+
+    pub fn update(&mut self, config: &mut Config, handler: Option<&cosmic_config::Config>, message: Message) -> Task<cosmic::Action<Message>> {
+        // without a handler the settings still apply, they just won't persist between sessions
+        let result = match (message, handler) {
+            (Message::ChangeFileType(file_type), Some(handler)) => config.set_file_type(handler, file_type),
+            (Message::ChangeParseMode(mode), Some(handler)) => config.set_parse_mode(handler, mode),
+            (Message::ToggleNormalise(normalise), Some(handler)) => config.set_normalise_on_save(handler, normalise),
+            (Message::ChangeFileType(file_type), None) => {
+                config.file_type = file_type;
+                Ok(true)
+            },
+            (Message::ChangeParseMode(mode), None) => {
+                config.parse_mode = mode;
+                Ok(true)
+            },
+            (Message::ToggleNormalise(normalise), None) => {
+                config.normalise_on_save = normalise;
+                Ok(true)
+            },
+        };
+        if let Err(why) = result {
+            println!("failed to save config: {why}");
+        }
+        Task::none()
+    }
+}
@@ -1,343 +1,659 @@
-/*
- * Copyright (C) 2025  Mia McMahill
- *
- * This program is free software: you can redistribute it and/or modify
- * it under the terms of the GNU General Public License as published by
- * the Free Software Foundation, either version 3 of the License, or
- * (at your option) any later version.
- *
- * This program is distributed in the hope that it will be useful,
- * but WITHOUT ANY WARRANTY; without even the implied warranty of
- * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
- * GNU General Public License for more details.
- */
-
-use cosmic::prelude::*;
-use cosmic::iced::Length;
-use cosmic::widget;
-use strum::VariantArray;
-use crate::id24json::{skydefs, ID24Json, ID24JsonData};
-use crate::id24json::skydefs::{Fire, Sky, SkyTex, SkyType};
-use crate::widgets::aligned_row;
-
-#[derive(Default)]
-pub struct Page {
-    skydefs_index: SkydefsIndex,
-    skies_model: widget::segmented_button::SingleSelectModel,
-    flatmapping_model: widget::segmented_button::SingleSelectModel,
-}
-
-#[derive(PartialEq, Clone, Copy, Debug, Default)]
-enum SkydefsIndex {
-    #[default]
-    None,
-    Sky(usize),
-    Flatmapping(usize)
-}
-
-#[derive(Debug, Clone)]
-pub enum SkyTexMessage {
-    ChangeName(String),
-    ChangeMid(u16),
-    ChangeScrollX(f32),
-    ChangeScrollY(f32),
-    ChangeScaleX(f32),
-    ChangeScaleY(f32),
-}
-
-#[derive(Debug, Clone)]
-pub enum Message {
-    UpdateSkyTexProp(SkyTexMessage),
-    UpdateSkyTexPropFG(SkyTexMessage),
-    ChangeSkyType(SkyType),
-    ChangeFireSpeed(f32),
-    ChangeFlatmapFlat(String),
-    ChangeFlatmapSky(String),
-    NewSky,
-    NewFlatmapping,
-    DeleteSky(usize),
-    DeleteFlatmapping(usize),
-    SelectSky(Option<usize>),
-    SelectFlatmapping(Option<usize>),
-    Dummy // TODO: remove this
-}
-
-impl Page {
-    pub fn reset_index(&mut self) {
-        self.skydefs_index = SkydefsIndex::None;
-    }
-
-    #[allow(clippy::too_many_lines)]
-    // TODO: make this less huge, just dont want it to yell at me for just a bit longer
-    pub fn view<'a>(&'a self, json: &'a ID24Json) -> Element<'a, Message> {
-        if let ID24JsonData::SKYDEFS { skies, flatmapping } = &json.data {
-            let mut properties_list = Vec::new();
-            if let (Some(skies), SkydefsIndex::Sky(idx)) = (skies, self.skydefs_index) {
-                // TODO: use .get and check that the sky exists
-                // we shouldn't run into a case where the index is out of bounds but just in case
-                let Sky {
-                    backgroundtex,
-                    sky_type,
-                    fire,
-                    foregroundtex
-                } = &skies[idx];
-                macro_rules! tex_fields {
-                    ($tex:expr, $message:expr, $prefix:expr) => {
-                        let name_input = widget::text_input("SKY1", &$tex.name)
-                                .on_input(|s| $message(SkyTexMessage::ChangeName(s)));
-                        let mid_spin = widget::spin_button(
-                            $tex.mid.to_string(), $tex.mid,
-                            1, 0, 1024,
-                            |v| $message(SkyTexMessage::ChangeMid(v))
-                        );
-                        let scrollx_spin = widget::spin_button(
-                            $tex.scrollx.to_string(), $tex.scrollx,
-                            0.1, -100.0, 100.0,
-                            |v| $message(SkyTexMessage::ChangeScrollX(v))
-                        );
-                        let scrolly_spin = widget::spin_button(
-                            $tex.scrolly.to_string(), $tex.scrolly,
-                            0.1, -100.0, 100.0,
-                            |v| $message(SkyTexMessage::ChangeScrollY(v))
-                        );
-                        let scalex_spin = widget::spin_button(
-                            $tex.scalex.to_string(), $tex.scalex,
-                            0.1, 0.0, 100.0,
-                            |v| $message(SkyTexMessage::ChangeScaleX(v))
-                        );
-                        let scaley_spin = widget::spin_button(
-                            $tex.scaley.to_string(), $tex.scaley,
-                            0.1, 0.0, 100.0,
-                            |v| $message(SkyTexMessage::ChangeScaleY(v))
-                        );
-
-                        properties_list.push(aligned_row(concat!($prefix, "Texture:"), name_input));
-                        properties_list.push(aligned_row(concat!($prefix, "Mid:"), mid_spin));
-                        properties_list.push(aligned_row(concat!($prefix, "Scroll X (seconds):"), scrolly_spin));
-                        properties_list.push(aligned_row(concat!($prefix, "Scroll Y (seconds):"), scrollx_spin));
-                        properties_list.push(aligned_row(concat!($prefix, "Scale X:"), scalex_spin));
-                        properties_list.push(aligned_row(concat!($prefix, "Scale Y:"), scaley_spin));
-                    };
-                }
-
-                tex_fields!(backgroundtex, Message::UpdateSkyTexProp, "");
-                let type_pick = cosmic::iced::widget::pick_list(
-                    SkyType::VARIANTS,
-                    Some(sky_type),
-                    Message::ChangeSkyType
-                );
-                properties_list.push(aligned_row("Type:", type_pick));
-                match (sky_type, fire, foregroundtex) {
-                    (SkyType::WithForeground, _, Some(foregroundtex)) => {
-                        tex_fields!(foregroundtex, Message::UpdateSkyTexPropFG, "Foreground ");
-                    }
-                    (SkyType::Fire, Some(Fire {
-                                             updatetime,
-                                             palette }), _) => {
-                        let time_spin = widget::spin_button(
-                            (*updatetime).to_string(), *updatetime,
-                            0.1, 0.0, 100.0,
-                            Message::ChangeFireSpeed
-                        );
-                        properties_list.push(aligned_row("Animation Speed (seconds):", time_spin));
-                        // TODO: figure out how this should even work in the gui and add a preview for fire skies
-                        // what palette to use for when loading from standalone json? this is a time where making this part of slade wouldve been nice
-                        properties_list.push(aligned_row("Palette:", widget::text::heading("coming soon")));
-                    }
-                    _ => ()
-                }
-            } else if let (Some(flatmapping), SkydefsIndex::Flatmapping(idx)) = (flatmapping, self.skydefs_index) {
-                let skydefs::FlatMapping { flat, sky } = &flatmapping[idx];
-                let flat_input = widget::text_input("F_SKY1", flat)
-                    .on_input(Message::ChangeFlatmapFlat);
-                let sky_input = widget::text_input("SKY1", sky)
-                    .on_input(Message::ChangeFlatmapSky);
-                properties_list.push(aligned_row("Flat:", flat_input));
-                properties_list.push(aligned_row("Sky:", sky_input));
-            }
-            let properties_list = properties_list.into_iter().fold(
-                widget::list_column(),
-                widget::ListColumn::add
-            );
-            let skies_list = skies.as_ref().map_or(
-                widget::list_column(),
-                |s| s.iter().enumerate().fold(
-                    widget::list_column(),
-                    |acc, (idx, sky)|
-                        acc.add(widget::button::text(&sky.backgroundtex.name)
-                            .on_press(Message::SelectSky(Some(idx)))
-                            .width(Length::Fill)
-                            .class(match self.skydefs_index {
-                                SkydefsIndex::Sky(i) if i == idx => widget::button::ButtonClass::Suggested,
-                                _ => widget::button::ButtonClass::Text
-                            }))
-                ));
-            let flatmapping_list = flatmapping.as_ref().map_or(
-                widget::list_column(),
-                |s| s.iter().enumerate().fold(
-                    widget::list_column(),
-                    |acc, (idx, mapping)|
-                        acc.add(widget::button::text(&mapping.flat)
-                            .on_press(Message::SelectFlatmapping(Some(idx)))
-                            .width(Length::Fill)
-                            .class(match self.skydefs_index {
-                                SkydefsIndex::Flatmapping(i) if i == idx => widget::button::ButtonClass::Suggested,
-                                _ => widget::button::ButtonClass::Text
-                            }))
-                ));
-            let content = widget::row::with_children(vec![
-                widget::container(widget::scrollable(properties_list))
-                    .width(Length::FillPortion(2))
-                    .into(),
-
-                widget::divider::vertical::heavy().into(),
-
-                widget::container(
-                    widget::column::with_children(vec![
-                        widget::row::with_children(vec![
-                            widget::button::text("New Sky").on_press(Message::NewSky).into(),
-                            widget::horizontal_space().into(),
-                            widget::button::text("Delete").on_press_maybe(match self.skydefs_index {
-                                SkydefsIndex::Sky(size) => Some(Message::DeleteSky(size)),
-                                _ => None
-                            }).into(),
-                        ]).into(),
-                        widget::container(widget::scrollable(skies_list))
-                            .height(Length::FillPortion(1))
-                            .into(),
-                        widget::divider::horizontal::heavy().into(),
-                        widget::row::with_children(vec![
-                            widget::button::text("New Flat Mapping").on_press(Message::NewFlatmapping).into(),
-                            widget::horizontal_space().into(),
-                            widget::button::text("Delete").on_press_maybe(match self.skydefs_index {
-                                SkydefsIndex::Flatmapping(size) => Some(Message::DeleteFlatmapping(size)),
-                                _ => None
-                            }).into(),
-                        ]).into(),
-                        widget::container(widget::scrollable(flatmapping_list))
-                            .height(Length::FillPortion(1))
-                            .into(),
-                    ]).spacing(5)
-                )
-                    .width(Length::FillPortion(1))
-                    .into(),
-            ])
-                .padding(10)
-                .spacing(10);
-
-            widget::container(content)
-                .width(Length::Fill)
-                .height(Length::Fill)
-                .into()
-        } else {
-            // TODO: figure out a better way to handle this
-            widget::container(widget::text::heading("You shouldn't be here."))
-                .center_x(Length::Fill)
-                .center_y(Length::Fill)
-                .into()
-        }
-    }
-
-    pub fn update(&mut self, json: &mut ID24Json, message: Message) -> Task<cosmic::Action<Message>> {
-        match message {
-            Message::SelectSky(Some(idx)) => {
-                self.skydefs_index = SkydefsIndex::Sky(idx);
-            },
-            Message::SelectFlatmapping(Some(idx)) => {
-                self.skydefs_index = SkydefsIndex::Flatmapping(idx);
-            },
-            Message::SelectSky(None) | Message::SelectFlatmapping(None) => {
-                self.skydefs_index = SkydefsIndex::None;
-            },
-            Message::NewSky => {
-                if let ID24JsonData::SKYDEFS { skies, .. } = &mut json.data {
-                    skies.get_or_insert_with(Vec::new).push(Sky::default());
-                }
-            },
-            Message::NewFlatmapping => {
-                if let ID24JsonData::SKYDEFS { flatmapping, .. } = &mut json.data {
-                    flatmapping.get_or_insert_with(Vec::new).push(skydefs::FlatMapping::default());
-                }
-            },
-            Message::DeleteSky(idx) => {
-                // TODO: make this async in case the list is very large
-                if let ID24JsonData::SKYDEFS { skies: Some(skies), .. } = &mut json.data {
-                    self.skydefs_index = SkydefsIndex::None;
-                    skies.remove(idx);
-                }
-            },
-            Message::DeleteFlatmapping(idx) => {
-                if let ID24JsonData::SKYDEFS { flatmapping: Some(flatmapping), .. } = &mut json.data {
-                    self.skydefs_index = SkydefsIndex::None;
-                    flatmapping.remove(idx);
-                }
-            },
-            Message::UpdateSkyTexProp(skymessage) => {
-                if let (ID24JsonData::SKYDEFS { skies: Some(skies), .. }, SkydefsIndex::Sky(idx)) = (&mut json.data, self.skydefs_index) {
-                    match skymessage {
-                        SkyTexMessage::ChangeName(name) => skies[idx].backgroundtex.name = name,
-                        SkyTexMessage::ChangeMid(mid) => skies[idx].backgroundtex.mid = mid,
-                        SkyTexMessage::ChangeScaleX(scale) => skies[idx].backgroundtex.scalex = scale,
-                        SkyTexMessage::ChangeScaleY(scale) => skies[idx].backgroundtex.scaley = scale,
-                        SkyTexMessage::ChangeScrollX(scroll) => skies[idx].backgroundtex.scrollx = scroll,
-                        SkyTexMessage::ChangeScrollY(scroll) => skies[idx].backgroundtex.scrolly = scroll,
-                    }
-                }
-            },
-            Message::UpdateSkyTexPropFG(skymessage) => {
-                if let (ID24JsonData::SKYDEFS { skies: Some(skies), .. }, SkydefsIndex::Sky(idx)) = (&mut json.data, self.skydefs_index) {
-                    if let Some(foreground) = &mut skies[idx].foregroundtex {
-                        match skymessage {
-                            SkyTexMessage::ChangeName(name) => foreground.name = name,
-                            SkyTexMessage::ChangeMid(mid) => foreground.mid = mid,
-                            SkyTexMessage::ChangeScaleX(scale) => foreground.scalex = scale,
-                            SkyTexMessage::ChangeScaleY(scale) => foreground.scaley = scale,
-                            SkyTexMessage::ChangeScrollX(scroll) => foreground.scrollx = scroll,
-                            SkyTexMessage::ChangeScrollY(scroll) => foreground.scrolly = scroll,
-                        }
-                    }
-                }
-            },
-            Message::ChangeFireSpeed(speed) => {
-                if let (ID24JsonData::SKYDEFS { skies: Some(skies), .. }, SkydefsIndex::Sky(idx)) = (&mut json.data, self.skydefs_index) {
-                    if let Some(fire) = &mut skies[idx].fire {
-                        fire.updatetime = speed;
-                    }
-                }
-            },
-            Message::ChangeSkyType(sky_type) => {
-                if let (ID24JsonData::SKYDEFS { skies: Some(skies), .. }, SkydefsIndex::Sky(idx)) = (&mut json.data, self.skydefs_index) {
-                    let sky = &mut skies[idx];
-                    sky.sky_type = sky_type;
-                    match sky_type {
-                        SkyType::Standard => {
-                            sky.foregroundtex = None;
-                            sky.fire = None;
-                        },
-                        SkyType::WithForeground => {
-                            sky.foregroundtex = Some(SkyTex::default());
-                            sky.fire = None;
-                        },
-                        SkyType::Fire => {
-                            sky.foregroundtex = None;
-                            sky.fire = Some(Fire::default());
-                        }
-                    }
-                }
-            },
-            Message::ChangeFlatmapFlat(flat) => {
-                if let (ID24JsonData::SKYDEFS { flatmapping: Some(flatmaps), .. }, SkydefsIndex::Flatmapping(idx)) = (&mut json.data, self.skydefs_index) {
-                    flatmaps[idx].flat = flat;
-                }
-            },
-            Message::ChangeFlatmapSky(sky) => {
-                if let (ID24JsonData::SKYDEFS { flatmapping: Some(flatmaps), .. }, SkydefsIndex::Flatmapping(idx)) = (&mut json.data, self.skydefs_index) {
-                    flatmaps[idx].sky = sky;
-                }
-            },
-            _ => ()
-        }
-
-        Task::none()
-    }
-}
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use cosmic::prelude::*;
+use cosmic::iced::Length;
+use cosmic::widget;
+use strum::VariantArray;
+use id24json::{skydefs, ID24Json, ID24JsonData};
+use id24json::skydefs::{Fire, Sky, SkyTex, SkyType};
+use id24json::diagnostics::pointer_tokens;
+use std::time::Instant;
+use doomstruct::graphics::{Image, Palette};
+use doomstruct::sky::{self, SkyLayer};
+use doomstruct::textures::TextureSet;
+use crate::config::FileType;
+use crate::widgets::{aligned_row, extra_fields, lump_name_input};
+
+#[derive(Default)]
+pub struct Page {
+    skydefs_index: SkydefsIndex,
+    skies_model: widget::segmented_button::SingleSelectModel,
+    flatmapping_model: widget::segmented_button::SingleSelectModel,
+    preview: Preview,
+    // the entry in a fire sky's palette that picking a colour changes
+    fire_colour: Option<usize>,
+}
+
+// the selected sky's textures, rendered once and kept until it uses different ones
+// None for the image means the texture couldn't be rendered
+#[derive(Default)]
+struct Preview {
+    background: Option<(String, Option<Image>)>,
+    foreground: Option<(String, Option<Image>)>,
+    // scrolling is timed from when the preview was last refreshed
+    start: Option<Instant>,
+    now: Option<Instant>,
+    // a fire sky's simulation, and how many colours it was started with
+    fire: Option<(usize, sky::Fire)>,
+    // seconds since the fire last updated
+    fire_time: f32,
+}
+
+#[derive(PartialEq, Clone, Copy, Debug, Default)]
+enum SkydefsIndex {
+    #[default]
+    None,
+    Sky(usize),
+    Flatmapping(usize)
+}
+
+#[derive(Debug, Clone)]
+pub enum SkyTexMessage {
+    ChangeName(String),
+    ChangeMid(u16),
+    ChangeScrollX(f32),
+    ChangeScrollY(f32),
+    ChangeScaleX(f32),
+    ChangeScaleY(f32),
+}
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    UpdateSkyTexProp(SkyTexMessage),
+    UpdateSkyTexPropFG(SkyTexMessage),
+    ChangeSkyType(SkyType),
+    ChangeFireSpeed(f32),
+    ChangeFlatmapFlat(String),
+    ChangeFlatmapSky(String),
+    NewSky,
+    NewFlatmapping,
+    DeleteSky(usize),
+    DeleteFlatmapping(usize),
+    SelectSky(Option<usize>),
+    SelectFlatmapping(Option<usize>),
+    SelectFireColour(usize),
+    SetFireColour(u8),
+    InsertFireColour,
+    RemoveFireColour,
+    MoveFireColourEarlier,
+    MoveFireColourLater,
+    ResetFirePalette,
+    Tick(Instant),
+    Dummy // TODO: remove this
+}
+
+impl Page {
+    pub fn reset_index(&mut self) {
+        self.skydefs_index = SkydefsIndex::None;
+    }
+
+    fn selected_sky<'a>(&self, json: &'a ID24Json) -> Option<&'a Sky> {
+        match (&json.data, self.skydefs_index) {
+            (ID24JsonData::SKYDEFS { skies: Some(skies), .. }, SkydefsIndex::Sky(idx)) => skies.get(idx),
+            _ => None
+        }
+    }
+
+    fn selected_fire<'a>(&self, json: &'a mut ID24Json) -> Option<&'a mut Fire> {
+        match (&mut json.data, self.skydefs_index) {
+            (ID24JsonData::SKYDEFS { skies: Some(skies), .. }, SkydefsIndex::Sky(idx)) => skies.get_mut(idx)?.fire.as_mut(),
+            _ => None
+        }
+    }
+
+    // for when the resources change, everything has to be rendered again
+    pub fn clear_preview(&mut self) {
+        self.preview = Preview::default();
+    }
+
+    // render returns the named texture, if the loaded resources have it
+    pub fn refresh_preview(&mut self, json: &ID24Json, render: impl Fn(&str) -> Option<Image>) {
+        let sky = self.selected_sky(json);
+        let background = sky.map(|sky| sky.backgroundtex.name.as_str());
+        let foreground = sky
+            .filter(|sky| sky.sky_type == SkyType::WithForeground)
+            .and_then(|sky| sky.foregroundtex.as_ref())
+            .map(|tex| tex.name.as_str());
+        for (slot, name) in [(&mut self.preview.background, background), (&mut self.preview.foreground, foreground)] {
+            match name {
+                Some(name) if slot.as_ref().is_some_and(|(cached, _)| cached == name) => (),
+                Some(name) => *slot = Some((name.to_owned(), render(name))),
+                None => *slot = None
+            }
+        }
+        // the fire burns in a texture the size of the one it replaces
+        let fire = sky.filter(|sky| sky.sky_type == SkyType::Fire).and_then(|sky| sky.fire.as_ref());
+        let (width, height) = match &self.preview.background {
+            Some((_, Some(image))) => (image.width, image.height),
+            _ => (256, 128)
+        };
+        match (fire, &self.preview.fire) {
+            (Some(fire), Some((colours, sim))) if *colours == fire.palette.len() && (sim.width, sim.height) == (width, height) => (),
+            (Some(fire), _) => self.preview.fire = Some((fire.palette.len(), sky::Fire::new(width, height, fire.palette.len()))),
+            (None, _) => self.preview.fire = None
+        }
+        self.preview.start.get_or_insert_with(Instant::now);
+    }
+
+    // only scrolling skies and burning fires need to be redrawn over time
+    pub fn animating(&self, json: &ID24Json) -> bool {
+        let scrolls = |tex: &SkyTex| tex.scrollx != 0.0 || tex.scrolly != 0.0;
+        let Some(sky) = self.selected_sky(json) else {
+            return false;
+        };
+        let burning = self.preview.fire.is_some() && sky.fire.as_ref().is_some_and(|fire| fire.updatetime > 0.0);
+        let has_image = burning || self.preview.background.as_ref().is_some_and(|(_, image)| image.is_some());
+        has_image && (burning || scrolls(&sky.backgroundtex) || sky.foregroundtex.as_ref().is_some_and(scrolls))
+    }
+
+    // runs the fire as many times as it would've updated since the last tick
+    fn burn(&mut self, updatetime: f32, elapsed: f32) {
+        let Some((_, fire)) = &mut self.preview.fire else {
+            return;
+        };
+        if updatetime <= 0.0 {
+            return;
+        }
+        // updates faster than the preview ticks can't be seen, so don't spend time on them
+        self.preview.fire_time = (self.preview.fire_time + elapsed).min(updatetime * 8.0);
+        while self.preview.fire_time >= updatetime {
+            fire.update();
+            self.preview.fire_time -= updatetime;
+        }
+    }
+
+    // the fire's palette as swatches, with the PLAYPAL to pick colours from under it
+    fn fire_palette_view<'a>(&self, colours: &'a [u8], playpal: Option<&'a Palette>) -> Element<'a, Message> {
+        let swatch = |index: u8| -> Element<'a, Message> {
+            match playpal {
+                Some(playpal) => {
+                    let [r, g, b] = playpal[usize::from(index)];
+                    widget::container(cosmic::iced::widget::Space::new(Length::Fixed(16.0), Length::Fixed(16.0)))
+                        .class(cosmic::theme::Container::custom(move |_| cosmic::iced::widget::container::Style {
+                            background: Some(cosmic::iced::Background::Color(cosmic::iced::Color::from_rgb8(r, g, b))),
+                            ..Default::default()
+                        }))
+                        .into()
+                },
+                // without a PLAYPAL there's only the number to go on
+                None => widget::text::body(index.to_string()).into()
+            }
+        };
+        let selected = self.fire_colour.filter(|idx| *idx < colours.len());
+        let entries: Vec<Element<'a, Message>> = colours.chunks(16).enumerate().map(|(row, chunk)| {
+            widget::row::with_children(chunk.iter().enumerate().map(|(col, index)| {
+                let idx = row * 16 + col;
+                widget::button::custom(swatch(*index))
+                    .on_press(Message::SelectFireColour(idx))
+                    .padding(2)
+                    .class(if selected == Some(idx) {
+                        widget::button::ButtonClass::Suggested
+                    } else {
+                        widget::button::ButtonClass::Text
+                    })
+                    .into()
+            }).collect::<Vec<_>>()).into()
+        }).collect();
+        let mut content = vec![
+            widget::column::with_children(entries).into(),
+            widget::row::with_children(vec![
+                widget::button::text("Insert").on_press(Message::InsertFireColour).into(),
+                widget::button::text("Remove").on_press_maybe(selected.map(|_| Message::RemoveFireColour)).into(),
+                widget::button::text("Move Earlier").on_press_maybe(selected.filter(|idx| *idx > 0).map(|_| Message::MoveFireColourEarlier)).into(),
+                widget::button::text("Move Later").on_press_maybe(selected.filter(|idx| idx + 1 < colours.len()).map(|_| Message::MoveFireColourLater)).into(),
+                widget::button::text("PSX Preset").on_press(Message::ResetFirePalette).into(),
+            ]).into()
+        ];
+        match playpal {
+            Some(_) => {
+                let grid: Vec<Element<'a, Message>> = (0..=255u8).collect::<Vec<_>>().chunks(16).map(|chunk| {
+                    widget::row::with_children(chunk.iter().map(|index| {
+                        widget::button::custom(swatch(*index))
+                            .on_press(Message::SetFireColour(*index))
+                            .padding(1)
+                            .class(widget::button::ButtonClass::Text)
+                            .into()
+                    }).collect::<Vec<_>>()).into()
+                }).collect();
+                content.push(widget::column::with_children(grid).into());
+            },
+            None => {
+                let index = selected.map_or(0, |idx| colours[idx]);
+                content.push(widget::spin_button(
+                    index.to_string(), index,
+                    1, 0, 255,
+                    Message::SetFireColour
+                ).into());
+            }
+        }
+        widget::column::with_children(content).spacing(4).into()
+    }
+
+    fn preview_view(&self, sky: &Sky, playpal: Option<&Palette>) -> Option<Element<'_, Message>> {
+        let layer = |image, tex: &SkyTex| SkyLayer {
+            image,
+            mid: f32::from(tex.mid),
+            scroll: (tex.scrollx, tex.scrolly),
+            scale: (tex.scalex, tex.scaley)
+        };
+        let fire = match (&sky.fire, &self.preview.fire, playpal) {
+            (Some(fire), Some((_, sim)), Some(playpal)) => Some(sim.to_image(&fire.palette, playpal)),
+            _ => None
+        };
+        let background = match (&fire, &self.preview.background) {
+            (Some(fire), _) => fire,
+            (None, Some((_, Some(background)))) => background,
+            _ => return None
+        };
+        let mut layers = vec![layer(background, &sky.backgroundtex)];
+        if let (Some((_, Some(foreground))), Some(tex)) = (&self.preview.foreground, &sky.foregroundtex) {
+            layers.push(layer(foreground, tex));
+        }
+        let time = match (self.preview.start, self.preview.now) {
+            (Some(start), Some(now)) => now.saturating_duration_since(start).as_secs_f32(),
+            _ => 0.0
+        };
+        let image = sky::render_sky(&layers, time);
+        #[allow(clippy::cast_possible_truncation)]
+        let handle = widget::image::Handle::from_rgba(sky::VIEW_WIDTH as u32, sky::VIEW_HEIGHT as u32, image.pixels);
+        // doubled, so the view is the size it'd be at 640x400
+        #[allow(clippy::cast_precision_loss)]
+        let preview = widget::image(handle)
+            .width(Length::Fixed(sky::VIEW_WIDTH as f32 * 2.0))
+            .height(Length::Fixed(sky::VIEW_HEIGHT as f32 * 2.0))
+            .filter_method(cosmic::iced::widget::image::FilterMethod::Nearest);
+        Some(preview.into())
+    }
+
+    // selects the sky or flat mapping that a diagnostic path points into
+    pub fn focus(&mut self, path: &str) {
+        let mut tokens = pointer_tokens(path).skip(1);
+        let list = tokens.next();
+        let idx = tokens.next().and_then(|idx| idx.parse().ok());
+        match (list.as_deref(), idx) {
+            (Some("skies"), Some(idx)) => self.skydefs_index = SkydefsIndex::Sky(idx),
+            (Some("flatmapping"), Some(idx)) => self.skydefs_index = SkydefsIndex::Flatmapping(idx),
+            _ => ()
+        }
+    }
+
+    #[allow(clippy::too_many_lines)]
+    // TODO: make this less huge, just dont want it to yell at me for just a bit longer
+    pub fn view<'a>(&'a self, json: &'a ID24Json, file_type: FileType, textures: &'a TextureSet, playpal: Option<&'a Palette>) -> Element<'a, Message> {
+        if let ID24JsonData::SKYDEFS { skies, flatmapping, .. } = &json.data {
+            let mut properties_list = Vec::new();
+            let texture_names: Vec<String> = textures.textures().iter().map(|texture| texture.name.clone()).collect();
+            // only offered once the texture lumps have been loaded from the resources
+            let texture_pick = |name: &str, on_select: fn(String) -> Message| {
+                (!texture_names.is_empty()).then(|| cosmic::iced::widget::pick_list(
+                    texture_names.clone(),
+                    textures.find(name).map(|texture| texture.name.clone()),
+                    on_select
+                ).placeholder("Choose a texture"))
+            };
+            if let (Some(skies), SkydefsIndex::Sky(idx)) = (skies, self.skydefs_index) {
+                // TODO: use .get and check that the sky exists
+                // we shouldn't run into a case where the index is out of bounds but just in case
+                let Sky {
+                    backgroundtex,
+                    sky_type,
+                    fire,
+                    foregroundtex,
+                    extra
+                } = &skies[idx];
+                if let Some(preview) = self.preview_view(&skies[idx], playpal) {
+                    properties_list.push(widget::row::with_children(vec![
+                        widget::horizontal_space().into(),
+                        preview,
+                        widget::horizontal_space().into()
+                    ]));
+                }
+                macro_rules! tex_fields {
+                    ($tex:expr, $message:expr, $prefix:expr) => {
+                        let name_input = lump_name_input("SKY1", &$tex.name, file_type, |s| $message(SkyTexMessage::ChangeName(s)));
+                        let mid_spin = widget::spin_button(
+                            $tex.mid.to_string(), $tex.mid,
+                            1, 0, 1024,
+                            |v| $message(SkyTexMessage::ChangeMid(v))
+                        );
+                        let scrollx_spin = widget::spin_button(
+                            $tex.scrollx.to_string(), $tex.scrollx,
+                            0.1, -100.0, 100.0,
+                            |v| $message(SkyTexMessage::ChangeScrollX(v))
+                        );
+                        let scrolly_spin = widget::spin_button(
+                            $tex.scrolly.to_string(), $tex.scrolly,
+                            0.1, -100.0, 100.0,
+                            |v| $message(SkyTexMessage::ChangeScrollY(v))
+                        );
+                        let scalex_spin = widget::spin_button(
+                            $tex.scalex.to_string(), $tex.scalex,
+                            0.1, 0.0, 100.0,
+                            |v| $message(SkyTexMessage::ChangeScaleX(v))
+                        );
+                        let scaley_spin = widget::spin_button(
+                            $tex.scaley.to_string(), $tex.scaley,
+                            0.1, 0.0, 100.0,
+                            |v| $message(SkyTexMessage::ChangeScaleY(v))
+                        );
+
+                        properties_list.push(aligned_row(concat!($prefix, "Texture:"), name_input));
+                        if let Some(pick) = texture_pick(&$tex.name, |s| $message(SkyTexMessage::ChangeName(s))) {
+                            properties_list.push(aligned_row("", pick));
+                        }
+                        if let Some(texture) = textures.find(&$tex.name) {
+                            let size = widget::text::body(format!("{}x{}", texture.width, texture.height));
+                            properties_list.push(aligned_row(concat!($prefix, "Texture size:"), size));
+                        }
+                        properties_list.push(aligned_row(concat!($prefix, "Mid:"), mid_spin));
+                        properties_list.push(aligned_row(concat!($prefix, "Scroll X (seconds):"), scrollx_spin));
+                        properties_list.push(aligned_row(concat!($prefix, "Scroll Y (seconds):"), scrolly_spin));
+                        properties_list.push(aligned_row(concat!($prefix, "Scale X:"), scalex_spin));
+                        properties_list.push(aligned_row(concat!($prefix, "Scale Y:"), scaley_spin));
+                    };
+                }
+
+                tex_fields!(backgroundtex, Message::UpdateSkyTexProp, "");
+                let type_pick = cosmic::iced::widget::pick_list(
+                    SkyType::VARIANTS,
+                    Some(sky_type),
+                    Message::ChangeSkyType
+                );
+                properties_list.push(aligned_row("Type:", type_pick));
+                match (sky_type, fire, foregroundtex) {
+                    (SkyType::WithForeground, _, Some(foregroundtex)) => {
+                        tex_fields!(foregroundtex, Message::UpdateSkyTexPropFG, "Foreground ");
+                    }
+                    (SkyType::Fire, Some(Fire {
+                                             updatetime,
+                                             palette,
+                                             .. }), _) => {
+                        let time_spin = widget::spin_button(
+                            (*updatetime).to_string(), *updatetime,
+                            0.1, 0.0, 100.0,
+                            Message::ChangeFireSpeed
+                        );
+                        properties_list.push(aligned_row("Animation Speed (seconds):", time_spin));
+                        properties_list.push(aligned_row("Palette:", self.fire_palette_view(palette, playpal)));
+                    }
+                    _ => ()
+                }
+                properties_list.extend(extra_fields("Extra fields", extra));
+                if let Some(foregroundtex) = foregroundtex {
+                    properties_list.extend(extra_fields("Foreground extra fields", &foregroundtex.extra));
+                }
+                if let Some(fire) = fire {
+                    properties_list.extend(extra_fields("Fire extra fields", &fire.extra));
+                }
+            } else if let (Some(flatmapping), SkydefsIndex::Flatmapping(idx)) = (flatmapping, self.skydefs_index) {
+                let skydefs::FlatMapping { flat, sky, extra } = &flatmapping[idx];
+                let flat_input = lump_name_input("F_SKY1", flat, file_type, Message::ChangeFlatmapFlat);
+                let sky_input = lump_name_input("SKY1", sky, file_type, Message::ChangeFlatmapSky);
+                properties_list.push(aligned_row("Flat:", flat_input));
+                properties_list.push(aligned_row("Sky:", sky_input));
+                if let Some(pick) = texture_pick(sky, Message::ChangeFlatmapSky) {
+                    properties_list.push(aligned_row("", pick));
+                }
+                properties_list.extend(extra_fields("Extra fields", extra));
+            }
+            let properties_list = properties_list.into_iter().fold(
+                widget::list_column(),
+                widget::ListColumn::add
+            );
+            let skies_list = skies.as_ref().map_or(
+                widget::list_column(),
+                |s| s.iter().enumerate().fold(
+                    widget::list_column(),
+                    |acc, (idx, sky)|
+                        acc.add(widget::button::text(&sky.backgroundtex.name)
+                            .on_press(Message::SelectSky(Some(idx)))
+                            .width(Length::Fill)
+                            .class(match self.skydefs_index {
+                                SkydefsIndex::Sky(i) if i == idx => widget::button::ButtonClass::Suggested,
+                                _ => widget::button::ButtonClass::Text
+                            }))
+                ));
+            let flatmapping_list = flatmapping.as_ref().map_or(
+                widget::list_column(),
+                |s| s.iter().enumerate().fold(
+                    widget::list_column(),
+                    |acc, (idx, mapping)|
+                        acc.add(widget::button::text(&mapping.flat)
+                            .on_press(Message::SelectFlatmapping(Some(idx)))
+                            .width(Length::Fill)
+                            .class(match self.skydefs_index {
+                                SkydefsIndex::Flatmapping(i) if i == idx => widget::button::ButtonClass::Suggested,
+                                _ => widget::button::ButtonClass::Text
+                            }))
+                ));
+            let content = widget::row::with_children(vec![
+                widget::container(widget::scrollable(properties_list))
+                    .width(Length::FillPortion(2))
+                    .into(),
+
+                widget::divider::vertical::heavy().into(),
+
+                widget::container(
+                    widget::column::with_children(vec![
+                        widget::row::with_children(vec![
+                            widget::button::text("New Sky").on_press(Message::NewSky).into(),
+                            widget::horizontal_space().into(),
+                            widget::button::text("Delete").on_press_maybe(match self.skydefs_index {
+                                SkydefsIndex::Sky(size) => Some(Message::DeleteSky(size)),
+                                _ => None
+                            }).into(),
+                        ]).into(),
+                        widget::container(widget::scrollable(skies_list))
+                            .height(Length::FillPortion(1))
+                            .into(),
+                        widget::divider::horizontal::heavy().into(),
+                        widget::row::with_children(vec![
+                            widget::button::text("New Flat Mapping").on_press(Message::NewFlatmapping).into(),
+                            widget::horizontal_space().into(),
+                            widget::button::text("Delete").on_press_maybe(match self.skydefs_index {
+                                SkydefsIndex::Flatmapping(size) => Some(Message::DeleteFlatmapping(size)),
+                                _ => None
+                            }).into(),
+                        ]).into(),
+                        widget::container(widget::scrollable(flatmapping_list))
+                            .height(Length::FillPortion(1))
+                            .into(),
+                    ]).spacing(5)
+                )
+                    .width(Length::FillPortion(1))
+                    .into(),
+            ])
+                .padding(10)
+                .spacing(10);
+
+            widget::container(content)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        } else {
+            // TODO: figure out a better way to handle this
+            widget::container(widget::text::heading("You shouldn't be here."))
+                .center_x(Length::Fill)
+                .center_y(Length::Fill)
+                .into()
+        }
+    }
+
+    pub fn update(&mut self, json: &mut ID24Json, message: Message) -> Task<cosmic::Action<Message>> {
+        match message {
+            Message::SelectSky(Some(idx)) => {
+                self.skydefs_index = SkydefsIndex::Sky(idx);
+                self.fire_colour = None;
+            },
+            Message::SelectFlatmapping(Some(idx)) => {
+                self.skydefs_index = SkydefsIndex::Flatmapping(idx);
+            },
+            Message::SelectSky(None) | Message::SelectFlatmapping(None) => {
+                self.skydefs_index = SkydefsIndex::None;
+            },
+            Message::Tick(now) => {
+                let elapsed = self.preview.now.map_or(0.0, |before| now.saturating_duration_since(before).as_secs_f32());
+                self.preview.now = Some(now);
+                if let Some(updatetime) = self.selected_sky(json).and_then(|sky| sky.fire.as_ref()).map(|fire| fire.updatetime) {
+                    self.burn(updatetime, elapsed);
+                }
+            },
+            Message::SelectFireColour(idx) => self.fire_colour = Some(idx),
+            Message::SetFireColour(index) => {
+                let selected = self.fire_colour;
+                if let Some(fire) = self.selected_fire(json) {
+                    match selected.filter(|idx| *idx < fire.palette.len()) {
+                        Some(idx) => fire.palette[idx] = index,
+                        // with nothing selected, picking a colour adds it to the end
+                        None => {
+                            fire.palette.push(index);
+                            self.fire_colour = Some(fire.palette.len() - 1);
+                        }
+                    }
+                }
+            },
+            Message::InsertFireColour => {
+                let selected = self.fire_colour;
+                if let Some(fire) = self.selected_fire(json) {
+                    // a copy of the selected colour goes after it, or of the last one at the end
+                    // so a ramp can be stretched
+                    let idx = selected.map_or(fire.palette.len(), |idx| idx + 1).min(fire.palette.len());
+                    let index = idx.checked_sub(1).map_or(0, |prev| fire.palette[prev]);
+                    fire.palette.insert(idx, index);
+                    self.fire_colour = Some(idx);
+                }
+            },
+            Message::RemoveFireColour => {
+                let selected = self.fire_colour;
+                if let Some(fire) = self.selected_fire(json)
+                    && let Some(idx) = selected.filter(|idx| *idx < fire.palette.len()) {
+                    fire.palette.remove(idx);
+                    self.fire_colour = fire.palette.len().checked_sub(1).map(|last| idx.min(last));
+                }
+            },
+            Message::MoveFireColourEarlier => {
+                let selected = self.fire_colour;
+                if let Some(fire) = self.selected_fire(json)
+                    && let Some(idx) = selected.filter(|idx| *idx > 0 && *idx < fire.palette.len()) {
+                    fire.palette.swap(idx - 1, idx);
+                    self.fire_colour = Some(idx - 1);
+                }
+            },
+            Message::MoveFireColourLater => {
+                let selected = self.fire_colour;
+                if let Some(fire) = self.selected_fire(json)
+                    && let Some(idx) = selected.filter(|idx| idx + 1 < fire.palette.len()) {
+                    fire.palette.swap(idx, idx + 1);
+                    self.fire_colour = Some(idx + 1);
+                }
+            },
+            Message::ResetFirePalette => {
+                if let Some(fire) = self.selected_fire(json) {
+                    fire.palette = skydefs::PSX_FIRE_PALETTE.to_vec();
+                }
+                self.fire_colour = None;
+            },
+            Message::NewSky => {
+                if let ID24JsonData::SKYDEFS { skies, .. } = &mut json.data {
+                    skies.get_or_insert_with(Vec::new).push(Sky::default());
+                }
+            },
+            Message::NewFlatmapping => {
+                if let ID24JsonData::SKYDEFS { flatmapping, .. } = &mut json.data {
+                    flatmapping.get_or_insert_with(Vec::new).push(skydefs::FlatMapping::default());
+                }
+            },
+            Message::DeleteSky(idx) => {
+                // TODO: make this async in case the list is very large
+                if let ID24JsonData::SKYDEFS { skies: Some(skies), .. } = &mut json.data {
+                    self.skydefs_index = SkydefsIndex::None;
+                    skies.remove(idx);
+                }
+            },
+            Message::DeleteFlatmapping(idx) => {
+                if let ID24JsonData::SKYDEFS { flatmapping: Some(flatmapping), .. } = &mut json.data {
+                    self.skydefs_index = SkydefsIndex::None;
+                    flatmapping.remove(idx);
+                }
+            },
+            Message::UpdateSkyTexProp(skymessage) => {
+                if let (ID24JsonData::SKYDEFS { skies: Some(skies), .. }, SkydefsIndex::Sky(idx)) = (&mut json.data, self.skydefs_index) {
+                    match skymessage {
+                        SkyTexMessage::ChangeName(name) => skies[idx].backgroundtex.name = name,
+                        SkyTexMessage::ChangeMid(mid) => skies[idx].backgroundtex.mid = mid,
+                        SkyTexMessage::ChangeScaleX(scale) => skies[idx].backgroundtex.scalex = scale,
+                        SkyTexMessage::ChangeScaleY(scale) => skies[idx].backgroundtex.scaley = scale,
+                        SkyTexMessage::ChangeScrollX(scroll) => skies[idx].backgroundtex.scrollx = scroll,
+                        SkyTexMessage::ChangeScrollY(scroll) => skies[idx].backgroundtex.scrolly = scroll,
+                    }
+                }
+            },
+            Message::UpdateSkyTexPropFG(skymessage) => {
+                if let (ID24JsonData::SKYDEFS { skies: Some(skies), .. }, SkydefsIndex::Sky(idx)) = (&mut json.data, self.skydefs_index) {
+                    if let Some(foreground) = &mut skies[idx].foregroundtex {
+                        match skymessage {
+                            SkyTexMessage::ChangeName(name) => foreground.name = name,
+                            SkyTexMessage::ChangeMid(mid) => foreground.mid = mid,
+                            SkyTexMessage::ChangeScaleX(scale) => foreground.scalex = scale,
+                            SkyTexMessage::ChangeScaleY(scale) => foreground.scaley = scale,
+                            SkyTexMessage::ChangeScrollX(scroll) => foreground.scrollx = scroll,
+                            SkyTexMessage::ChangeScrollY(scroll) => foreground.scrolly = scroll,
+                        }
+                    }
+                }
+            },
+            Message::ChangeFireSpeed(speed) => {
+                if let (ID24JsonData::SKYDEFS { skies: Some(skies), .. }, SkydefsIndex::Sky(idx)) = (&mut json.data, self.skydefs_index) {
+                    if let Some(fire) = &mut skies[idx].fire {
+                        fire.updatetime = speed;
+                    }
+                }
+            },
+            Message::ChangeSkyType(sky_type) => {
+                if let (ID24JsonData::SKYDEFS { skies: Some(skies), .. }, SkydefsIndex::Sky(idx)) = (&mut json.data, self.skydefs_index) {
+                    let sky = &mut skies[idx];
+                    sky.sky_type = sky_type;
+                    match sky_type {
+                        SkyType::Standard => {
+                            sky.foregroundtex = None;
+                            sky.fire = None;
+                        },
+                        SkyType::WithForeground => {
+                            sky.foregroundtex = Some(SkyTex::default());
+                            sky.fire = None;
+                        },
+                        SkyType::Fire => {
+                            sky.foregroundtex = None;
+                            sky.fire = Some(Fire::default());
+                        }
+                    }
+                }
+            },
+            Message::ChangeFlatmapFlat(flat) => {
+                if let (ID24JsonData::SKYDEFS { flatmapping: Some(flatmaps), .. }, SkydefsIndex::Flatmapping(idx)) = (&mut json.data, self.skydefs_index) {
+                    flatmaps[idx].flat = flat;
+                }
+            },
+            Message::ChangeFlatmapSky(sky) => {
+                if let (ID24JsonData::SKYDEFS { flatmapping: Some(flatmaps), .. }, SkydefsIndex::Flatmapping(idx)) = (&mut json.data, self.skydefs_index) {
+                    flatmaps[idx].sky = sky;
+                }
+            },
+            _ => ()
+        }
+
+        Task::none()
+    }
+}
@@ -0,0 +1,151 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use cosmic::prelude::*;
+use cosmic::iced::{Alignment, Length};
+use cosmic::widget;
+use id24json::{ID24Json, ID24JsonData};
+use id24json::translation;
+use crate::widgets::{aligned_row, extra_fields};
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    ChangeName(String),
+    ToggleSbarTranslation(bool),
+    ChangeEntry(usize, u8),
+    ChangeSourceStart(u8),
+    ChangeSourceEnd(u8),
+    ChangeDestStart(u8),
+    ChangeDestEnd(u8),
+    ApplyRemap,
+    ResetTable,
+}
+
+// the range remap being built, kept between edits so the same range can be tried out on different tables
+pub struct Page {
+    source: (u8, u8),
+    dest: (u8, u8),
+}
+
+impl Default for Page {
+    fn default() -> Self {
+        // doom's green player colours
+        Self {
+            source: (112, 127),
+            dest: (112, 127),
+        }
+    }
+}
+
+fn index_spin<'a>(value: u8, on_change: impl Fn(u8) -> Message + 'static) -> Element<'a, Message> {
+    widget::spin_button(value.to_string(), value, 1, 0, u8::MAX, on_change).into()
+}
+
+impl Page {
+    pub fn view<'a>(&'a self, json: &'a ID24Json) -> Element<'a, Message> {
+        if let ID24JsonData::Translation { name, sbartranslation, table, extra } = &json.data {
+            let name_input = widget::text_input("Green to red", name)
+                .on_input(Message::ChangeName);
+            let sbar_toggle = widget::toggler(*sbartranslation)
+                .on_toggle(Message::ToggleSbarTranslation);
+            let source_row = widget::row()
+                .push(index_spin(self.source.0, Message::ChangeSourceStart))
+                .push(widget::text::body("to"))
+                .push(index_spin(self.source.1, Message::ChangeSourceEnd))
+                .spacing(8)
+                .align_y(Alignment::Center);
+            let dest_row = widget::row()
+                .push(index_spin(self.dest.0, Message::ChangeDestStart))
+                .push(widget::text::body("to"))
+                .push(index_spin(self.dest.1, Message::ChangeDestEnd))
+                .spacing(8)
+                .align_y(Alignment::Center);
+            let buttons = widget::row()
+                .push(widget::button::standard("Reset").on_press(Message::ResetTable))
+                .push(widget::button::suggested("Apply").on_press(Message::ApplyRemap))
+                .spacing(8);
+
+            let mut properties_list = vec![
+                aligned_row("Name:", name_input),
+                aligned_row("Status bar translation:", sbar_toggle),
+                aligned_row("Remap from:", source_row),
+                aligned_row("Remap to:", dest_row),
+                aligned_row("", buttons),
+            ];
+            properties_list.extend(extra_fields("Extra fields", extra));
+
+            let properties_list = properties_list.into_iter().fold(
+                widget::list_column(),
+                widget::ListColumn::add
+            );
+
+            let table_list = table.iter().enumerate().fold(
+                widget::list_column(),
+                |acc, (idx, value)|
+                    acc.add(widget::row()
+                        .push(widget::text::heading(idx.to_string()))
+                        .push(widget::horizontal_space())
+                        .push(index_spin(*value, move |value| Message::ChangeEntry(idx, value)))
+                        .align_y(Alignment::Center))
+            );
+
+            let content = widget::row::with_children(vec![
+                widget::container(widget::scrollable(properties_list))
+                    .width(Length::FillPortion(2))
+                    .into(),
+                widget::divider::vertical::heavy().into(),
+                widget::container(widget::scrollable(table_list))
+                    .width(Length::FillPortion(1))
+                    .into(),
+            ]);
+
+            widget::container(content)
+                .width(Length::Fill)
+                .height(Length::Fill)
+                .into()
+        } else {
+            widget::container(widget::text::heading("You shouldn't be here."))
+                .center_x(Length::Fill)
+                .center_y(Length::Fill)
+                .into()
+        }
+    }
+
+    pub fn update(&mut self, json: &mut ID24Json, message: Message) -> Task<cosmic::Action<Message>> {
+        match message {
+            Message::ChangeSourceStart(idx) => self.source.0 = idx,
+            Message::ChangeSourceEnd(idx) => self.source.1 = idx,
+            Message::ChangeDestStart(idx) => self.dest.0 = idx,
+            Message::ChangeDestEnd(idx) => self.dest.1 = idx,
+            message => {
+                let ID24JsonData::Translation { name, sbartranslation, table, .. } = &mut json.data else {
+                    return Task::none();
+                };
+                match message {
+                    Message::ChangeName(new_name) => *name = new_name,
+                    Message::ToggleSbarTranslation(b) => *sbartranslation = b,
+                    Message::ChangeEntry(idx, value) => {
+                        if let Some(entry) = table.get_mut(idx) {
+                            *entry = value;
+                        }
+                    },
+                    Message::ApplyRemap => translation::remap(table, self.source, self.dest),
+                    Message::ResetTable => *table = translation::identity(),
+                    _ => ()
+                }
+            }
+        }
+        Task::none()
+    }
+}
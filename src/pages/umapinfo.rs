@@ -0,0 +1,141 @@
+/*
+ * Copyright (C) 2025  Mia McMahill
+ *
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ *
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+use cosmic::prelude::*;
+use cosmic::iced::Length;
+use cosmic::widget;
+use id24json::umapinfo::{MapEntry, UMapInfo};
+use id24json::diagnostics::pointer_tokens;
+use crate::widgets::aligned_row;
+
+// keys that get their own input, anything else in a map is shown read only
+const EDITABLE_KEYS: &[(&str, &str)] = &[
+    ("levelname",  "Level name:"),
+    ("next",       "Next map:"),
+    ("nextsecret", "Secret exit map:"),
+    ("music",      "Music lump:"),
+    ("enteranim",  "Enter animation:"),
+    ("exitanim",   "Exit animation:"),
+    ("endfinale",  "End finale:"),
+];
+
+#[derive(Debug, Clone)]
+pub enum Message {
+    NewMap,
+    SelectMap(Option<usize>),
+    ChangeMapName(String),
+    // an empty value removes the key
+    ChangeField(&'static str, String),
+}
+
+#[derive(Default)]
+pub struct Page {
+    index: Option<usize>,
+}
+
+impl Page {
+    pub fn reset_index(&mut self) {
+        self.index = None;
+    }
+
+    // diagnostic paths start with the map name, e.g. /MAP01/enteranim
+    pub fn focus(&mut self, umapinfo: &UMapInfo, path: &str) {
+        if let Some(name) = pointer_tokens(path).next() {
+            self.index = umapinfo.maps.iter().position(|map| map.name.eq_ignore_ascii_case(&name));
+        }
+    }
+
+    pub fn view<'a>(&'a self, umapinfo: &'a UMapInfo) -> Element<'a, Message> {
+        let mut properties_list = Vec::new();
+        if let Some(map) = self.index.and_then(|idx| umapinfo.maps.get(idx)) {
+            properties_list.push(aligned_row("Map:", widget::text_input("MAP01", &map.name)
+                .on_input(Message::ChangeMapName)));
+            for &(key, label) in EDITABLE_KEYS {
+                let input = widget::text_input("", map.get_string(key).unwrap_or(""))
+                    .on_input(move |value| Message::ChangeField(key, value));
+                properties_list.push(aligned_row(label, input));
+            }
+            let other_fields: Vec<_> = map.fields.iter()
+                .filter(|field| !EDITABLE_KEYS.iter().any(|(key, _)| field.key.eq_ignore_ascii_case(key)))
+                .collect();
+            if !other_fields.is_empty() {
+                properties_list.push(widget::row().push(widget::text::heading("Other keys")));
+            }
+            for field in other_fields {
+                let values = field.values.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ");
+                properties_list.push(aligned_row(&field.key, widget::text::body(values)));
+            }
+        }
+
+        let properties_list = properties_list.into_iter().fold(
+            widget::list_column(),
+            widget::ListColumn::add
+        );
+
+        let maps_list = umapinfo.maps.iter().enumerate().fold(
+            widget::list_column(),
+            |acc, (idx, map)|
+                acc.add(widget::button::text(&map.name)
+                    .on_press(Message::SelectMap(Some(idx)))
+                    .width(Length::Fill)
+                    .class(match self.index {
+                        Some(i) if i == idx => widget::button::ButtonClass::Suggested,
+                        _ => widget::button::ButtonClass::Text
+                    }))
+        );
+
+        let maps_list = maps_list.add(widget::button::text("New Map")
+            .on_press(Message::NewMap)
+            .width(Length::Fill)
+            .class(widget::button::ButtonClass::Text));
+
+        let content = widget::row::with_children(vec![
+            widget::container(widget::scrollable(properties_list))
+                .width(Length::FillPortion(2))
+                .into(),
+            widget::divider::vertical::heavy().into(),
+            widget::container(widget::scrollable(maps_list))
+                .width(Length::FillPortion(1))
+                .into(),
+        ]);
+
+        widget::container(content)
+            .width(Length::Fill)
+            .height(Length::Fill)
+            .into()
+    }
+
+    pub fn update(&mut self, umapinfo: &mut UMapInfo, message: Message) -> Task<cosmic::Action<Message>> {
+        match message {
+            Message::NewMap => {
+                umapinfo.maps.push(MapEntry::new(format!("MAP{:02}", umapinfo.maps.len() + 1)));
+                self.index = Some(umapinfo.maps.len() - 1);
+            },
+            Message::SelectMap(idx) => {
+                self.index = idx;
+            },
+            Message::ChangeMapName(name) => {
+                if let Some(map) = self.index.and_then(|idx| umapinfo.maps.get_mut(idx)) {
+                    map.name = name;
+                }
+            },
+            Message::ChangeField(key, value) => {
+                if let Some(map) = self.index.and_then(|idx| umapinfo.maps.get_mut(idx)) {
+                    map.set_string(key, (!value.is_empty()).then_some(value));
+                }
+            },
+        }
+        Task::none()
+    }
+}
@@ -1,29 +1,57 @@
-/*
- * Copyright (C) 2025  Mia McMahill
- * 
- * This program is free software: you can redistribute it and/or modify
- * it under the terms of the GNU General Public License as published by
- * the Free Software Foundation, either version 3 of the License, or
- * (at your option) any later version.
- * 
- * This program is distributed in the hope that it will be useful,
- * but WITHOUT ANY WARRANTY; without even the implied warranty of
- * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
- * GNU General Public License for more details.
- */
-
-mod select_list;
-
-use cosmic::{widget, Element};
-use cosmic::iced::Alignment;
-
-pub fn aligned_row<'a, Message: 'a>(
-    label: &'a str,
-    widget: impl Into<Element<'a, Message>>,
-) -> widget::Row<'a, Message> {
-    widget::row()
-        .push(widget::text::heading(label))
-        .push(widget::horizontal_space())
-        .push(widget.into())
-        .align_y(Alignment::Center)
-}
\ No newline at end of file
+/*
+ * Copyright (C) 2025  Mia McMahill
+ * 
+ * This program is free software: you can redistribute it and/or modify
+ * it under the terms of the GNU General Public License as published by
+ * the Free Software Foundation, either version 3 of the License, or
+ * (at your option) any later version.
+ * 
+ * This program is distributed in the hope that it will be useful,
+ * but WITHOUT ANY WARRANTY; without even the implied warranty of
+ * MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE.  See the
+ * GNU General Public License for more details.
+ */
+
+mod select_list;
+
+use cosmic::{widget, Element};
+use cosmic::iced::Alignment;
+use id24json::Extra;
+use id24json::references::filter_wad_lump_name;
+use crate::config::FileType;
+
+pub fn aligned_row<'a, Message: 'a>(
+    label: &'a str,
+    widget: impl Into<Element<'a, Message>>,
+) -> widget::Row<'a, Message> {
+    widget::row()
+        .push(widget::text::heading(label))
+        .push(widget::horizontal_space())
+        .push(widget.into())
+        .align_y(Alignment::Center)
+}
+
+// in WAD mode lump names are upper cased and cut off at 8 characters as they're typed
+pub fn lump_name_input<'a, Message: Clone + 'a>(
+    placeholder: &'a str,
+    value: &'a str,
+    file_type: FileType,
+    on_input: impl Fn(String) -> Message + 'a,
+) -> widget::text_input::TextInput<'a, Message> {
+    widget::text_input(placeholder, value).on_input(move |input| match file_type {
+        FileType::Wad => on_input(filter_wad_lump_name(&input)),
+        FileType::Zip => on_input(input),
+    })
+}
+
+// read only rows for the keys the editor doesn't understand, they are written back untouched on save
+pub fn extra_fields<'a, Message: 'a>(heading: &'a str, extra: &'a Extra) -> Vec<widget::Row<'a, Message>> {
+    if extra.is_empty() {
+        return Vec::new();
+    }
+    let mut rows = vec![widget::row().push(widget::text::heading(heading))];
+    rows.extend(extra.iter().map(|(key, value)|
+        aligned_row(key, widget::text::body(value.to_string()))
+    ));
+    rows
+}